@@ -1,5 +1,5 @@
 use super::*;
-use crate::brew::ServiceEntry;
+use crate::brew::{DeprecatedPackage, Deprecation, DoctorIssue, ServiceEntry};
 
 impl App {
     pub fn is_cask_mode(&self) -> bool {
@@ -10,6 +10,26 @@ impl App {
         self.outdated_leaves.contains(pkg)
     }
 
+    /// Deprecation notice for an installed package from the last status check.
+    pub fn installed_deprecation(&self, pkg: &str, kind: PackageKind) -> Option<&Deprecation> {
+        self.deprecated_packages()
+            .iter()
+            .find(|entry| entry.name == pkg && entry.cask == (kind == PackageKind::Cask))
+            .map(|entry| &entry.deprecation)
+    }
+
+    /// Prefers the package's own details, which are fresher than the status check.
+    pub fn deprecation_for(&self, pkg: &str, kind: PackageKind) -> Option<&Deprecation> {
+        match self.details_cache.peek(pkg) {
+            Some(details) => details.deprecation.as_ref(),
+            None => self.installed_deprecation(pkg, kind),
+        }
+    }
+
+    pub fn deprecated_packages(&self) -> &[DeprecatedPackage] {
+        &self.deprecated_packages
+    }
+
     pub fn toggle_outdated_filter(&mut self) {
         if self.is_cask_mode() {
             self.status = "Outdated filter only applies to formulae".to_string();
@@ -62,6 +82,24 @@ impl App {
         snapshot.services.get(selected)
     }
 
+    /// Services a `--all` action would change, ignoring the list filters.
+    pub fn services_affected_by(&self, action: ServiceAction) -> Vec<String> {
+        let Some(snapshot) = self.system_status.as_ref() else {
+            return Vec::new();
+        };
+        snapshot
+            .services
+            .iter()
+            .filter(|service| match action {
+                ServiceAction::Start | ServiceAction::Run => !service.is_running(),
+                ServiceAction::Stop => service.is_running() || service.auto_start_enabled(),
+                ServiceAction::Kill => service.is_running(),
+                ServiceAction::Restart => true,
+            })
+            .map(|service| service.name.clone())
+            .collect()
+    }
+
     pub fn filtered_service_indices(&self) -> Vec<usize> {
         let Some(snapshot) = self.system_status.as_ref() else {
             return Vec::new();
@@ -107,6 +145,166 @@ impl App {
         self.status_scroll_offset = 0;
     }
 
+    pub fn selected_issue(&self) -> Option<&DoctorIssue> {
+        let selected = self.issues_selected_index?;
+        self.system_status.as_ref()?.doctor_issues.get(selected)
+    }
+
+    pub fn selected_issue_command(&self) -> Option<&str> {
+        self.selected_issue()?
+            .commands
+            .get(self.issue_command_index)
+            .map(String::as_str)
+    }
+
+    pub fn select_next_issue(&mut self) {
+        self.step_issue_selection(StepDirection::Next);
+    }
+
+    pub fn select_prev_issue(&mut self) {
+        self.step_issue_selection(StepDirection::Prev);
+    }
+
+    /// Deprecated packages follow the doctor issues in the Issues selection.
+    pub fn selected_deprecated_package(&self) -> Option<&DeprecatedPackage> {
+        let doctor_count = self.system_status.as_ref()?.doctor_issues.len();
+        let selected = self.issues_selected_index?.checked_sub(doctor_count)?;
+        self.deprecated_packages().get(selected)
+    }
+
+    /// Whether Details shows the Issues tab selection instead of a package.
+    pub fn details_shows_issue(&self) -> bool {
+        self.status_tab == StatusTab::Issues
+            && (self.selected_issue().is_some() || self.selected_deprecated_package().is_some())
+    }
+
+    fn issue_entry_count(&self) -> usize {
+        self.system_status
+            .as_ref()
+            .map_or(0, |snapshot| snapshot.doctor_issues.len())
+            + self.deprecated_packages.len()
+    }
+
+    /// Row of an Issues entry: a placeholder stands in for zero doctor issues
+    /// and the deprecated section has a heading.
+    fn issue_row(&self, index: usize) -> usize {
+        let doctor_count = self
+            .system_status
+            .as_ref()
+            .map_or(0, |snapshot| snapshot.doctor_issues.len());
+        if index < doctor_count {
+            index
+        } else {
+            doctor_count.max(1) + 1 + (index - doctor_count)
+        }
+    }
+
+    pub(super) fn issues_row_count(&self) -> usize {
+        let deprecated = self.deprecated_packages().len();
+        let doctor_rows = self
+            .system_status
+            .as_ref()
+            .map_or(0, |snapshot| snapshot.doctor_issues.len())
+            .max(1);
+        if deprecated == 0 {
+            doctor_rows
+        } else {
+            doctor_rows + 1 + deprecated
+        }
+    }
+
+    pub fn reconcile_issue_selection(&mut self) {
+        let count = self.issue_entry_count();
+        self.issues_selected_index = match self.issues_selected_index {
+            _ if count == 0 => None,
+            Some(selected) if selected < count => Some(selected),
+            _ => Some(0),
+        };
+        let commands = self
+            .selected_issue()
+            .map_or(0, |issue| issue.commands.len());
+        if self.issue_command_index >= commands {
+            self.issue_command_index = 0;
+        }
+    }
+
+    /// Moves the command highlight in the issue details; false when there is nothing to move.
+    pub fn select_next_issue_command(&mut self) -> bool {
+        self.step_issue_command(StepDirection::Next)
+    }
+
+    pub fn select_prev_issue_command(&mut self) -> bool {
+        self.step_issue_command(StepDirection::Prev)
+    }
+
+    fn step_issue_command(&mut self, direction: StepDirection) -> bool {
+        if self.status_tab != StatusTab::Issues {
+            return false;
+        }
+        let commands = self
+            .selected_issue()
+            .map_or(0, |issue| issue.commands.len());
+        if commands == 0 {
+            return false;
+        }
+        self.issue_command_index =
+            step_position(Some(self.issue_command_index), commands, direction);
+        true
+    }
+
+    /// Whether Details shows the selected installed package rather than a search
+    /// result, service, or doctor issue.
+    pub fn details_shows_installed_package(&self) -> bool {
+        !matches!(
+            self.input_mode,
+            InputMode::PackageSearch | InputMode::PackageResults
+        ) && self.view_mode == ViewMode::Details
+            && self.status_tab != StatusTab::Services
+            && !self.details_shows_issue()
+    }
+
+    /// The formula whose installed versions Details lists, once its details are loaded.
+    pub fn keg_formula(&self) -> Option<(&str, &Details)> {
+        if self.is_cask_mode() || !self.details_shows_installed_package() {
+            return None;
+        }
+        let pkg = self.selected_installed_package()?;
+        let details = self.details_cache.peek(pkg)?;
+        (!details.is_cask() && !details.installed.is_empty()).then_some((pkg, details))
+    }
+
+    pub fn selected_keg(&self) -> Option<&str> {
+        let (_, details) = self.keg_formula()?;
+        details
+            .installed
+            .get(self.keg_selected_index)
+            .map(String::as_str)
+    }
+
+    /// Moves the installed-version highlight; false when there is only one version.
+    pub fn select_next_keg(&mut self) -> bool {
+        self.step_keg(StepDirection::Next)
+    }
+
+    pub fn select_prev_keg(&mut self) -> bool {
+        self.step_keg(StepDirection::Prev)
+    }
+
+    fn step_keg(&mut self, direction: StepDirection) -> bool {
+        let count = self
+            .keg_formula()
+            .map_or(0, |(_, details)| details.installed.len());
+        if count < 2 {
+            return false;
+        }
+        self.keg_selected_index = step_position(
+            Some(self.keg_selected_index.min(count - 1)),
+            count,
+            direction,
+        );
+        true
+    }
+
     pub fn toggle_services_failed_filter(&mut self) {
         self.services_failed_only = !self.services_failed_only;
         self.reconcile_service_selection();
@@ -145,6 +343,105 @@ impl App {
         )
     }
 
+    /// Indices into `command_history` that pass the search and filters, newest first.
+    pub fn filtered_history_indices(&self) -> Vec<usize> {
+        let query = self.history_query.trim();
+        let query_lower = (!query.is_ascii()).then(|| query.to_lowercase());
+
+        self.command_history
+            .iter()
+            .enumerate()
+            .filter(|(_, record)| self.history_outcome_filter.matches(record.success))
+            .filter(|(_, record)| {
+                self.history_kind_filter
+                    .as_ref()
+                    .is_none_or(|kind| &record.kind == kind)
+            })
+            .filter(|(_, record)| {
+                query.is_empty()
+                    || [
+                        record.command_line().as_str(),
+                        &record.stdout,
+                        &record.stderr,
+                    ]
+                    .iter()
+                    .any(|text| {
+                        leaf_matches_query(text, query, query_lower.as_deref(), query.is_ascii())
+                    })
+            })
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    pub fn selected_history_record(&self) -> Option<&CommandRecord> {
+        self.command_history.get(self.history_selected_index?)
+    }
+
+    pub fn select_next_history(&mut self) {
+        self.step_history_selection(StepDirection::Next);
+    }
+
+    pub fn select_prev_history(&mut self) {
+        self.step_history_selection(StepDirection::Prev);
+    }
+
+    pub fn reconcile_history_selection(&mut self) {
+        let filtered = self.filtered_history_indices();
+        if let Some(selected) = self.history_selected_index
+            && let Some(position) = filtered.iter().position(|candidate| *candidate == selected)
+        {
+            if self.status_tab == StatusTab::History {
+                self.status_scroll_offset = position;
+            }
+            return;
+        }
+
+        self.history_selected_index = filtered.first().copied();
+        if self.status_tab == StatusTab::History {
+            self.status_scroll_offset = 0;
+        }
+    }
+
+    /// Steps through the kinds present in the history, then back to all kinds.
+    pub fn cycle_history_kind_filter(&mut self) {
+        let mut kinds: Vec<&str> = self
+            .command_history
+            .iter()
+            .map(|record| record.kind.as_str())
+            .collect();
+        kinds.sort_unstable();
+        kinds.dedup();
+
+        let next = match self.history_kind_filter.as_deref() {
+            None => kinds.first().copied(),
+            Some(current) => kinds
+                .iter()
+                .position(|kind| *kind == current)
+                .and_then(|position| kinds.get(position + 1))
+                .copied(),
+        };
+        self.history_kind_filter = next.map(str::to_string);
+        self.reconcile_history_selection();
+        self.status = format!("History filter: {}", self.history_filter_summary());
+        self.last_refresh = Instant::now();
+    }
+
+    pub fn cycle_history_outcome_filter(&mut self) {
+        self.history_outcome_filter = self.history_outcome_filter.next();
+        self.reconcile_history_selection();
+        self.status = format!("History filter: {}", self.history_filter_summary());
+        self.last_refresh = Instant::now();
+    }
+
+    pub fn history_filter_summary(&self) -> String {
+        let kind = self.history_kind_filter.as_deref().unwrap_or("all kinds");
+        let mut summary = format!("{kind}, {}", self.history_outcome_filter.label());
+        if !self.history_query.trim().is_empty() {
+            summary.push_str(&format!(", \"{}\"", self.history_query.trim()));
+        }
+        summary
+    }
+
     pub fn is_service_cask_backed(&self, service_name: &str) -> bool {
         self.casks
             .binary_search_by(|candidate| candidate.as_str().cmp(service_name))
@@ -280,6 +577,39 @@ impl App {
         self.status_scroll_offset = next_pos;
     }
 
+    fn step_issue_selection(&mut self, direction: StepDirection) {
+        let count = self.issue_entry_count();
+        if count == 0 {
+            self.issues_selected_index = None;
+            self.status_scroll_offset = 0;
+            return;
+        }
+
+        let next = step_position(self.issues_selected_index, count, direction);
+        if self.issues_selected_index != Some(next) {
+            self.issue_command_index = 0;
+            self.details_scroll_offset = 0;
+        }
+        self.issues_selected_index = Some(next);
+        self.status_scroll_offset = self.issue_row(next);
+    }
+
+    fn step_history_selection(&mut self, direction: StepDirection) {
+        let filtered = self.filtered_history_indices();
+        if filtered.is_empty() {
+            self.history_selected_index = None;
+            self.status_scroll_offset = 0;
+            return;
+        }
+
+        let current_pos = self
+            .history_selected_index
+            .and_then(|selected| filtered.iter().position(|candidate| *candidate == selected));
+        let next_pos = step_position(current_pos, filtered.len(), direction);
+        self.history_selected_index = filtered.get(next_pos).copied();
+        self.status_scroll_offset = next_pos;
+    }
+
     fn service_matches_filters(&self, service: &ServiceEntry) -> bool {
         if self.services_failed_only && !service.has_failed() {
             return false;
@@ -395,7 +725,8 @@ fn contains_ascii_case_insensitive(haystack: &[u8], needle: &[u8]) -> bool {
 
 #[cfg(test)]
 mod tests {
-    use super::{contains_ascii_case_insensitive, leaf_matches_query};
+    use super::{App, ServiceAction, contains_ascii_case_insensitive, leaf_matches_query};
+    use crate::brew::{ServiceEntry, StatusSnapshot};
 
     #[test]
     fn matches_ascii_query_case_insensitively() {
@@ -419,4 +750,51 @@ mod tests {
         ));
         assert!(leaf_matches_query("naive", "NAIVE", Some("naive"), false));
     }
+
+    #[test]
+    fn service_actions_affect_only_services_they_change() {
+        let service = |name: &str, status: &str, file: Option<&str>| ServiceEntry {
+            name: name.to_string(),
+            status: status.to_string(),
+            user: None,
+            file: file.map(str::to_string),
+            exit_code: None,
+        };
+        let mut app = App::new();
+        app.system_status = Some(StatusSnapshot {
+            services: vec![
+                service(
+                    "redis",
+                    "started",
+                    Some("/home/me/.config/systemd/user/homebrew.redis.service"),
+                ),
+                service("memcached", "started", None),
+                service(
+                    "nginx",
+                    "none",
+                    Some("/home/me/.config/systemd/user/homebrew.nginx.service"),
+                ),
+                service("unbound", "none", None),
+            ],
+            ..StatusSnapshot::default()
+        });
+
+        assert_eq!(
+            app.services_affected_by(ServiceAction::Start),
+            ["nginx", "unbound"]
+        );
+        assert_eq!(
+            app.services_affected_by(ServiceAction::Run),
+            ["nginx", "unbound"]
+        );
+        assert_eq!(
+            app.services_affected_by(ServiceAction::Stop),
+            ["redis", "memcached", "nginx"]
+        );
+        assert_eq!(
+            app.services_affected_by(ServiceAction::Kill),
+            ["redis", "memcached"]
+        );
+        assert_eq!(app.services_affected_by(ServiceAction::Restart).len(), 4);
+    }
 }
@@ -1,40 +1,55 @@
 mod filters;
+mod pager;
 mod reducers;
 mod requests;
 mod state;
 mod types;
 
+pub use pager::{log_highlight, match_ranges, output_highlight};
 pub use types::{
-    CommandHistoryEntry, FocusedPanel, IconMode, InputMode, PackageAction, PackageKind,
-    PendingPackageAction, PendingServiceAction, ServiceAction, ServiceKindFilter, StatusTab, Toast,
+    FocusedPanel, HistoryOutcome, IconMode, InputMode, KegAction, LogTail, Migration,
+    OutputHighlight, PackageAction, PackageKind, Pager, PagerLine, PagerLineKind, PendingKegAction,
+    PendingMigration, PendingPackageAction, PendingServiceAction, PendingZap, ServiceAction,
+    ServiceKindFilter, ServiceTarget, StaleData, StatusTab, TailedFile, ThemePicker, Toast,
     ToastLevel, ViewMode,
 };
 
 use std::collections::{HashSet, VecDeque};
 use std::num::NonZeroUsize;
+use std::path::PathBuf;
 use std::time::{Duration, Instant};
 
 use lru::LruCache;
 
 use crate::brew::{
-    CasksMessage, CommandKind, CommandMessage, Details, DetailsLoad, DetailsMessage, LeavesMessage,
-    SizeEntry, SizesMessage, StatusMessage, StatusSnapshot, fetch_casks, fetch_details_basic,
-    fetch_details_full, fetch_leaves, fetch_sizes, fetch_status, run_brew_command, run_command,
+    CasksMessage, CommandEvent, CommandKind, CommandMessage, DeprecatedMessage, DeprecatedPackage,
+    Details, DetailsLoad, DetailsMessage, LeavesMessage, LinkPreview, ServiceEntry,
+    ServiceLogsMessage, SizeEntry, SizesMessage, StatusMessage, StatusSnapshot, UpdateSummary,
+    extract_caveats, fetch_casks, fetch_deprecated, fetch_details_basic, fetch_details_full,
+    fetch_leaves, fetch_service_logs, fetch_sizes, fetch_status, parse_link_preview,
+    parse_update_summary, run_command_streaming,
 };
-use crate::theme::{Theme, ThemeMode, detect_system_theme};
+use crate::config::{Config, write_config_value};
+use crate::keymap::{Action, Keymap};
+use crate::storage::{
+    CommandRecord, FetchedAt, SizeBaseline, SizeHistory, StartupSnapshot, load_command_history,
+    load_snapshot, output_tail, record_command, record_size_snapshot, save_snapshot, unix_now,
+};
+use crate::theme::{ColorDepth, Theme, ThemeMode, detect_system_theme, theme_names};
 
-/// Maximum number of package details to cache
-const DETAILS_CACHE_CAPACITY: usize = 64;
-const COMMAND_HISTORY_CAPACITY: usize = 24;
 const TOAST_DURATION: Duration = Duration::from_secs(5);
 
 pub struct App {
+    pub config: Config,
+    pub config_path: Option<PathBuf>,
+    pub keymap: Keymap,
     pub started_at: Instant,
     pub last_refresh: Instant,
     pub status: String,
     pub toast: Option<Toast>,
     pub theme_mode: ThemeMode,
     pub theme: Theme,
+    pub color_depth: ColorDepth,
     pub input_mode: InputMode,
     pub leaves_query: String,
     pub package_query: String,
@@ -55,6 +70,8 @@ pub struct App {
     pub package_results: Vec<String>,
     pub view_mode: ViewMode,
     pub sizes: Vec<SizeEntry>,
+    pub size_history: SizeHistory,
+    pub size_baseline: SizeBaseline,
     pub pending_sizes: bool,
     pub icon_mode: IconMode,
     pub icons_ascii: bool,
@@ -70,9 +87,33 @@ pub struct App {
     pub last_error: Option<String>,
     pub pending_package_action: Option<PendingPackageAction>,
     pub pending_service_action: Option<PendingServiceAction>,
+    /// `--all` was chosen; the next service action applies to every service.
+    pub pending_service_all: bool,
+    pub pending_services_cleanup: bool,
+    pub pending_keg_action: Option<PendingKegAction>,
+    pub pending_migration: Option<PendingMigration>,
+    /// Cask whose zap preview is showing in Details.
+    pub pending_zap: Option<PendingZap>,
+    /// Replacement install in flight; its success offers removing the old package.
+    pub active_migration: Option<Migration>,
+    /// Last `brew link --overwrite --dry-run`, by formula.
+    pub link_preview: Option<(String, LinkPreview)>,
+    /// Caveats printed by the last successful install, by package.
+    pub install_caveats: Option<(String, String)>,
     pub pending_upgrade_all_outdated: bool,
     pub pending_self_update: bool,
-    pub command_history: VecDeque<CommandHistoryEntry>,
+    /// Parsed result of the last successful `brew update` this session.
+    pub last_update_summary: Option<UpdateSummary>,
+    /// When Brewery last started `brew update`, manually or on schedule.
+    pub last_update_started_at: Option<Instant>,
+    /// Suggested doctor command waiting for a second key press.
+    pub pending_issue_command: Option<String>,
+    /// Finished commands, newest first; mirrors the on-disk history.
+    pub command_history: VecDeque<CommandRecord>,
+    pub history_query: String,
+    pub history_kind_filter: Option<String>,
+    pub history_outcome_filter: HistoryOutcome,
+    pub history_selected_index: Option<usize>,
     pub last_command_args: Vec<String>,
     pub pending_leaves: bool,
     pub pending_casks: bool,
@@ -86,8 +127,13 @@ pub struct App {
     pub focus_panel: FocusedPanel,
     pub sizes_scroll_offset: usize,
     pub details_scroll_offset: usize,
+    /// Highlighted installed version in the Details panel.
+    pub keg_selected_index: usize,
     pub status_scroll_offset: usize,
     pub system_status: Option<StatusSnapshot>,
+    /// Installed packages Homebrew has deprecated or disabled; fetched apart
+    /// from the status check because it needs a full `brew info --installed`.
+    pub deprecated_packages: Vec<DeprecatedPackage>,
     pub pending_status: bool,
     pub last_status_check: Option<Instant>,
     pub status_tab: StatusTab,
@@ -95,10 +141,22 @@ pub struct App {
     pub services_failed_only: bool,
     pub services_autostart_only: bool,
     pub services_kind_filter: ServiceKindFilter,
+    pub issues_selected_index: Option<usize>,
+    /// Highlighted suggested command within the selected issue.
+    pub issue_command_index: usize,
     pub leaves_outdated_only: bool,
     pub show_help_popup: bool,
     pub help_scroll_offset: usize,
     pub help_selected_command: usize,
+    pub theme_picker: Option<ThemePicker>,
+    pub pager: Option<Pager>,
+    pub stale: StaleData,
+    /// Set by `--read-only`; actions that change the system are refused.
+    pub read_only: bool,
+    /// When each section restored from the startup snapshot was fetched.
+    pub snapshot_fetched_at: FetchedAt,
+    /// Cached details restored from the snapshot that should be refetched on view.
+    pub stale_details: HashSet<String>,
     pub needs_redraw: bool,
     pub last_selection_change: Option<Instant>,
     /// Count of recent selection changes (for detecting rapid scrolling)
@@ -0,0 +1,501 @@
+use super::*;
+use crate::brew::{LogSnapshot, read_log_from};
+use crate::storage::{format_timestamp, save_output_log};
+
+/// How often a tailing pager checks its log files for new lines.
+const LOG_POLL_INTERVAL: Duration = Duration::from_millis(500);
+/// Lines a log pager keeps; older ones are dropped as new ones arrive.
+const MAX_LOG_LINES: usize = 10_000;
+
+impl Pager {
+    /// Header with the command line, start time, duration and exit code, then both streams.
+    pub fn for_record(record: &CommandRecord) -> Self {
+        let exit_label = record
+            .exit_code
+            .map(|code| code.to_string())
+            .unwrap_or_else(|| "n/a".to_string());
+        let mut lines = vec![
+            PagerLine::new(
+                PagerLineKind::Header,
+                format!("$ {}", record.command_line()),
+            ),
+            PagerLine::new(
+                PagerLineKind::Header,
+                format!(
+                    "Started {} · took {} · exit {exit_label}",
+                    format_timestamp(record.started_at),
+                    record.duration_label()
+                ),
+            ),
+        ];
+
+        for (kind, label, text) in [
+            (PagerLineKind::Stdout, "stdout", &record.stdout),
+            (PagerLineKind::Stderr, "stderr", &record.stderr),
+        ] {
+            if text.trim().is_empty() {
+                continue;
+            }
+            lines.push(PagerLine::new(PagerLineKind::Header, String::new()));
+            lines.push(PagerLine::new(
+                PagerLineKind::Header,
+                format!("── {label} ──"),
+            ));
+            lines.extend(
+                text.lines()
+                    .map(|line| PagerLine::new(kind, line.to_string())),
+            );
+        }
+
+        let stamp = format_timestamp(record.started_at)
+            .replace(' ', "_")
+            .replace(':', "");
+        Self {
+            title: format!("{} transcript", record.kind),
+            file_stem: format!("{}-{stamp}", record.kind.replace(' ', "-")),
+            lines,
+            scroll: 0,
+            search_input: None,
+            query: String::new(),
+            matches: Vec::new(),
+            current_match: None,
+            tail: None,
+        }
+    }
+
+    /// The end of each log file under its own heading, followed live from there.
+    pub fn for_service_logs(service: &str, snapshots: Vec<LogSnapshot>) -> Self {
+        let mut lines = Vec::new();
+        let mut files = Vec::new();
+        for snapshot in snapshots {
+            if !lines.is_empty() {
+                lines.push(PagerLine::new(PagerLineKind::Header, String::new()));
+            }
+            let stream = if snapshot.log.stderr {
+                "stderr"
+            } else {
+                "stdout"
+            };
+            lines.push(PagerLine::new(
+                PagerLineKind::Header,
+                format!("── {} ({stream}) ──", snapshot.log.path.display()),
+            ));
+            let kind = log_line_kind(snapshot.log.stderr);
+            match snapshot.text {
+                Some(text) => lines.extend(
+                    text.lines()
+                        .map(|line| PagerLine::new(kind, line.to_string())),
+                ),
+                None => lines.push(PagerLine::new(
+                    PagerLineKind::Header,
+                    "(not created yet)".to_string(),
+                )),
+            }
+            files.push(TailedFile {
+                path: snapshot.log.path,
+                offset: snapshot.offset,
+                stderr: snapshot.log.stderr,
+            });
+        }
+        let last_file = files.len().saturating_sub(1);
+        let mut pager = Self {
+            title: format!("{service} logs"),
+            file_stem: format!("{service}-logs"),
+            lines,
+            scroll: 0,
+            search_input: None,
+            query: String::new(),
+            matches: Vec::new(),
+            current_match: None,
+            tail: Some(LogTail {
+                files,
+                last_file,
+                last_poll: Instant::now(),
+            }),
+        };
+        pager.drop_front_lines(pager.lines.len().saturating_sub(MAX_LOG_LINES));
+        pager
+    }
+
+    /// Appends lines written since the last read, labelling them when the file
+    /// they came from changes. Returns whether anything was added.
+    pub fn append_log_lines(&mut self) -> bool {
+        let Some(tail) = self.tail.as_mut() else {
+            return false;
+        };
+        tail.last_poll = Instant::now();
+        let start = self.lines.len();
+        for (index, file) in tail.files.iter_mut().enumerate() {
+            let Ok((text, offset)) = read_log_from(&file.path, file.offset) else {
+                continue;
+            };
+            file.offset = offset;
+            if text.is_empty() {
+                continue;
+            }
+            if tail.last_file != index {
+                self.lines
+                    .push(PagerLine::new(PagerLineKind::Header, String::new()));
+                self.lines.push(PagerLine::new(
+                    PagerLineKind::Header,
+                    format!("── {} ──", file.path.display()),
+                ));
+                tail.last_file = index;
+            }
+            let kind = log_line_kind(file.stderr);
+            self.lines.extend(
+                text.lines()
+                    .map(|line| PagerLine::new(kind, line.to_string())),
+            );
+        }
+        if !self.query.is_empty() {
+            for index in start..self.lines.len() {
+                if !match_ranges(&self.lines[index].text, &self.query).is_empty() {
+                    self.matches.push(index);
+                }
+            }
+        }
+        let added = self.lines.len() > start;
+        self.drop_front_lines(self.lines.len().saturating_sub(MAX_LOG_LINES));
+        added
+    }
+
+    /// Removes the oldest `count` lines, keeping the view and matches on the
+    /// same text.
+    fn drop_front_lines(&mut self, count: usize) {
+        if count == 0 {
+            return;
+        }
+        self.lines.drain(..count);
+        self.scroll = self.scroll.saturating_sub(count);
+        let dropped = self.matches.partition_point(|&line| line < count);
+        self.matches.drain(..dropped);
+        for line in &mut self.matches {
+            *line -= count;
+        }
+        self.current_match = match self.current_match {
+            _ if self.matches.is_empty() => None,
+            Some(current) => Some(current.saturating_sub(dropped)),
+            None => None,
+        };
+    }
+
+    /// Recomputes matches for `query`, selecting the first one at or below `scroll`.
+    pub fn set_query(&mut self, query: String) {
+        self.matches = if query.is_empty() {
+            Vec::new()
+        } else {
+            self.lines
+                .iter()
+                .enumerate()
+                .filter(|(_, line)| !match_ranges(&line.text, &query).is_empty())
+                .map(|(index, _)| index)
+                .collect()
+        };
+        self.query = query;
+        self.current_match = if self.matches.is_empty() {
+            None
+        } else {
+            Some(
+                self.matches
+                    .iter()
+                    .position(|&line| line >= self.scroll)
+                    .unwrap_or(0),
+            )
+        };
+    }
+
+    /// Moves to the next (or previous) match, wrapping around at either end.
+    pub fn step_match(&mut self, forward: bool) {
+        let count = self.matches.len();
+        if count == 0 {
+            return;
+        }
+        self.current_match = Some(match (self.current_match, forward) {
+            (None, _) => 0,
+            (Some(current), true) => (current + 1) % count,
+            (Some(current), false) => (current + count - 1) % count,
+        });
+    }
+
+    pub fn current_match_line(&self) -> Option<usize> {
+        self.current_match
+            .and_then(|index| self.matches.get(index).copied())
+    }
+
+    pub fn plain_text(&self) -> String {
+        let mut text = String::new();
+        for line in &self.lines {
+            text.push_str(&line.text);
+            text.push('\n');
+        }
+        text
+    }
+}
+
+/// Classifies a line by brew's `==>`, `Warning:` and `Error:` prefixes.
+pub fn output_highlight(text: &str) -> Option<OutputHighlight> {
+    let trimmed = text.trim_start();
+    if trimmed.starts_with("==>") {
+        Some(OutputHighlight::Section)
+    } else if trimmed.starts_with("Warning:") {
+        Some(OutputHighlight::Warning)
+    } else if trimmed.starts_with("Error:") {
+        Some(OutputHighlight::Error)
+    } else {
+        None
+    }
+}
+
+fn log_line_kind(stderr: bool) -> PagerLineKind {
+    if stderr {
+        PagerLineKind::Stderr
+    } else {
+        PagerLineKind::Stdout
+    }
+}
+
+/// Classifies a service log line by the severity words daemons commonly print.
+pub fn log_highlight(text: &str) -> Option<OutputHighlight> {
+    let lower = text.to_ascii_lowercase();
+    let has = |words: &[&str]| words.iter().any(|word| lower.contains(word));
+    if has(&["error", "fatal", "panic", "exception", "failed", "crit"]) {
+        Some(OutputHighlight::Error)
+    } else if has(&["warn"]) {
+        Some(OutputHighlight::Warning)
+    } else {
+        None
+    }
+}
+
+/// Byte ranges of case-insensitive (ASCII) occurrences of `query` in `text`.
+pub fn match_ranges(text: &str, query: &str) -> Vec<std::ops::Range<usize>> {
+    if query.is_empty() {
+        return Vec::new();
+    }
+    let haystack = text.to_ascii_lowercase();
+    let needle = query.to_ascii_lowercase();
+    haystack
+        .match_indices(&needle)
+        .map(|(start, found)| start..start + found.len())
+        .collect()
+}
+
+impl PagerLine {
+    pub fn new(kind: PagerLineKind, text: String) -> Self {
+        Self { kind, text }
+    }
+}
+
+impl App {
+    pub fn open_history_transcript(&mut self) {
+        let Some(record) = self.selected_history_record() else {
+            self.status = "No command selected".to_string();
+            self.last_refresh = Instant::now();
+            return;
+        };
+        self.pager = Some(Pager::for_record(record));
+    }
+
+    /// Opens the most recent command's output.
+    pub fn open_last_output(&mut self) {
+        let Some(record) = self.command_history.front() else {
+            self.status = "No command output yet".to_string();
+            self.last_refresh = Instant::now();
+            return;
+        };
+        self.pager = Some(Pager::for_record(record));
+    }
+
+    /// Reads new log lines into a tailing pager, following along when the view
+    /// was already at the bottom.
+    pub fn poll_log_tail(&mut self, visible_lines: usize) {
+        let Some(pager) = self.pager.as_mut() else {
+            return;
+        };
+        if pager.tail.is_some() {
+            // The pager opens scrolled past the end; settle on the last page.
+            pager.scroll = pager
+                .scroll
+                .min(pager.lines.len().saturating_sub(visible_lines));
+        }
+        if pager
+            .tail
+            .as_ref()
+            .is_none_or(|tail| tail.last_poll.elapsed() < LOG_POLL_INTERVAL)
+        {
+            return;
+        }
+        let following = pager.scroll >= pager.lines.len().saturating_sub(visible_lines);
+        if pager.append_log_lines() {
+            if following {
+                pager.scroll = pager.lines.len().saturating_sub(visible_lines);
+            }
+            self.needs_redraw = true;
+        }
+    }
+
+    pub fn close_pager(&mut self) {
+        self.pager = None;
+    }
+
+    /// Scrolls by `delta` lines, stopping once the last line is on screen.
+    pub fn scroll_pager(&mut self, delta: isize, max_offset: usize) {
+        if let Some(pager) = self.pager.as_mut() {
+            pager.scroll = pager.scroll.saturating_add_signed(delta).min(max_offset);
+        }
+    }
+
+    pub fn start_pager_search(&mut self) {
+        if let Some(pager) = self.pager.as_mut() {
+            pager.search_input = Some(pager.query.clone());
+        }
+    }
+
+    /// Applies the typed query and jumps to its first match below the current view.
+    pub fn commit_pager_search(&mut self, max_offset: usize) {
+        let Some(pager) = self.pager.as_mut() else {
+            return;
+        };
+        let query = pager.search_input.take().unwrap_or_default();
+        pager.set_query(query);
+        if !pager.query.is_empty() && pager.matches.is_empty() {
+            self.status = format!("No matches for \"{}\"", pager.query);
+            self.last_refresh = Instant::now();
+        }
+        self.reveal_pager_match(max_offset);
+    }
+
+    pub fn step_pager_match(&mut self, forward: bool, max_offset: usize) {
+        if let Some(pager) = self.pager.as_mut() {
+            pager.step_match(forward);
+        }
+        self.reveal_pager_match(max_offset);
+    }
+
+    fn reveal_pager_match(&mut self, max_offset: usize) {
+        if let Some(pager) = self.pager.as_mut()
+            && let Some(line) = pager.current_match_line()
+        {
+            pager.scroll = line.min(max_offset);
+        }
+    }
+
+    pub fn save_pager_output(&mut self) {
+        let Some(pager) = self.pager.as_ref() else {
+            return;
+        };
+        self.status = match save_output_log(&pager.file_stem, &pager.plain_text()) {
+            Ok(path) => format!("Saved output to {}", path.display()),
+            Err(err) => format!("Could not save output: {err}"),
+        };
+        self.last_refresh = Instant::now();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        CommandRecord, OutputHighlight, Pager, PagerLine, PagerLineKind, log_highlight,
+        match_ranges, output_highlight,
+    };
+
+    fn sample_record() -> CommandRecord {
+        CommandRecord {
+            kind: "install".to_string(),
+            argv: vec!["brew".to_string(), "install".to_string(), "jq".to_string()],
+            started_at: 0,
+            duration_ms: 1_500,
+            success: false,
+            exit_code: Some(1),
+            stdout: "==> Fetching jq\n".to_string(),
+            stderr: "Error: network down\n".to_string(),
+        }
+    }
+
+    #[test]
+    fn transcript_lists_header_then_both_streams() {
+        let pager = Pager::for_record(&sample_record());
+        assert_eq!(pager.lines[0].text, "$ brew install jq");
+        assert!(pager.lines[1].text.contains("took 1.5s · exit 1"));
+        let stderr: Vec<&str> = pager
+            .lines
+            .iter()
+            .filter(|line| line.kind == PagerLineKind::Stderr)
+            .map(|line| line.text.as_str())
+            .collect();
+        assert_eq!(stderr, vec!["Error: network down"]);
+    }
+
+    #[test]
+    fn classifies_brew_output_markers() {
+        assert_eq!(
+            output_highlight("==> Pouring jq"),
+            Some(OutputHighlight::Section)
+        );
+        assert_eq!(
+            output_highlight("Warning: jq 1.7 is already installed"),
+            Some(OutputHighlight::Warning)
+        );
+        assert_eq!(
+            output_highlight("Error: No such keg"),
+            Some(OutputHighlight::Error)
+        );
+        assert_eq!(output_highlight("plain line"), None);
+
+        assert_eq!(
+            log_highlight("2026-10-18 FATAL could not bind"),
+            Some(OutputHighlight::Error)
+        );
+        assert_eq!(
+            log_highlight("# WARNING overcommit_memory is set to 0"),
+            Some(OutputHighlight::Warning)
+        );
+        assert_eq!(log_highlight("Ready to accept connections"), None);
+    }
+
+    #[test]
+    fn dropping_old_lines_keeps_view_and_matches_aligned() {
+        let mut pager = Pager::for_record(&sample_record());
+        pager.lines = (0..10)
+            .map(|index| {
+                let word = if index % 3 == 2 { "hit" } else { "miss" };
+                PagerLine::new(PagerLineKind::Stdout, format!("{word} {index}"))
+            })
+            .collect();
+        pager.set_query("hit".to_string());
+        assert_eq!(pager.matches, vec![2, 5, 8]);
+        pager.step_match(true);
+        pager.scroll = 6;
+
+        pager.drop_front_lines(4);
+        assert_eq!(pager.lines.len(), 6);
+        assert_eq!(pager.lines[0].text, "miss 4");
+        assert_eq!(pager.scroll, 2);
+        assert_eq!(pager.matches, vec![1, 4]);
+        assert_eq!(pager.current_match_line(), Some(1));
+
+        pager.drop_front_lines(5);
+        assert!(pager.matches.is_empty());
+        assert_eq!(pager.current_match, None);
+        assert_eq!(pager.scroll, 0);
+    }
+
+    #[test]
+    fn search_matches_case_insensitively_and_wraps() {
+        assert_eq!(
+            match_ranges("Fetching JQ and jq", "jq"),
+            vec![9..11, 16..18]
+        );
+
+        let mut pager = Pager::for_record(&sample_record());
+        pager.set_query("JQ".to_string());
+        assert_eq!(pager.matches.len(), 2);
+        assert_eq!(pager.current_match_line(), Some(0));
+        pager.step_match(true);
+        pager.step_match(true);
+        assert_eq!(pager.current_match_line(), Some(0));
+        pager.step_match(false);
+        assert_eq!(pager.current_match_line(), pager.matches.last().copied());
+    }
+}
@@ -1,5 +1,8 @@
 use super::*;
 
+/// Output lines kept on screen while a command is still running.
+const LIVE_OUTPUT_LINES: usize = 8;
+
 impl App {
     pub fn apply_leaves_message(&mut self, message: LeavesMessage) {
         match message.result {
@@ -17,6 +20,7 @@ impl App {
                     self.selected_index = Some(0);
                 }
                 self.last_leaves_refresh = Some(Instant::now());
+                self.stale.leaves = false;
                 self.last_error = None;
                 self.status = "Leaves updated".to_string();
             }
@@ -35,16 +39,37 @@ impl App {
         match message.result {
             Ok(details) => {
                 // LruCache doesn't have entry API, so we handle it manually
-                if let Some(existing) = self.details_cache.get_mut(&message.pkg) {
+                if self.stale_details.remove(&message.pkg) {
+                    self.details_cache.put(message.pkg.clone(), details);
+                } else if let Some(existing) = self.details_cache.get_mut(&message.pkg) {
                     merge_details(existing, &details);
                 } else {
                     self.details_cache.put(message.pkg.clone(), details);
                 }
+                if self.selected_installed_package() == Some(message.pkg.as_str())
+                    && let Some(details) = self.details_cache.peek(&message.pkg)
+                {
+                    self.keg_selected_index = self
+                        .keg_selected_index
+                        .min(details.installed.len().saturating_sub(1));
+                }
                 self.last_error = None;
                 self.status = match message.load {
                     DetailsLoad::Basic => "Details loaded".to_string(),
                     DetailsLoad::Full => "Deps/uses loaded".to_string(),
                 };
+                if self
+                    .pending_zap
+                    .as_ref()
+                    .is_some_and(|pending| pending.cask() == message.pkg)
+                {
+                    self.status = format!(
+                        "Zap preview ready for {}: [{}] continue, [{}] cancel",
+                        message.pkg,
+                        self.key_hint(Action::Zap),
+                        self.key_hint(Action::Cancel)
+                    );
+                }
             }
             Err(err) => {
                 self.last_error = Some(err.to_string());
@@ -61,6 +86,9 @@ impl App {
         match message.result {
             Ok(sizes) => {
                 self.sizes = sizes;
+                if let Some(history) = message.history {
+                    self.size_history = history;
+                }
                 if self.sizes.is_empty() {
                     self.sizes_scroll_offset = 0;
                 } else {
@@ -70,6 +98,7 @@ impl App {
                 self.last_error = None;
                 self.status = "Sizes updated".to_string();
                 self.last_sizes_refresh = Some(Instant::now());
+                self.stale.sizes = false;
             }
             Err(err) => {
                 self.last_error = Some(err.to_string());
@@ -99,6 +128,7 @@ impl App {
                     self.selected_cask_index = Some(0);
                 }
                 self.last_casks_refresh = Some(Instant::now());
+                self.stale.casks = false;
                 self.reconcile_service_selection();
                 self.last_error = None;
                 self.status = "Casks updated".to_string();
@@ -120,12 +150,14 @@ impl App {
                 self.outdated_leaves = status_snapshot.outdated_packages.iter().cloned().collect();
                 self.system_status = Some(status_snapshot);
                 self.reconcile_service_selection();
+                self.reconcile_issue_selection();
                 self.update_filtered_leaves();
                 let max_scroll = self.max_status_scroll();
                 self.status_scroll_offset = self.status_scroll_offset.min(max_scroll);
                 self.last_error = None;
                 self.status = "Status check complete".to_string();
                 self.last_status_check = Some(Instant::now());
+                self.stale.status = false;
             }
             Err(err) => {
                 self.last_error = Some(err.to_string());
@@ -140,12 +172,55 @@ impl App {
         self.needs_redraw = true;
     }
 
+    /// A failed fetch keeps the previous list rather than hiding known notices.
+    pub fn apply_deprecated_message(&mut self, message: DeprecatedMessage) {
+        if let Ok(deprecated) = message.result {
+            self.deprecated_packages = deprecated;
+            self.reconcile_issue_selection();
+            let max_scroll = self.max_status_scroll();
+            self.status_scroll_offset = self.status_scroll_offset.min(max_scroll);
+            self.needs_redraw = true;
+        }
+    }
+
+    pub fn apply_service_logs_message(&mut self, message: ServiceLogsMessage) {
+        match message.result {
+            Ok(snapshots) => {
+                let files = snapshots.len();
+                let mut pager = Pager::for_service_logs(&message.service, snapshots);
+                // Open at the newest lines; the draw clamps this to the last page.
+                pager.scroll = pager.lines.len();
+                self.pager = Some(pager);
+                self.status = format!(
+                    "Following {} log{} for {}",
+                    files,
+                    if files == 1 { "" } else { "s" },
+                    message.service
+                );
+            }
+            Err(err) => {
+                self.status = err.to_string();
+            }
+        }
+        self.last_refresh = Instant::now();
+        self.needs_redraw = true;
+    }
+
+    pub fn apply_command_output(&mut self, line: String) {
+        if self.last_command_output.len() >= LIVE_OUTPUT_LINES {
+            self.last_command_output.remove(0);
+        }
+        self.last_command_output.push(line);
+        self.needs_redraw = true;
+    }
+
     pub fn apply_command_message(&mut self, message: CommandMessage) {
         let mut toast: Option<(ToastLevel, String)> = None;
-        let command_duration_secs = self
-            .command_started_at
-            .map(|started| started.elapsed().as_secs())
-            .unwrap_or(0);
+        let migration = if message.kind == CommandKind::Install {
+            self.active_migration.take()
+        } else {
+            None
+        };
 
         match message.result {
             Ok(result) => {
@@ -180,6 +255,19 @@ impl App {
                             ToastLevel::Success,
                             "Brewery updated. Restart to use the new version".to_string(),
                         ));
+                    } else if message.kind == CommandKind::DoctorFix {
+                        toast = Some((
+                            ToastLevel::Success,
+                            "Suggested fix ran, re-checking doctor".to_string(),
+                        ));
+                    } else if message.kind == CommandKind::Update {
+                        let summary = parse_update_summary(&result.stdout);
+                        self.status = format!("Homebrew updated: {}", summary.headline());
+                        toast = Some((
+                            ToastLevel::Success,
+                            format!("brew update: {}", summary.headline()),
+                        ));
+                        self.last_update_summary = Some(summary);
                     }
                 } else {
                     self.status = format!("{} failed", message.kind);
@@ -215,9 +303,71 @@ impl App {
                             ToastLevel::Error,
                             format!("Brewery update failed: {reason}"),
                         ));
+                    } else if message.kind == CommandKind::DoctorFix {
+                        let reason = first_nonempty_line(&result.stderr)
+                            .or_else(|| first_nonempty_line(&result.stdout))
+                            .unwrap_or("Unknown error");
+                        toast =
+                            Some((ToastLevel::Error, format!("Suggested fix failed: {reason}")));
+                    } else if message.kind == CommandKind::Update {
+                        let reason = first_nonempty_line(&result.stderr)
+                            .or_else(|| first_nonempty_line(&result.stdout))
+                            .unwrap_or("Unknown error");
+                        toast = Some((ToastLevel::Error, format!("brew update failed: {reason}")));
+                    }
+                }
+
+                // The activity log keeps a few lines; caveats stay whole in Details.
+                if result.success
+                    && let Some(target) = self.last_command_target.clone()
+                {
+                    match message.kind {
+                        CommandKind::Install => {
+                            if let Some(caveats) = extract_caveats(&result.stdout) {
+                                toast = Some((
+                                    ToastLevel::Success,
+                                    format!(
+                                        "Install succeeded for {target}, caveats shown in Details"
+                                    ),
+                                ));
+                                self.install_caveats = Some((target.clone(), caveats));
+                            } else {
+                                self.install_caveats = None;
+                            }
+                            if let Some(migration) = migration.filter(|m| m.to == target) {
+                                self.status = format!(
+                                    "Installed {target}. Uninstall {} now? [{}] confirm, [{}] keep",
+                                    migration.from,
+                                    self.key_hint(Action::Migrate),
+                                    self.key_hint(Action::Cancel)
+                                );
+                                self.pending_migration =
+                                    Some(PendingMigration::UninstallOld(migration));
+                            }
+                        }
+                        CommandKind::Uninstall
+                            if self
+                                .install_caveats
+                                .as_ref()
+                                .is_some_and(|(pkg, _)| *pkg == target) =>
+                        {
+                            self.install_caveats = None;
+                        }
+                        _ => {}
                     }
                 }
 
+                if message.kind == CommandKind::LinkPreview
+                    && let Some(target) = self.last_command_target.clone()
+                {
+                    let preview =
+                        parse_link_preview(&format!("{}\n{}", result.stdout, result.stderr));
+                    if result.success {
+                        self.status = format!("Link preview for {target}: {}", preview.headline());
+                    }
+                    self.link_preview = Some((target, preview));
+                }
+
                 if message.kind == CommandKind::Search {
                     self.package_results = result
                         .stdout
@@ -244,13 +394,6 @@ impl App {
                 {
                     self.last_command_completed = Some((message.kind, target, Instant::now()));
                 }
-
-                self.push_command_history(
-                    message.kind,
-                    result.success,
-                    result.exit_code,
-                    command_duration_secs,
-                );
             }
             Err(err) => {
                 self.last_command_error = Some(err.to_string());
@@ -273,12 +416,16 @@ impl App {
                     ));
                 } else if message.kind == CommandKind::SelfUpdate {
                     toast = Some((ToastLevel::Error, format!("Brewery update failed: {err}")));
+                } else if message.kind == CommandKind::DoctorFix {
+                    toast = Some((ToastLevel::Error, format!("Suggested fix failed: {err}")));
+                } else if message.kind == CommandKind::Update {
+                    toast = Some((ToastLevel::Error, format!("brew update failed: {err}")));
                 }
-
-                self.push_command_history(message.kind, false, None, command_duration_secs);
             }
         }
 
+        self.push_command_history(message.record);
+
         if let Some((level, message)) = toast {
             self.show_toast(level, message);
         }
@@ -298,36 +445,13 @@ impl App {
         });
     }
 
-    fn push_command_history(
-        &mut self,
-        kind: CommandKind,
-        success: bool,
-        exit_code: Option<i32>,
-        duration_secs: u64,
-    ) {
-        let binary = if kind == CommandKind::SelfUpdate {
-            "cargo"
-        } else {
-            "brew"
-        };
-        let args = self.last_command_args.join(" ");
-        let command = if args.is_empty() {
-            binary.to_string()
-        } else {
-            format!("{binary} {args}")
-        };
-        self.command_history.push_front(CommandHistoryEntry {
-            kind: kind.label().to_string(),
-            command,
-            success,
-            exit_code,
-            finished_at: Instant::now(),
-            duration_secs,
-        });
-
-        while self.command_history.len() > COMMAND_HISTORY_CAPACITY {
-            self.command_history.pop_back();
-        }
+    fn push_command_history(&mut self, record: CommandRecord) {
+        // Indices shift by one, keep the same record selected.
+        self.history_selected_index = self.history_selected_index.map(|index| index + 1);
+        self.command_history.push_front(record);
+        self.command_history
+            .truncate(self.config.cache.command_history);
+        self.reconcile_history_selection();
     }
 }
 
@@ -336,14 +460,26 @@ fn merge_details(existing: &mut Details, incoming: &Details) {
     existing.homepage = incoming.homepage.clone();
     existing.latest = incoming.latest.clone();
     existing.installed = incoming.installed.clone();
+    existing.linked_keg = incoming.linked_keg.clone();
+    existing.keg_only = incoming.keg_only;
+    existing.keg_only_reason = incoming.keg_only_reason.clone();
+    existing.caveats = incoming.caveats.clone();
+    existing.metadata = incoming.metadata.clone();
+    existing.deprecation = incoming.deprecation.clone();
+    if incoming.keg_sizes.is_some() {
+        existing.keg_sizes = incoming.keg_sizes.clone();
+    }
     if incoming.deps.is_some() {
         existing.deps = incoming.deps.clone();
     }
     if incoming.uses.is_some() {
         existing.uses = incoming.uses.clone();
     }
-    if incoming.artifacts.is_some() {
-        existing.artifacts = incoming.artifacts.clone();
+    if incoming.cask.is_some() {
+        existing.cask = incoming.cask.clone();
+    }
+    if incoming.paths.is_some() {
+        existing.paths = incoming.paths.clone();
     }
 }
 
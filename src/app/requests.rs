@@ -66,7 +66,10 @@ impl App {
             return;
         }
 
-        if !force && let Some(existing) = self.details_cache.get(&pkg) {
+        if !force
+            && !self.stale_details.contains(&pkg)
+            && let Some(existing) = self.details_cache.get(&pkg)
+        {
             match load {
                 DetailsLoad::Basic => return,
                 DetailsLoad::Full => {
@@ -106,9 +109,19 @@ impl App {
         set_request_status(self, "Loading sizes...", true);
 
         spawn_request(tx, async {
-            SizesMessage {
-                result: fetch_sizes().await,
-            }
+            let result = fetch_sizes().await;
+            let history = match &result {
+                Ok(sizes) => {
+                    // Rewriting the history file is blocking I/O; keep it off the runtime.
+                    let sizes = sizes.clone();
+                    tokio::task::spawn_blocking(move || record_size_snapshot(&sizes))
+                        .await
+                        .ok()
+                        .and_then(Result::ok)
+                }
+                Err(_) => None,
+            };
+            SizesMessage { result, history }
         });
     }
 
@@ -144,20 +157,50 @@ impl App {
         });
     }
 
+    /// Quiet background fetch; only needed at startup and after package changes.
+    pub fn request_deprecated(&mut self, tx: &mpsc::UnboundedSender<DeprecatedMessage>) {
+        spawn_request(tx, async {
+            DeprecatedMessage {
+                result: fetch_deprecated().await,
+            }
+        });
+    }
+
+    pub fn request_service_logs(
+        &mut self,
+        service: &ServiceEntry,
+        tx: &mpsc::UnboundedSender<ServiceLogsMessage>,
+    ) {
+        set_request_status(self, &format!("Loading {} logs...", service.name), true);
+        let service = service.clone();
+        spawn_request(tx, async move {
+            ServiceLogsMessage {
+                result: fetch_service_logs(&service).await,
+                service: service.name,
+            }
+        });
+    }
+
     pub fn request_command(
         &mut self,
         kind: CommandKind,
         args: &[&str],
-        tx: &mpsc::UnboundedSender<CommandMessage>,
+        tx: &mpsc::UnboundedSender<CommandEvent>,
     ) {
         if self.pending_command {
             return;
         }
 
         self.pending_command = true;
+        if kind == CommandKind::Update {
+            self.last_update_started_at = Some(Instant::now());
+        }
         self.last_command = Some(kind);
         self.last_command_target = if kind.has_named_target() {
-            args.last().map(|value| (*value).to_string())
+            args.last().map(|value| match *value {
+                "--all" => "all services".to_string(),
+                value => value.to_string(),
+            })
         } else {
             None
         };
@@ -172,14 +215,42 @@ impl App {
 
         let tx = tx.clone();
         let args: Vec<String> = args.iter().map(|arg| (*arg).to_string()).collect();
+        let history_capacity = self.config.cache.command_history;
         tokio::spawn(async move {
             let arg_refs: Vec<&str> = args.iter().map(String::as_str).collect();
-            let result = if kind == CommandKind::SelfUpdate {
-                run_command("cargo", &arg_refs).await
-            } else {
-                run_brew_command(&arg_refs).await
+            let started_at = unix_now();
+            let started = Instant::now();
+            let binary = kind.binary();
+            let result = run_command_streaming(binary, &arg_refs, &tx).await;
+
+            let (success, exit_code, stdout, stderr) = match &result {
+                Ok(output) => (
+                    output.success,
+                    output.exit_code,
+                    output.stdout.clone(),
+                    output.stderr.clone(),
+                ),
+                Err(err) => (false, None, String::new(), err.to_string()),
+            };
+            let record = CommandRecord {
+                kind: kind.label().to_string(),
+                argv: std::iter::once(binary.to_string()).chain(args).collect(),
+                started_at,
+                duration_ms: started.elapsed().as_millis() as u64,
+                success,
+                exit_code,
+                stdout: output_tail(stdout),
+                stderr: output_tail(stderr),
             };
-            let _ = tx.send(CommandMessage { kind, result });
+            // Rewriting the history file is blocking I/O; keep it off the runtime.
+            let stored = record.clone();
+            let _ = tokio::task::spawn_blocking(move || record_command(&stored, history_capacity))
+                .await;
+            let _ = tx.send(CommandEvent::Finished(CommandMessage {
+                kind,
+                result,
+                record,
+            }));
         });
     }
 }
@@ -1,19 +1,49 @@
 use super::*;
 
 impl App {
+    #[cfg(test)]
     pub fn new() -> Self {
-        let theme = detect_system_theme();
+        Self::with_config(Config::default(), None)
+    }
+
+    pub fn with_config(config: Config, config_path: Option<PathBuf>) -> Self {
+        let icon_mode = if detect_icon_ascii() {
+            IconMode::Ascii
+        } else {
+            config.icons
+        };
+        let color_depth = config.colors.resolve();
+        let details_capacity =
+            NonZeroUsize::new(config.cache.details_capacity).unwrap_or(NonZeroUsize::MIN);
+
         Self {
+            theme: config
+                .theme
+                .resolve()
+                .unwrap_or_else(|_| detect_system_theme())
+                .for_depth(color_depth),
+            color_depth,
+            theme_mode: config.theme.clone(),
+            active_package_kind: config.default_list,
+            details_cache: LruCache::new(details_capacity),
+            icon_mode,
+            icons_ascii: resolve_icons_ascii(icon_mode),
+            mouse_enabled: detect_mouse_override().unwrap_or(config.mouse),
+            command_history: VecDeque::with_capacity(config.cache.command_history),
+            history_query: String::new(),
+            history_kind_filter: None,
+            history_outcome_filter: HistoryOutcome::default(),
+            history_selected_index: None,
+            keymap: config.keymap.clone(),
+            config,
+            config_path,
             started_at: Instant::now(),
             last_refresh: Instant::now(),
             status: "Ready".to_string(),
             toast: None,
-            theme_mode: ThemeMode::Auto,
-            theme,
             input_mode: InputMode::Normal,
             leaves_query: String::new(),
             package_query: String::new(),
-            active_package_kind: PackageKind::Formula,
             leaves: Vec::new(),
             casks: Vec::new(),
             filtered_leaves: Vec::new(),
@@ -25,15 +55,13 @@ impl App {
             last_result_details_pkg: None,
             selected_index: Some(0),
             selected_cask_index: Some(0),
-            details_cache: LruCache::new(NonZeroUsize::new(DETAILS_CACHE_CAPACITY).unwrap()),
             pending_details: None,
             package_results: Vec::new(),
             view_mode: ViewMode::Details,
             sizes: Vec::new(),
+            size_history: SizeHistory::default(),
+            size_baseline: SizeBaseline::default(),
             pending_sizes: false,
-            icon_mode: IconMode::Auto,
-            icons_ascii: detect_icon_ascii(),
-            mouse_enabled: detect_mouse_enabled(),
             pending_command: false,
             last_command: None,
             last_command_target: None,
@@ -45,9 +73,19 @@ impl App {
             last_error: None,
             pending_package_action: None,
             pending_service_action: None,
+            pending_service_all: false,
+            pending_services_cleanup: false,
+            pending_keg_action: None,
+            pending_migration: None,
+            active_migration: None,
+            link_preview: None,
+            pending_zap: None,
+            install_caveats: None,
             pending_upgrade_all_outdated: false,
             pending_self_update: false,
-            command_history: VecDeque::with_capacity(COMMAND_HISTORY_CAPACITY),
+            last_update_summary: None,
+            last_update_started_at: None,
+            pending_issue_command: None,
             last_command_args: Vec::new(),
             pending_leaves: false,
             pending_casks: false,
@@ -61,8 +99,10 @@ impl App {
             focus_panel: FocusedPanel::Leaves,
             sizes_scroll_offset: 0,
             details_scroll_offset: 0,
+            keg_selected_index: 0,
             status_scroll_offset: 0,
             system_status: None,
+            deprecated_packages: Vec::new(),
             pending_status: false,
             last_status_check: None,
             status_tab: StatusTab::default(),
@@ -70,10 +110,18 @@ impl App {
             services_failed_only: false,
             services_autostart_only: false,
             services_kind_filter: ServiceKindFilter::default(),
+            issues_selected_index: None,
+            issue_command_index: 0,
             leaves_outdated_only: false,
             show_help_popup: false,
             help_scroll_offset: 0,
             help_selected_command: 0,
+            theme_picker: None,
+            pager: None,
+            stale: StaleData::default(),
+            read_only: false,
+            snapshot_fetched_at: FetchedAt::default(),
+            stale_details: HashSet::new(),
             needs_redraw: true,
             last_selection_change: None,
             recent_selection_count: 0,
@@ -123,6 +171,7 @@ impl App {
         self.last_selection_change = Some(Instant::now());
         // Increment counter, saturating at 255
         self.recent_selection_count = self.recent_selection_count.saturating_add(1);
+        self.keg_selected_index = 0;
         self.needs_redraw = true;
     }
 
@@ -132,33 +181,193 @@ impl App {
         self.recent_selection_count > 2
     }
 
-    pub fn cycle_theme(&mut self) {
-        self.theme_mode = match self.theme_mode {
-            ThemeMode::Auto => ThemeMode::Light,
-            ThemeMode::Light => ThemeMode::Dark,
-            ThemeMode::Dark => ThemeMode::Auto,
+    pub fn open_theme_picker(&mut self) {
+        let options = theme_names();
+        let selected = options
+            .iter()
+            .position(|name| name == self.theme_mode.label())
+            .unwrap_or(0);
+        self.theme_picker = Some(ThemePicker {
+            options,
+            selected,
+            previous_mode: self.theme_mode.clone(),
+            previous_theme: self.theme,
+        });
+    }
+
+    /// Moves the picker selection and previews the theme under it.
+    pub fn theme_picker_move(&mut self, delta: isize) {
+        let Some(picker) = self.theme_picker.as_mut() else {
+            return;
         };
-        self.theme = match self.theme_mode {
-            ThemeMode::Light => Theme::light(),
-            ThemeMode::Dark => Theme::dark(),
-            ThemeMode::Auto => detect_system_theme(),
+        let last = picker.options.len().saturating_sub(1);
+        picker.selected = picker.selected.saturating_add_signed(delta).min(last);
+        let mode = ThemeMode::from(picker.options[picker.selected].clone());
+
+        match mode.resolve() {
+            Ok(theme) => {
+                self.theme = theme.for_depth(self.color_depth);
+                self.status = format!("Theme preview: {}", mode.label());
+            }
+            Err(err) => self.status = err.to_string(),
+        }
+        self.last_refresh = Instant::now();
+    }
+
+    pub fn apply_theme_picker(&mut self) {
+        let Some(picker) = self.theme_picker.take() else {
+            return;
         };
-        self.status = format!("Theme: {:?}", self.theme_mode);
+        let mode = ThemeMode::from(picker.options[picker.selected].clone());
+
+        match mode.resolve() {
+            Ok(theme) => {
+                self.theme = theme.for_depth(self.color_depth);
+                self.theme_mode = mode;
+                self.status = format!("Theme: {}", self.theme_mode.label());
+                self.save_ui_toggle("theme", self.theme_mode.label().into());
+            }
+            Err(err) => {
+                self.theme = picker.previous_theme;
+                self.status = err.to_string();
+            }
+        }
         self.last_refresh = Instant::now();
     }
 
+    pub fn cancel_theme_picker(&mut self) {
+        if let Some(picker) = self.theme_picker.take() {
+            self.theme = picker.previous_theme;
+            self.theme_mode = picker.previous_mode;
+            self.status = "Theme unchanged".to_string();
+            self.last_refresh = Instant::now();
+        }
+    }
+
+    /// Loads the saved command history; called once at startup.
+    pub fn load_command_history(&mut self) {
+        self.command_history = load_command_history(self.config.cache.command_history).into();
+        self.reconcile_history_selection();
+    }
+
+    /// Seeds the panels from the last run's snapshot; called once at startup.
+    pub fn restore_snapshot(&mut self) {
+        let Some(snapshot) = self.config.cache.snapshot_ttl().and_then(load_snapshot) else {
+            return;
+        };
+
+        // Replaying through the reducers clears the flags, so they are set afterwards.
+        let stale = StaleData {
+            leaves: !snapshot.leaves.is_empty(),
+            casks: !snapshot.casks.is_empty(),
+            sizes: !snapshot.sizes.is_empty(),
+            status: snapshot.status.is_some(),
+        };
+        if stale.leaves {
+            self.apply_leaves_message(LeavesMessage {
+                result: Ok(snapshot.leaves),
+            });
+        }
+        if stale.casks {
+            self.apply_casks_message(CasksMessage {
+                result: Ok(snapshot.casks),
+            });
+        }
+        if stale.sizes {
+            self.apply_sizes_message(SizesMessage {
+                result: Ok(snapshot.sizes),
+                history: None,
+            });
+        }
+        if let Some(status) = snapshot.status {
+            self.apply_status_message(StatusMessage { result: Ok(status) });
+        }
+        // Oldest first so the most recently used entries end up hottest in the LRU.
+        for (pkg, details) in snapshot.details.into_iter().rev() {
+            self.stale_details.insert(pkg.clone());
+            self.details_cache.put(pkg, details);
+        }
+
+        // The reducers stamp these as fresh; the live fetches will set them for real.
+        self.last_leaves_refresh = None;
+        self.last_casks_refresh = None;
+        self.last_sizes_refresh = None;
+        self.last_status_check = None;
+        self.stale = stale;
+        self.snapshot_fetched_at = snapshot.fetched_at;
+        self.status = "Loaded cached snapshot".to_string();
+    }
+
+    /// Writes the current inventory for the next launch; called on quit.
+    pub fn save_snapshot(&self) {
+        if self.config.cache.snapshot_ttl().is_none() {
+            return;
+        }
+        if let Some(snapshot) = self.snapshot_to_save(unix_now()) {
+            let _ = save_snapshot(&snapshot);
+        }
+    }
+
+    /// Everything on screen, each section stamped with when it was fetched.
+    /// Sections still showing restored data keep their restored time, so
+    /// saving them again neither drops them nor makes them look newer.
+    fn snapshot_to_save(&self, now: u64) -> Option<StartupSnapshot> {
+        let restored = &self.snapshot_fetched_at;
+        let fetched_at = |stale: bool, restored_at: u64, refreshed: Option<Instant>| {
+            if stale {
+                restored_at
+            } else {
+                refreshed.map_or(now, |at| now.saturating_sub(at.elapsed().as_secs()))
+            }
+        };
+        let details: Vec<(String, Details)> = self
+            .details_cache
+            .iter()
+            .map(|(pkg, details)| (pkg.clone(), details.clone()))
+            .collect();
+        let snapshot = StartupSnapshot {
+            saved_at: now,
+            leaves: self.leaves.clone(),
+            casks: self.casks.clone(),
+            sizes: self.sizes.clone(),
+            status: self.system_status.clone(),
+            fetched_at: FetchedAt {
+                leaves: fetched_at(self.stale.leaves, restored.leaves, self.last_leaves_refresh),
+                casks: fetched_at(self.stale.casks, restored.casks, self.last_casks_refresh),
+                sizes: fetched_at(self.stale.sizes, restored.sizes, self.last_sizes_refresh),
+                status: fetched_at(self.stale.status, restored.status, self.last_status_check),
+                details: details
+                    .iter()
+                    .map(|(pkg, _)| {
+                        let restored_at = restored.details.get(pkg).copied().unwrap_or(now);
+                        let stale = self.stale_details.contains(pkg);
+                        (pkg.clone(), fetched_at(stale, restored_at, None))
+                    })
+                    .collect(),
+            },
+            details,
+        };
+        snapshot.has_data().then_some(snapshot)
+    }
+
+    /// Re-resolves the Auto theme after the terminal reported a new background.
+    pub fn redetect_auto_theme(&mut self) {
+        if self.theme_mode != ThemeMode::Auto || self.theme_picker.is_some() {
+            return;
+        }
+        self.theme = detect_system_theme().for_depth(self.color_depth);
+        self.needs_redraw = true;
+    }
+
     pub fn toggle_icons(&mut self) {
         self.icon_mode = match self.icon_mode {
             IconMode::Ascii => IconMode::Nerd,
             IconMode::Auto | IconMode::Nerd => IconMode::Ascii,
         };
-        self.icons_ascii = match self.icon_mode {
-            IconMode::Ascii => true,
-            IconMode::Nerd => false,
-            IconMode::Auto => detect_icon_ascii(),
-        };
+        self.icons_ascii = resolve_icons_ascii(self.icon_mode);
         self.status = format!("Icons: {}", if self.icons_ascii { "ASCII" } else { "Nerd" });
         self.last_refresh = Instant::now();
+        self.save_ui_toggle("icons", self.icon_mode.label().into());
     }
 
     pub fn toggle_mouse(&mut self) {
@@ -172,6 +381,32 @@ impl App {
             }
         );
         self.last_refresh = Instant::now();
+        self.save_ui_toggle("mouse", self.mouse_enabled.into());
+    }
+
+    /// Persists a UI toggle to the config file when `save_ui_toggles` is on.
+    fn save_ui_toggle(&mut self, key: &str, value: toml_edit::Value) {
+        if !self.config.save_ui_toggles {
+            return;
+        }
+        let Some(path) = self.config_path.as_deref() else {
+            return;
+        };
+
+        if let Err(err) = write_config_value(path, key, value) {
+            self.last_error = Some(format!("Could not save {key} to config: {err}"));
+        }
+    }
+
+    /// Primary key for `action` as shown in inline hints.
+    pub fn key_hint(&self, action: Action) -> String {
+        self.keymap.primary_label(action, self.icons_ascii)
+    }
+
+    pub fn cycle_size_baseline(&mut self) {
+        self.size_baseline = self.size_baseline.next();
+        self.status = format!("Size delta: since {}", self.size_baseline.label());
+        self.last_refresh = Instant::now();
     }
 
     pub fn cycle_focus(&mut self) {
@@ -194,6 +429,9 @@ impl App {
             StatusTab::History => StatusTab::Activity,
         };
         self.status_scroll_offset = 0; // Reset scroll when switching tabs
+        if self.status_tab == StatusTab::History {
+            self.reconcile_history_selection();
+        }
     }
 
     pub fn status_tab_prev(&mut self) {
@@ -205,6 +443,9 @@ impl App {
             StatusTab::History => StatusTab::Services,
         };
         self.status_scroll_offset = 0;
+        if self.status_tab == StatusTab::History {
+            self.reconcile_history_selection();
+        }
     }
 
     pub fn toggle_help(&mut self) {
@@ -250,12 +491,18 @@ impl App {
             FocusedPanel::Status => {
                 if self.status_tab == StatusTab::Services {
                     self.select_prev_service();
+                } else if self.status_tab == StatusTab::Issues {
+                    self.select_prev_issue();
+                } else if self.status_tab == StatusTab::History {
+                    self.select_prev_history();
                 } else {
                     self.status_scroll_offset = self.status_scroll_offset.saturating_sub(1);
                 }
             }
             FocusedPanel::Details => {
-                self.details_scroll_offset = self.details_scroll_offset.saturating_sub(1);
+                if !self.select_prev_issue_command() && !self.select_prev_keg() {
+                    self.details_scroll_offset = self.details_scroll_offset.saturating_sub(1);
+                }
             }
         }
     }
@@ -270,24 +517,49 @@ impl App {
             FocusedPanel::Status => {
                 if self.status_tab == StatusTab::Services {
                     self.select_next_service();
+                } else if self.status_tab == StatusTab::Issues {
+                    self.select_next_issue();
+                } else if self.status_tab == StatusTab::History {
+                    self.select_next_history();
                 } else {
                     let max_scroll = self.max_status_scroll();
                     self.status_scroll_offset = (self.status_scroll_offset + 1).min(max_scroll);
                 }
             }
             FocusedPanel::Details => {
-                self.details_scroll_offset += 1;
+                if !self.select_next_issue_command() && !self.select_next_keg() {
+                    self.details_scroll_offset += 1;
+                }
             }
         }
     }
 
+    /// Scheduled `brew update` is due: nothing is running and neither Brewery nor
+    /// the user updated within `interval`.
+    pub fn auto_update_due(&self, interval: Duration) -> bool {
+        if self.pending_command || self.read_only || self.stale.status {
+            return false;
+        }
+        if self
+            .last_update_started_at
+            .is_some_and(|started| started.elapsed() < interval)
+        {
+            return false;
+        }
+        self.system_status.as_ref().is_some_and(|status| {
+            status
+                .last_brew_update_secs_ago
+                .is_none_or(|secs| secs >= interval.as_secs())
+        })
+    }
+
     pub(super) fn max_status_scroll(&self) -> usize {
         self.system_status.as_ref().map_or(0, |h| {
             let count = match self.status_tab {
                 StatusTab::Outdated => h.outdated_packages.len(),
-                StatusTab::Issues => h.doctor_issues.len(),
+                StatusTab::Issues => self.issues_row_count(),
                 StatusTab::Services => self.filtered_service_count(),
-                StatusTab::History => self.command_history.len(),
+                StatusTab::History => self.filtered_history_indices().len(),
                 StatusTab::Activity => self.activity_item_count(),
             };
             count.saturating_sub(2)
@@ -309,7 +581,12 @@ impl App {
             if self.last_command_target.is_some()
                 || matches!(
                     self.last_command,
-                    Some(CommandKind::UpgradeAll | CommandKind::SelfUpdate)
+                    Some(
+                        CommandKind::UpgradeAll
+                            | CommandKind::SelfUpdate
+                            | CommandKind::DoctorFix
+                            | CommandKind::Update
+                    )
                 )
             {
                 count += 1;
@@ -332,6 +609,9 @@ impl App {
         if system_status.last_brew_update_secs_ago.is_some() {
             count += 1;
         }
+        if self.last_update_summary.is_some() {
+            count += 1;
+        }
         if self.last_status_check.is_some() {
             count += 1;
         }
@@ -363,6 +643,14 @@ impl App {
     }
 }
 
+fn resolve_icons_ascii(mode: IconMode) -> bool {
+    match mode {
+        IconMode::Ascii => true,
+        IconMode::Nerd => false,
+        IconMode::Auto => detect_icon_ascii(),
+    }
+}
+
 fn detect_icon_ascii() -> bool {
     if let Ok(value) = std::env::var("BREWERY_ASCII")
         && (value == "1" || value.eq_ignore_ascii_case("true"))
@@ -372,13 +660,48 @@ fn detect_icon_ascii() -> bool {
     false
 }
 
-fn detect_mouse_enabled() -> bool {
-    if let Ok(value) = std::env::var("BREWERY_MOUSE") {
-        let value = value.trim();
-        if value == "0" || value.eq_ignore_ascii_case("false") || value.eq_ignore_ascii_case("off")
-        {
-            return false;
-        }
+/// `BREWERY_MOUSE` overrides the config file when set.
+fn detect_mouse_override() -> Option<bool> {
+    let value = std::env::var("BREWERY_MOUSE").ok()?;
+    let value = value.trim();
+    if value == "0" || value.eq_ignore_ascii_case("false") || value.eq_ignore_ascii_case("off") {
+        return Some(false);
+    }
+    Some(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{App, Details};
+
+    fn details() -> Details {
+        serde_json::from_str(
+            r#"{"desc":null,"homepage":null,"latest":null,"installed":[],"deps":null,"uses":null}"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn snapshot_keeps_restored_sections_with_their_original_time() {
+        let mut app = App::new();
+        app.leaves = vec!["jq".to_string()];
+        app.casks = vec!["firefox".to_string()];
+        app.stale.leaves = true;
+        app.snapshot_fetched_at.leaves = 1_000;
+        app.snapshot_fetched_at
+            .details
+            .insert("jq".to_string(), 1_200);
+        app.details_cache.put("jq".to_string(), details());
+        app.stale_details.insert("jq".to_string());
+        app.details_cache.put("wget".to_string(), details());
+
+        let snapshot = app.snapshot_to_save(2_000).expect("panels hold data");
+        assert_eq!(snapshot.saved_at, 2_000);
+        assert_eq!(snapshot.leaves, vec!["jq".to_string()]);
+        assert_eq!(snapshot.fetched_at.leaves, 1_000);
+        assert_eq!(snapshot.casks, vec!["firefox".to_string()]);
+        assert_eq!(snapshot.fetched_at.casks, 2_000);
+        assert_eq!(snapshot.fetched_at.details.get("jq"), Some(&1_200));
+        assert_eq!(snapshot.fetched_at.details.get("wget"), Some(&2_000));
     }
-    true
 }
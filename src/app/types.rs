@@ -1,25 +1,51 @@
+use std::path::PathBuf;
 use std::time::Instant;
 
+use serde::Deserialize;
+
+use crate::theme::{Theme, ThemeMode};
+
 #[derive(Clone, Copy, PartialEq)]
 pub enum InputMode {
     Normal,
     SearchLeaves,
     PackageSearch,
     PackageResults,
+    SearchHistory,
 }
 
-#[derive(Clone, Copy, PartialEq)]
+#[derive(Clone, Copy, PartialEq, Debug, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum IconMode {
+    #[default]
     Auto,
     Nerd,
     Ascii,
 }
 
+impl IconMode {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Auto => "auto",
+            Self::Nerd => "nerd",
+            Self::Ascii => "ascii",
+        }
+    }
+}
+
 #[derive(Clone, Copy, PartialEq)]
 pub enum PackageAction {
     Install,
     Uninstall,
     Upgrade,
+    Link,
+    Unlink,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum KegAction {
+    Switch,
+    Remove,
 }
 
 #[derive(Clone, Copy, PartialEq)]
@@ -27,6 +53,17 @@ pub enum ServiceAction {
     Start,
     Stop,
     Restart,
+    /// Start without registering it to launch at login.
+    Run,
+    /// Stop immediately but stay registered to launch at login.
+    Kill,
+}
+
+/// One service, or every service via `--all`.
+#[derive(Clone, PartialEq)]
+pub enum ServiceTarget {
+    One(String),
+    All,
 }
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
@@ -55,9 +92,12 @@ impl ServiceKindFilter {
     }
 }
 
-#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Deserialize)]
 pub enum PackageKind {
+    #[default]
+    #[serde(rename = "formulae", alias = "formula")]
     Formula,
+    #[serde(rename = "casks", alias = "cask")]
     Cask,
 }
 
@@ -68,10 +108,58 @@ pub struct PendingPackageAction {
     pub pkg: String,
 }
 
+#[derive(Clone, PartialEq)]
+pub struct PendingKegAction {
+    pub action: KegAction,
+    pub formula: String,
+    pub version: String,
+}
+
+/// Moving off a deprecated package onto Homebrew's suggested replacement.
+#[derive(Clone, PartialEq)]
+pub struct Migration {
+    pub from: String,
+    pub from_kind: PackageKind,
+    pub to: String,
+    pub to_kind: PackageKind,
+}
+
+#[derive(Clone, PartialEq)]
+pub enum PendingMigration {
+    InstallReplacement(Migration),
+    /// The replacement installed; offer to uninstall the old package.
+    UninstallOld(Migration),
+}
+
+/// Zapping asks twice: once to review the preview, once more before deleting.
+#[derive(Clone, PartialEq)]
+pub enum PendingZap {
+    Preview(String),
+    Confirm(String),
+}
+
+impl PendingZap {
+    pub fn cask(&self) -> &str {
+        match self {
+            Self::Preview(cask) | Self::Confirm(cask) => cask,
+        }
+    }
+}
+
 #[derive(Clone, PartialEq)]
 pub struct PendingServiceAction {
     pub action: ServiceAction,
-    pub service: String,
+    pub target: ServiceTarget,
+}
+
+/// Open theme picker; moving the selection previews the theme live.
+#[derive(Clone)]
+pub struct ThemePicker {
+    pub options: Vec<String>,
+    pub selected: usize,
+    /// Restored when the picker is canceled.
+    pub previous_mode: ThemeMode,
+    pub previous_theme: Theme,
 }
 
 #[derive(Clone, Copy, PartialEq)]
@@ -80,7 +168,7 @@ pub enum ViewMode {
     PackageResults,
 }
 
-#[derive(Clone, Copy, PartialEq, Debug)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub enum FocusedPanel {
     Leaves,
     Sizes,
@@ -98,14 +186,109 @@ pub enum StatusTab {
     History,
 }
 
-#[derive(Clone)]
-pub struct CommandHistoryEntry {
-    pub kind: String,
-    pub command: String,
-    pub success: bool,
-    pub exit_code: Option<i32>,
-    pub finished_at: Instant,
-    pub duration_secs: u64,
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum HistoryOutcome {
+    #[default]
+    All,
+    Failed,
+    Succeeded,
+}
+
+impl HistoryOutcome {
+    pub fn next(self) -> Self {
+        match self {
+            Self::All => Self::Failed,
+            Self::Failed => Self::Succeeded,
+            Self::Succeeded => Self::All,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::All => "any outcome",
+            Self::Failed => "failed",
+            Self::Succeeded => "succeeded",
+        }
+    }
+
+    pub fn matches(self, success: bool) -> bool {
+        match self {
+            Self::All => true,
+            Self::Failed => !success,
+            Self::Succeeded => success,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PagerLineKind {
+    Header,
+    Stdout,
+    Stderr,
+}
+
+#[derive(Clone, Debug)]
+pub struct PagerLine {
+    pub kind: PagerLineKind,
+    pub text: String,
+}
+
+/// Panels still showing the startup snapshot rather than live data.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct StaleData {
+    pub leaves: bool,
+    pub casks: bool,
+    pub sizes: bool,
+    pub status: bool,
+}
+
+impl StaleData {
+    pub fn any(self) -> bool {
+        self.leaves || self.casks || self.sizes || self.status
+    }
+}
+
+/// Brew's own emphasis markers in command output.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum OutputHighlight {
+    /// `==>` section headings.
+    Section,
+    Warning,
+    Error,
+}
+
+/// Full-screen, scrollable view over command output.
+#[derive(Clone, Debug)]
+pub struct Pager {
+    pub title: String,
+    /// Base name used when saving, e.g. `install-2026-10-18_140312`.
+    pub file_stem: String,
+    pub lines: Vec<PagerLine>,
+    pub scroll: usize,
+    /// Search being typed; `None` when not in the search prompt.
+    pub search_input: Option<String>,
+    pub query: String,
+    /// Indices of lines containing `query`.
+    pub matches: Vec<usize>,
+    pub current_match: Option<usize>,
+    /// Set while following service logs; new lines are appended as they arrive.
+    pub tail: Option<LogTail>,
+}
+
+/// Log files the pager keeps reading from.
+#[derive(Clone, Debug)]
+pub struct LogTail {
+    pub files: Vec<TailedFile>,
+    /// Index of the file the most recent lines came from.
+    pub last_file: usize,
+    pub last_poll: Instant,
+}
+
+#[derive(Clone, Debug)]
+pub struct TailedFile {
+    pub path: PathBuf,
+    pub offset: u64,
+    pub stderr: bool,
 }
 
 #[derive(Clone, Copy, PartialEq)]
@@ -0,0 +1,521 @@
+use std::path::{Path, PathBuf};
+
+use serde_json::Value;
+
+use super::paths::brew_prefix;
+use super::size::du_path_sizes;
+
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ArtifactKind {
+    App,
+    Suite,
+    Binary,
+    Manpage,
+    Font,
+    Pkg,
+    Installer,
+    Prefpane,
+    Qlplugin,
+    Colorpicker,
+    Dictionary,
+    InputMethod,
+    ScreenSaver,
+    Service,
+    Other(String),
+}
+
+impl ArtifactKind {
+    fn from_key(key: &str) -> Self {
+        match key {
+            "app" => Self::App,
+            "suite" => Self::Suite,
+            "binary" => Self::Binary,
+            "manpage" => Self::Manpage,
+            "font" => Self::Font,
+            "pkg" => Self::Pkg,
+            "installer" => Self::Installer,
+            "prefpane" => Self::Prefpane,
+            "qlplugin" => Self::Qlplugin,
+            "colorpicker" => Self::Colorpicker,
+            "dictionary" => Self::Dictionary,
+            "input_method" => Self::InputMethod,
+            "screen_saver" => Self::ScreenSaver,
+            "service" => Self::Service,
+            other => Self::Other(other.to_string()),
+        }
+    }
+
+    pub fn label(&self) -> &str {
+        match self {
+            Self::App => "app",
+            Self::Suite => "suite",
+            Self::Binary => "binary",
+            Self::Manpage => "manpage",
+            Self::Font => "font",
+            Self::Pkg => "pkg",
+            Self::Installer => "installer",
+            Self::Prefpane => "prefpane",
+            Self::Qlplugin => "qlplugin",
+            Self::Colorpicker => "colorpicker",
+            Self::Dictionary => "dictionary",
+            Self::InputMethod => "input method",
+            Self::ScreenSaver => "screen saver",
+            Self::Service => "service",
+            Self::Other(key) => key,
+        }
+    }
+}
+
+/// Something a cask installs, with where it should be once installed.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct CaskArtifact {
+    pub kind: ArtifactKind,
+    pub name: String,
+    pub path: Option<String>,
+    /// Whether `path` exists; `None` when there is nothing to check.
+    pub present: Option<bool>,
+}
+
+/// One directive of an `uninstall` or `zap` stanza, e.g. `trash: ~/Library/Caches/Foo`.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct StanzaItem {
+    pub directive: String,
+    pub value: String,
+    pub present: Option<bool>,
+    /// Disk usage of a present path, once measured for a zap preview.
+    #[serde(default)]
+    pub size_kb: Option<u64>,
+}
+
+impl StanzaItem {
+    pub fn removes_path(&self) -> bool {
+        matches!(self.directive.as_str(), "delete" | "trash" | "rmdir")
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct CaskInfo {
+    pub artifacts: Vec<CaskArtifact>,
+    pub auto_updates: bool,
+    pub depends_on: Vec<String>,
+    pub conflicts_with: Vec<String>,
+    pub uninstall: Vec<StanzaItem>,
+    pub zap: Vec<StanzaItem>,
+    /// Set once the present zap paths have been sized.
+    #[serde(default)]
+    pub zap_measured: bool,
+}
+
+impl CaskInfo {
+    pub fn missing_artifacts(&self) -> usize {
+        self.artifacts
+            .iter()
+            .filter(|artifact| artifact.present == Some(false))
+            .count()
+    }
+
+    /// Zap paths that exist on disk and would be deleted.
+    pub fn zap_present(&self) -> impl Iterator<Item = &StanzaItem> {
+        self.zap.iter().filter(|item| item.present == Some(true))
+    }
+
+    pub fn zap_size_kb(&self) -> u64 {
+        self.zap_present().filter_map(|item| item.size_kb).sum()
+    }
+}
+
+/// Sizes every zap path present on disk for the zap preview.
+pub async fn measure_zap_paths(info: &mut CaskInfo) {
+    let home = PathBuf::from(std::env::var("HOME").unwrap_or_default());
+    let paths: Vec<PathBuf> = info
+        .zap_present()
+        .map(|item| expand_home(&item.value, &home))
+        .collect();
+    let sizes = du_path_sizes(&paths).await;
+    for item in info
+        .zap
+        .iter_mut()
+        .filter(|item| item.present == Some(true))
+    {
+        let path = expand_home(&item.value, &home);
+        item.size_kb = sizes
+            .iter()
+            .find(|(measured, _)| *measured == path)
+            .map(|(_, size_kb)| *size_kb);
+    }
+    info.zap_measured = true;
+}
+
+/// Where cask artifacts land; mirrors `HOMEBREW_CASK_OPTS` directory overrides.
+pub struct CaskDirs {
+    pub home: PathBuf,
+    pub prefix: PathBuf,
+    pub appdir: PathBuf,
+    pub fontdir: PathBuf,
+}
+
+impl CaskDirs {
+    pub async fn detect() -> Self {
+        let home = PathBuf::from(std::env::var("HOME").unwrap_or_default());
+        let prefix = PathBuf::from(brew_prefix().await);
+        let opts = std::env::var("HOMEBREW_CASK_OPTS").unwrap_or_default();
+        let dir_opt = |name: &str| {
+            opts.split_whitespace()
+                .find_map(|opt| opt.strip_prefix(name))
+                .map(|dir| expand_home(dir, &home))
+        };
+        Self {
+            appdir: dir_opt("--appdir=").unwrap_or_else(|| PathBuf::from("/Applications")),
+            fontdir: dir_opt("--fontdir=").unwrap_or_else(|| home.join("Library/Fonts")),
+            prefix,
+            home,
+        }
+    }
+
+    fn artifact_path(&self, kind: &ArtifactKind, name: &str) -> Option<PathBuf> {
+        if name.starts_with('/') || name.starts_with('~') {
+            return Some(expand_home(name, &self.home));
+        }
+        let library = |dir: &str| Some(self.home.join("Library").join(dir).join(name));
+        match kind {
+            ArtifactKind::App | ArtifactKind::Suite => Some(self.appdir.join(name)),
+            ArtifactKind::Binary => Some(self.prefix.join("bin").join(name)),
+            ArtifactKind::Manpage => {
+                let section = name.rsplit('.').next()?;
+                Some(
+                    self.prefix
+                        .join("share/man")
+                        .join(format!("man{section}"))
+                        .join(name),
+                )
+            }
+            ArtifactKind::Font => Some(self.fontdir.join(name)),
+            ArtifactKind::Prefpane => library("PreferencePanes"),
+            ArtifactKind::Qlplugin => library("QuickLook"),
+            ArtifactKind::Colorpicker => library("ColorPickers"),
+            ArtifactKind::Dictionary => library("Dictionaries"),
+            ArtifactKind::InputMethod => library("Input Methods"),
+            ArtifactKind::ScreenSaver => library("Screen Savers"),
+            ArtifactKind::Service => library("Services"),
+            ArtifactKind::Pkg | ArtifactKind::Installer | ArtifactKind::Other(_) => None,
+        }
+    }
+}
+
+/// Builds the cask model from a `brew info --json=v2` cask entry. Artifact presence is
+/// only checked for installed casks.
+pub fn parse_cask_info(cask: &Value, dirs: &CaskDirs, installed: bool) -> CaskInfo {
+    let mut info = CaskInfo {
+        auto_updates: cask
+            .get("auto_updates")
+            .and_then(Value::as_bool)
+            .unwrap_or(false),
+        depends_on: cask
+            .get("depends_on")
+            .map(describe_requirements)
+            .unwrap_or_default(),
+        conflicts_with: cask
+            .get("conflicts_with")
+            .map(describe_requirements)
+            .unwrap_or_default(),
+        ..CaskInfo::default()
+    };
+
+    let entries = cask.get("artifacts").and_then(Value::as_array);
+    for entry in entries.into_iter().flatten() {
+        let Some((key, args)) = entry.as_object().and_then(|object| object.iter().next()) else {
+            continue;
+        };
+        match key.as_str() {
+            "uninstall" => info.uninstall = parse_stanza(args, dirs),
+            "zap" => info.zap = parse_stanza(args, dirs),
+            "preflight" | "postflight" | "uninstall_preflight" | "uninstall_postflight" => {}
+            _ => info
+                .artifacts
+                .extend(parse_artifact(key, args, dirs, installed)),
+        }
+    }
+    info
+}
+
+fn parse_artifact(
+    key: &str,
+    args: &Value,
+    dirs: &CaskDirs,
+    installed: bool,
+) -> Option<CaskArtifact> {
+    let args = args.as_array()?;
+    let source = args.first()?.as_str()?;
+    let target = args
+        .iter()
+        .skip(1)
+        .find_map(|arg| arg.get("target").and_then(Value::as_str));
+    let kind = ArtifactKind::from_key(key);
+    // Installed names come from the target, else the source's file name.
+    let name = target
+        .map(str::to_string)
+        .unwrap_or_else(|| file_name(source).to_string());
+    let path = dirs.artifact_path(&kind, &name);
+    let present = path.as_deref().filter(|_| installed).map(Path::exists);
+    Some(CaskArtifact {
+        kind,
+        name,
+        path: path.map(|path| path.display().to_string()),
+        present,
+    })
+}
+
+/// Removed paths are checked even when the cask is gone: leftovers are what zap cleans.
+fn parse_stanza(args: &Value, dirs: &CaskDirs) -> Vec<StanzaItem> {
+    let mut items = Vec::new();
+    let objects = args
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_object);
+    for object in objects {
+        for (directive, value) in object {
+            let values: Vec<String> = match value {
+                Value::String(text) => vec![text.clone()],
+                Value::Array(list) => list.iter().map(value_text).collect(),
+                other => vec![value_text(other)],
+            };
+            for value in values {
+                let mut item = StanzaItem {
+                    directive: directive.clone(),
+                    value,
+                    present: None,
+                    size_kb: None,
+                };
+                if !item.removes_path() {
+                    items.push(item);
+                    continue;
+                }
+                if !is_glob(&item.value) {
+                    item.present = Some(expand_home(&item.value, &dirs.home).exists());
+                    items.push(item);
+                    continue;
+                }
+                // Each match is listed and sized on its own; a glob with none stays absent.
+                let matches = expand_glob(&item.value, &dirs.home);
+                if matches.is_empty() {
+                    item.present = Some(false);
+                    items.push(item);
+                }
+                items.extend(matches.into_iter().map(|value| StanzaItem {
+                    directive: directive.clone(),
+                    value,
+                    present: Some(true),
+                    size_kb: None,
+                }));
+            }
+        }
+    }
+    items
+}
+
+/// Renders `depends_on` / `conflicts_with` maps such as `{"macos": {">=": ["12"]}}`.
+fn describe_requirements(value: &Value) -> Vec<String> {
+    let Some(object) = value.as_object() else {
+        return Vec::new();
+    };
+    object
+        .iter()
+        .filter(|(_, value)| !value.is_null())
+        .map(|(key, value)| match value {
+            Value::Object(ops) => {
+                let constraints: Vec<String> = ops
+                    .iter()
+                    .map(|(op, versions)| format!("{op} {}", value_text(versions)))
+                    .collect();
+                format!("{key} {}", constraints.join(", "))
+            }
+            other => format!("{key}: {}", value_text(other)),
+        })
+        .collect()
+}
+
+fn value_text(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        Value::Array(list) => list.iter().map(value_text).collect::<Vec<_>>().join(", "),
+        Value::Object(object) => object
+            .iter()
+            .map(|(key, value)| format!("{key}={}", value_text(value)))
+            .collect::<Vec<_>>()
+            .join(" "),
+        other => other.to_string(),
+    }
+}
+
+fn file_name(path: &str) -> &str {
+    path.trim_end_matches('/')
+        .rsplit('/')
+        .next()
+        .unwrap_or(path)
+}
+
+fn is_glob(path: &str) -> bool {
+    path.contains(['*', '?'])
+}
+
+/// Existing paths matching a `*`/`?` pattern, written the way the pattern was
+/// (`~/` kept). Like Ruby's `Dir.glob`, wildcards skip dotfiles.
+fn expand_glob(pattern: &str, home: &Path) -> Vec<String> {
+    let (mut found, rest) = match pattern.strip_prefix("~/") {
+        Some(rest) => (vec![("~".to_string(), home.to_path_buf())], rest),
+        None => match pattern.strip_prefix('/') {
+            Some(rest) => (vec![(String::new(), PathBuf::from("/"))], rest),
+            None => return Vec::new(),
+        },
+    };
+    for component in rest.split('/').filter(|component| !component.is_empty()) {
+        if !is_glob(component) {
+            for (shown, path) in &mut found {
+                shown.push('/');
+                shown.push_str(component);
+                path.push(component);
+            }
+            continue;
+        }
+        let mut next = Vec::new();
+        for (shown, path) in &found {
+            let Ok(entries) = std::fs::read_dir(path) else {
+                continue;
+            };
+            let mut names: Vec<String> = entries
+                .flatten()
+                .map(|entry| entry.file_name().to_string_lossy().into_owned())
+                .filter(|name| {
+                    (component.starts_with('.') || !name.starts_with('.'))
+                        && wildcard_match(component.as_bytes(), name.as_bytes())
+                })
+                .collect();
+            names.sort();
+            next.extend(
+                names
+                    .into_iter()
+                    .map(|name| (format!("{shown}/{name}"), path.join(name))),
+            );
+        }
+        found = next;
+    }
+    found
+        .into_iter()
+        .filter(|(_, path)| path.exists())
+        .map(|(shown, _)| shown)
+        .collect()
+}
+
+fn wildcard_match(pattern: &[u8], name: &[u8]) -> bool {
+    match (pattern.first(), name.first()) {
+        (None, None) => true,
+        (Some(b'*'), _) => {
+            wildcard_match(&pattern[1..], name)
+                || (!name.is_empty() && wildcard_match(pattern, &name[1..]))
+        }
+        (Some(b'?'), Some(_)) => wildcard_match(&pattern[1..], &name[1..]),
+        (Some(expected), Some(actual)) if expected == actual => {
+            wildcard_match(&pattern[1..], &name[1..])
+        }
+        _ => false,
+    }
+}
+
+fn expand_home(path: &str, home: &Path) -> PathBuf {
+    match path.strip_prefix("~/") {
+        Some(rest) => home.join(rest),
+        None => PathBuf::from(path),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ArtifactKind, CaskDirs, parse_cask_info, parse_stanza};
+
+    #[test]
+    fn parses_cask_model_and_checks_presence() {
+        let root = std::env::temp_dir().join(format!("brewery-cask-{}", std::process::id()));
+        let dirs = CaskDirs {
+            home: root.join("home"),
+            prefix: root.join("prefix"),
+            appdir: root.join("Applications"),
+            fontdir: root.join("home/Library/Fonts"),
+        };
+        std::fs::create_dir_all(dirs.appdir.join("Firefox.app")).expect("app dir");
+        std::fs::create_dir_all(dirs.home.join("Library/Caches/Firefox")).expect("cache dir");
+
+        let cask: serde_json::Value = serde_json::from_str(
+            r#"{
+                "auto_updates": true,
+                "depends_on": {"macos": {">=": ["10.15"]}},
+                "conflicts_with": {"cask": ["firefox@beta"]},
+                "artifacts": [
+                    {"uninstall": [{"quit": "org.mozilla.firefox"}]},
+                    {"app": ["Firefox.app"]},
+                    {"binary": ["$APPDIR/Firefox.app/Contents/MacOS/firefox", {"target": "firefox"}]},
+                    {"zap": [{"trash": ["~/Library/Caches/Firefox", "~/Library/Preferences/org.mozilla.firefox.plist"]}]}
+                ]
+            }"#,
+        )
+        .expect("json");
+        let info = parse_cask_info(&cask, &dirs, true);
+        std::fs::remove_dir_all(&root).ok();
+
+        assert!(info.auto_updates);
+        assert_eq!(info.depends_on, ["macos >= 10.15"]);
+        assert_eq!(info.conflicts_with, ["cask: firefox@beta"]);
+        assert_eq!(info.artifacts.len(), 2);
+        assert_eq!(info.artifacts[0].kind, ArtifactKind::App);
+        assert_eq!(info.artifacts[0].present, Some(true));
+        assert_eq!(info.artifacts[1].name, "firefox");
+        assert_eq!(info.artifacts[1].present, Some(false));
+        assert_eq!(info.missing_artifacts(), 1);
+        assert_eq!(info.uninstall[0].directive, "quit");
+        assert_eq!(info.uninstall[0].present, None);
+        let zap_present: Vec<_> = info.zap.iter().map(|item| item.present).collect();
+        assert_eq!(zap_present, [Some(true), Some(false)]);
+    }
+
+    #[test]
+    fn expands_zap_globs_into_present_paths() {
+        let root = std::env::temp_dir().join(format!("brewery-zap-glob-{}", std::process::id()));
+        let dirs = CaskDirs {
+            home: root.join("home"),
+            prefix: root.join("prefix"),
+            appdir: root.join("Applications"),
+            fontdir: root.join("home/Library/Fonts"),
+        };
+        let caches = dirs.home.join("Library/Caches");
+        std::fs::create_dir_all(&caches).expect("cache dir");
+        for name in [
+            "com.foo.app",
+            "com.foo.helper",
+            ".com.foo.hidden",
+            "com.bar",
+        ] {
+            std::fs::write(caches.join(name), "").expect("cache file");
+        }
+
+        let zap: serde_json::Value = serde_json::from_str(
+            r#"[{"trash": ["~/Library/Caches/com.foo.*", "~/Library/*/org.none.?"]}]"#,
+        )
+        .expect("json");
+        let items = parse_stanza(&zap, &dirs);
+        std::fs::remove_dir_all(&root).ok();
+
+        let listed: Vec<_> = items
+            .iter()
+            .map(|item| (item.value.as_str(), item.present))
+            .collect();
+        assert_eq!(
+            listed,
+            [
+                ("~/Library/Caches/com.foo.app", Some(true)),
+                ("~/Library/Caches/com.foo.helper", Some(true)),
+                ("~/Library/*/org.none.?", Some(false)),
+            ]
+        );
+    }
+}
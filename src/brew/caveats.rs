@@ -0,0 +1,73 @@
+/// Pulls the `==> Caveats` sections out of `brew install` output.
+///
+/// Per-package subheadings (`==> jq:`) inside the section are skipped and
+/// repeated blocks, as printed again in brew's end-of-run summary, are kept once.
+pub fn extract_caveats(output: &str) -> Option<String> {
+    let mut blocks: Vec<Vec<&str>> = Vec::new();
+    let mut current: Option<Vec<&str>> = None;
+
+    for line in output.lines() {
+        let trimmed = line.trim_end();
+        if trimmed == "==> Caveats" {
+            blocks.extend(current.take());
+            current = Some(Vec::new());
+            continue;
+        }
+        let Some(block) = current.as_mut() else {
+            continue;
+        };
+        if let Some(heading) = trimmed.strip_prefix("==> ") {
+            if heading.ends_with(':') {
+                blocks.extend(current.replace(Vec::new()));
+                continue;
+            }
+            blocks.extend(current.take());
+            continue;
+        }
+        block.push(trimmed);
+    }
+    blocks.extend(current);
+
+    let mut seen: Vec<String> = Vec::new();
+    for block in blocks {
+        let text = block.join("\n").trim_matches('\n').to_string();
+        if !text.trim().is_empty() && !seen.contains(&text) {
+            seen.push(text);
+        }
+    }
+    (!seen.is_empty()).then(|| seen.join("\n\n"))
+}
+
+/// Caveats indent the commands and paths they ask you to run or edit.
+pub fn is_shell_snippet(line: &str) -> bool {
+    let trimmed = line.trim();
+    !trimmed.is_empty() && (line.starts_with([' ', '\t']) || trimmed.starts_with("$ "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{extract_caveats, is_shell_snippet};
+
+    #[test]
+    fn extracts_caveats_once_and_spots_snippets() {
+        let output = "\
+==> Pouring openssl@3--3.3.1.arm64_sonoma.bottle.tar.gz
+==> Caveats
+If you need to have openssl@3 first in your PATH, run:
+  echo 'export PATH=\"/opt/homebrew/opt/openssl@3/bin:$PATH\"' >> ~/.zshrc
+==> Summary
+🍺  /opt/homebrew/Cellar/openssl@3/3.3.1: 6,986 files, 32.5MB
+==> Caveats
+==> openssl@3:
+If you need to have openssl@3 first in your PATH, run:
+  echo 'export PATH=\"/opt/homebrew/opt/openssl@3/bin:$PATH\"' >> ~/.zshrc
+";
+        let caveats = extract_caveats(output).expect("caveats");
+        let lines: Vec<&str> = caveats.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(!is_shell_snippet(lines[0]));
+        assert!(is_shell_snippet(lines[1]));
+
+        assert_eq!(extract_caveats("==> Summary\n🍺 done"), None);
+    }
+}
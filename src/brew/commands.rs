@@ -1,4 +1,10 @@
 use std::fmt;
+use std::process::Stdio;
+
+use tokio::io::{AsyncBufReadExt, AsyncRead, BufReader};
+use tokio::sync::mpsc;
+
+use crate::storage::CommandRecord;
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum CommandKind {
@@ -10,11 +16,21 @@ pub enum CommandKind {
     ServiceStart,
     ServiceStop,
     ServiceRestart,
+    ServiceRun,
+    ServiceKill,
+    ServiceCleanup,
     ServiceInfo,
     SelfUpdate,
     Cleanup,
     Autoremove,
     BundleDump,
+    DoctorFix,
+    Update,
+    SwitchKeg,
+    RemoveKeg,
+    Link,
+    Unlink,
+    LinkPreview,
 }
 
 impl CommandKind {
@@ -28,11 +44,30 @@ impl CommandKind {
             Self::ServiceStart => "services start",
             Self::ServiceStop => "services stop",
             Self::ServiceRestart => "services restart",
+            Self::ServiceRun => "services run",
+            Self::ServiceKill => "services kill",
+            Self::ServiceCleanup => "services cleanup",
             Self::ServiceInfo => "services info",
             Self::SelfUpdate => "self-update",
             Self::Cleanup => "cleanup",
             Self::Autoremove => "autoremove",
             Self::BundleDump => "bundle dump",
+            Self::DoctorFix => "doctor fix",
+            Self::Update => "update",
+            Self::SwitchKeg => "switch version",
+            Self::RemoveKeg => "remove version",
+            Self::Link => "link",
+            Self::Unlink => "unlink",
+            Self::LinkPreview => "link preview",
+        }
+    }
+
+    /// Program the command's args are passed to.
+    pub fn binary(self) -> &'static str {
+        match self {
+            Self::SelfUpdate => "cargo",
+            Self::SwitchKeg | Self::RemoveKeg => "sh",
+            _ => "brew",
         }
     }
 
@@ -43,12 +78,28 @@ impl CommandKind {
     pub fn is_service_action(self) -> bool {
         matches!(
             self,
-            Self::ServiceStart | Self::ServiceStop | Self::ServiceRestart
+            Self::ServiceStart
+                | Self::ServiceStop
+                | Self::ServiceRestart
+                | Self::ServiceRun
+                | Self::ServiceKill
         )
     }
 
+    pub fn is_keg_action(self) -> bool {
+        matches!(self, Self::SwitchKeg | Self::RemoveKeg)
+    }
+
+    pub fn is_link_action(self) -> bool {
+        matches!(self, Self::Link | Self::Unlink | Self::LinkPreview)
+    }
+
     pub fn has_named_target(self) -> bool {
-        self.is_package_action() || self.is_service_action() || matches!(self, Self::ServiceInfo)
+        self.is_package_action()
+            || self.is_service_action()
+            || self.is_keg_action()
+            || self.is_link_action()
+            || matches!(self, Self::ServiceInfo)
     }
 
     pub fn is_activity_command(self) -> bool {
@@ -61,8 +112,18 @@ impl CommandKind {
                 | Self::ServiceStart
                 | Self::ServiceStop
                 | Self::ServiceRestart
+                | Self::ServiceRun
+                | Self::ServiceKill
+                | Self::ServiceCleanup
                 | Self::ServiceInfo
                 | Self::SelfUpdate
+                | Self::DoctorFix
+                | Self::Update
+                | Self::SwitchKeg
+                | Self::RemoveKeg
+                | Self::Link
+                | Self::Unlink
+                | Self::LinkPreview
         )
     }
 
@@ -79,7 +140,23 @@ impl CommandKind {
     }
 
     pub fn refreshes_status_on_success(self) -> bool {
-        self.refreshes_lists_on_success() || self.is_service_action()
+        self.refreshes_lists_on_success()
+            || self.is_service_action()
+            || self.is_keg_action()
+            || matches!(self, Self::ServiceCleanup | Self::DoctorFix | Self::Update)
+    }
+
+    /// Commands that change which installed packages can be deprecated.
+    pub fn refreshes_deprecations_on_success(self) -> bool {
+        matches!(
+            self,
+            Self::Install | Self::Uninstall | Self::Upgrade | Self::UpgradeAll
+        )
+    }
+
+    /// Commands that change what `brew info` reports for their target.
+    pub fn refreshes_details_on_success(self) -> bool {
+        matches!(self, Self::Upgrade | Self::Link | Self::Unlink) || self.is_keg_action()
     }
 
     pub fn action_title(self) -> &'static str {
@@ -90,7 +167,14 @@ impl CommandKind {
             Self::ServiceStart => "Start service",
             Self::ServiceStop => "Stop service",
             Self::ServiceRestart => "Restart service",
+            Self::ServiceRun => "Run service",
+            Self::ServiceKill => "Kill service",
             Self::ServiceInfo => "Service info",
+            Self::SwitchKeg => "Version switch",
+            Self::RemoveKeg => "Version removal",
+            Self::Link => "Link",
+            Self::Unlink => "Unlink",
+            Self::LinkPreview => "Link preview",
             _ => "Action",
         }
     }
@@ -113,6 +197,15 @@ pub struct CommandResult {
 pub struct CommandMessage {
     pub kind: CommandKind,
     pub result: anyhow::Result<CommandResult>,
+    /// History entry for this run, already saved to disk when possible.
+    pub record: CommandRecord,
+}
+
+/// What a running command reports on the command channel.
+pub enum CommandEvent {
+    /// A line of stdout or stderr, as soon as it is printed.
+    Output(String),
+    Finished(CommandMessage),
 }
 
 pub async fn run_brew_command(args: &[&str]) -> anyhow::Result<CommandResult> {
@@ -135,6 +228,52 @@ pub async fn run_command(binary: &str, args: &[&str]) -> anyhow::Result<CommandR
     })
 }
 
+/// Runs a command like [`run_command`], forwarding each output line as it arrives.
+pub async fn run_command_streaming(
+    binary: &str,
+    args: &[&str],
+    tx: &mpsc::UnboundedSender<CommandEvent>,
+) -> anyhow::Result<CommandResult> {
+    let mut child = tokio::process::Command::new(binary)
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    let stdout = child.stdout.take().expect("stdout is piped");
+    let stderr = child.stderr.take().expect("stderr is piped");
+    let (stdout, stderr) = tokio::join!(forward_lines(stdout, tx), forward_lines(stderr, tx));
+    let status = child.wait().await?;
+
+    Ok(CommandResult {
+        stdout: stdout?,
+        stderr: stderr?,
+        success: status.success(),
+        exit_code: status.code(),
+    })
+}
+
+async fn forward_lines(
+    stream: impl AsyncRead + Unpin,
+    tx: &mpsc::UnboundedSender<CommandEvent>,
+) -> std::io::Result<String> {
+    let mut reader = BufReader::new(stream);
+    let mut collected = String::new();
+    let mut buf = Vec::new();
+    loop {
+        buf.clear();
+        if reader.read_until(b'\n', &mut buf).await? == 0 {
+            return Ok(collected);
+        }
+        let line = String::from_utf8_lossy(&buf);
+        collected.push_str(&line);
+        let line = line.trim_end();
+        if !line.trim().is_empty() {
+            let _ = tx.send(CommandEvent::Output(line.to_string()));
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::CommandKind;
@@ -158,10 +297,26 @@ mod tests {
         assert!(!CommandKind::ServiceInfo.refreshes_lists_on_success());
     }
 
+    #[test]
+    fn refreshes_deprecations_only_for_package_changes() {
+        assert!(CommandKind::Install.refreshes_deprecations_on_success());
+        assert!(CommandKind::Uninstall.refreshes_deprecations_on_success());
+        assert!(CommandKind::Upgrade.refreshes_deprecations_on_success());
+        assert!(CommandKind::UpgradeAll.refreshes_deprecations_on_success());
+        assert!(!CommandKind::Update.refreshes_deprecations_on_success());
+        assert!(!CommandKind::ServiceStart.refreshes_deprecations_on_success());
+    }
+
     #[test]
     fn refreshes_status_for_service_actions() {
         assert!(CommandKind::ServiceStart.refreshes_status_on_success());
         assert!(CommandKind::ServiceStop.refreshes_status_on_success());
         assert!(CommandKind::ServiceRestart.refreshes_status_on_success());
+        assert!(CommandKind::ServiceRun.refreshes_status_on_success());
+        assert!(CommandKind::ServiceKill.refreshes_status_on_success());
+        assert!(CommandKind::ServiceCleanup.refreshes_status_on_success());
+        assert!(CommandKind::DoctorFix.refreshes_status_on_success());
+        assert!(CommandKind::Update.refreshes_status_on_success());
+        assert!(!CommandKind::Update.refreshes_lists_on_success());
     }
 }
@@ -0,0 +1,213 @@
+use super::process::{ensure_success, run_brew};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DeprecationState {
+    Deprecated,
+    Disabled,
+}
+
+impl DeprecationState {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Deprecated => "deprecated",
+            Self::Disabled => "disabled",
+        }
+    }
+}
+
+/// Homebrew's deprecation or disable notice for a formula or cask.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Deprecation {
+    pub state: DeprecationState,
+    pub date: Option<String>,
+    pub reason: Option<String>,
+    pub replacement: Option<String>,
+    pub replacement_is_cask: bool,
+}
+
+impl Deprecation {
+    /// "Deprecated since 2024-05-01: does not build".
+    pub fn summary(&self) -> String {
+        let mut text = match self.state {
+            DeprecationState::Deprecated => "Deprecated".to_string(),
+            DeprecationState::Disabled => "Disabled".to_string(),
+        };
+        if let Some(date) = self.date.as_deref() {
+            text.push_str(&format!(" since {date}"));
+        }
+        if let Some(reason) = self.reason.as_deref() {
+            text.push_str(&format!(": {reason}"));
+        }
+        text
+    }
+}
+
+/// An installed formula or cask Homebrew has deprecated or disabled.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct DeprecatedPackage {
+    pub name: String,
+    pub cask: bool,
+    pub deprecation: Deprecation,
+}
+
+/// The deprecation fields of a formula or cask entry, flattened into its parent.
+#[derive(Default, serde::Deserialize)]
+pub(super) struct RawDeprecation {
+    #[serde(default)]
+    deprecated: bool,
+    deprecation_date: Option<String>,
+    deprecation_reason: Option<String>,
+    /// Before Homebrew 4.5 the replacement's kind was implied by the package's own.
+    deprecation_replacement: Option<String>,
+    deprecation_replacement_formula: Option<String>,
+    deprecation_replacement_cask: Option<String>,
+    #[serde(default)]
+    disabled: bool,
+    disable_date: Option<String>,
+    disable_reason: Option<String>,
+    disable_replacement: Option<String>,
+    disable_replacement_formula: Option<String>,
+    disable_replacement_cask: Option<String>,
+}
+
+impl RawDeprecation {
+    /// Disabled wins over deprecated; `cask` says what kind the package itself is.
+    pub(super) fn into_deprecation(self, cask: bool) -> Option<Deprecation> {
+        let (state, date, reason, legacy, formula, cask_replacement) = if self.disabled {
+            (
+                DeprecationState::Disabled,
+                self.disable_date,
+                self.disable_reason,
+                self.disable_replacement,
+                self.disable_replacement_formula,
+                self.disable_replacement_cask,
+            )
+        } else if self.deprecated {
+            (
+                DeprecationState::Deprecated,
+                self.deprecation_date,
+                self.deprecation_reason,
+                self.deprecation_replacement,
+                self.deprecation_replacement_formula,
+                self.deprecation_replacement_cask,
+            )
+        } else {
+            return None;
+        };
+        let (replacement, replacement_is_cask) = match (formula, cask_replacement, legacy) {
+            (Some(formula), _, _) => (Some(formula), false),
+            (None, Some(cask_name), _) => (Some(cask_name), true),
+            (None, None, legacy) => (legacy, cask),
+        };
+        Some(Deprecation {
+            state,
+            date,
+            reason: reason.map(|reason| humanize_reason(&reason)),
+            replacement,
+            replacement_is_cask,
+        })
+    }
+}
+
+#[derive(serde::Deserialize)]
+struct InstalledInfo {
+    #[serde(default)]
+    formulae: Vec<FormulaEntry>,
+    #[serde(default)]
+    casks: Vec<CaskEntry>,
+}
+
+#[derive(serde::Deserialize)]
+struct FormulaEntry {
+    name: String,
+    #[serde(flatten)]
+    deprecation: RawDeprecation,
+}
+
+#[derive(serde::Deserialize)]
+struct CaskEntry {
+    token: String,
+    #[serde(flatten)]
+    deprecation: RawDeprecation,
+}
+
+pub struct DeprecatedMessage {
+    pub result: anyhow::Result<Vec<DeprecatedPackage>>,
+}
+
+/// Every installed formula and cask that is deprecated or disabled.
+pub async fn fetch_deprecated() -> anyhow::Result<Vec<DeprecatedPackage>> {
+    let output = run_brew(&["info", "--json=v2", "--installed"]).await?;
+    ensure_success(&output, "brew info --installed failed")?;
+    parse_deprecated(&output.stdout)
+}
+
+fn parse_deprecated(stdout: &[u8]) -> anyhow::Result<Vec<DeprecatedPackage>> {
+    let info: InstalledInfo = serde_json::from_slice(stdout)?;
+    let formulae = info
+        .formulae
+        .into_iter()
+        .map(|entry| (entry.name, false, entry.deprecation));
+    let casks = info
+        .casks
+        .into_iter()
+        .map(|entry| (entry.token, true, entry.deprecation));
+    Ok(formulae
+        .chain(casks)
+        .filter_map(|(name, cask, raw)| {
+            raw.into_deprecation(cask)
+                .map(|deprecation| DeprecatedPackage {
+                    name,
+                    cask,
+                    deprecation,
+                })
+        })
+        .collect())
+}
+
+/// Homebrew reports built-in reasons as symbols such as `does_not_build`.
+fn humanize_reason(reason: &str) -> String {
+    if reason.contains(' ') {
+        reason.to_string()
+    } else {
+        reason.replace('_', " ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DeprecationState, parse_deprecated};
+
+    #[test]
+    fn parses_deprecated_and_disabled_packages() {
+        let json = br#"{
+            "formulae": [
+                {"name": "jq", "deprecated": false, "disabled": false},
+                {"name": "python@3.8", "deprecated": false, "disabled": true,
+                 "disable_date": "2024-10-14", "disable_reason": "unsupported",
+                 "disable_replacement_formula": "python@3.12"},
+                {"name": "youtube-dl", "deprecated": true, "deprecation_date": "2024-01-01",
+                 "deprecation_reason": "does_not_build", "deprecation_replacement": "yt-dlp"}
+            ],
+            "casks": [
+                {"token": "old-app", "name": ["Old App"], "deprecated": true,
+                 "deprecation_reason": "discontinued", "deprecation_replacement_cask": "new-app"}
+            ]
+        }"#;
+        let packages = parse_deprecated(json).expect("parse");
+        assert_eq!(packages.len(), 3);
+
+        let python = &packages[0].deprecation;
+        assert_eq!(python.state, DeprecationState::Disabled);
+        assert_eq!(python.replacement.as_deref(), Some("python@3.12"));
+        assert_eq!(python.summary(), "Disabled since 2024-10-14: unsupported");
+
+        let youtube = &packages[1].deprecation;
+        assert_eq!(youtube.reason.as_deref(), Some("does not build"));
+        assert!(!youtube.replacement_is_cask);
+
+        assert!(packages[2].cask);
+        assert!(packages[2].deprecation.replacement_is_cask);
+    }
+}
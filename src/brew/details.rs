@@ -1,14 +1,49 @@
+use super::cask::{CaskDirs, CaskInfo, measure_zap_paths, parse_cask_info};
+use super::deprecation::{Deprecation, RawDeprecation};
+use super::link::describe_keg_only_reason;
+use super::metadata::{FormulaMetadata, RawMetadata};
+use super::paths::PackagePaths;
 use super::process::{ensure_success, nonempty_lines, run_brew};
+use super::size::{SizeEntry, fetch_keg_sizes};
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
 pub struct Details {
     pub desc: Option<String>,
     pub homepage: Option<String>,
     pub latest: Option<String>,
     pub installed: Vec<String>,
+    /// Version the formula's symlinks point at, if linked.
+    #[serde(default)]
+    pub linked_keg: Option<String>,
+    /// Installed outside the prefix; brew won't link it without `--force`.
+    #[serde(default)]
+    pub keg_only: bool,
+    #[serde(default)]
+    pub keg_only_reason: Option<String>,
+    /// Post-install notes such as PATH changes or service hints.
+    #[serde(default)]
+    pub caveats: Option<String>,
+    /// License, tap, bottles and declared dependencies; formulae only.
+    #[serde(default)]
+    pub metadata: Option<FormulaMetadata>,
+    #[serde(default)]
+    pub deprecation: Option<Deprecation>,
+    /// Disk usage per installed version, loaded with deps/uses.
+    #[serde(default)]
+    pub keg_sizes: Option<Vec<SizeEntry>>,
     pub deps: Option<Vec<String>>,
     pub uses: Option<Vec<String>>,
-    pub artifacts: Option<Vec<String>>,
+    /// Artifacts, stanzas and requirements; present only for casks.
+    #[serde(default)]
+    pub cask: Option<CaskInfo>,
+    #[serde(default)]
+    pub paths: Option<PackagePaths>,
+}
+
+impl Details {
+    pub fn is_cask(&self) -> bool {
+        self.cask.is_some()
+    }
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -31,11 +66,29 @@ struct BrewInfo {
 
 #[derive(serde::Deserialize)]
 struct FormulaInfo {
+    name: Option<String>,
     desc: Option<String>,
     homepage: Option<String>,
     versions: Option<FormulaVersions>,
     #[serde(default)]
     installed: Vec<InstalledInfo>,
+    linked_keg: Option<String>,
+    #[serde(default)]
+    keg_only: bool,
+    keg_only_reason: Option<KegOnlyReason>,
+    caveats: Option<String>,
+    #[serde(flatten)]
+    metadata: RawMetadata,
+    #[serde(flatten)]
+    deprecation: RawDeprecation,
+}
+
+#[derive(serde::Deserialize)]
+struct KegOnlyReason {
+    #[serde(default)]
+    reason: String,
+    #[serde(default)]
+    explanation: String,
 }
 
 #[derive(serde::Deserialize)]
@@ -46,6 +99,8 @@ struct FormulaVersions {
 #[derive(serde::Deserialize)]
 struct InstalledInfo {
     version: String,
+    time: Option<u64>,
+    poured_from_bottle: Option<bool>,
 }
 
 pub async fn fetch_details_basic(pkg: &str) -> anyhow::Result<Details> {
@@ -55,12 +110,23 @@ pub async fn fetch_details_basic(pkg: &str) -> anyhow::Result<Details> {
     ensure_success(&output, &fallback)?;
 
     let info: BrewInfo = serde_json::from_slice(&output.stdout)?;
-    if let Some(formula) = info.formulae.first() {
+    if let Some(formula) = info.formulae.into_iter().next() {
         let installed = formula
             .installed
             .iter()
             .map(|item| item.version.clone())
             .collect();
+        let current_keg = formula
+            .installed
+            .iter()
+            .find(|item| formula.linked_keg.as_deref() == Some(item.version.as_str()))
+            .or_else(|| formula.installed.last());
+        let name = formula.name.as_deref().unwrap_or(pkg);
+        let paths = PackagePaths::formula(name).await;
+        let metadata = formula.metadata.into_metadata(
+            current_keg.and_then(|item| item.time),
+            current_keg.and_then(|item| item.poured_from_bottle),
+        );
 
         return Ok(Details {
             desc: formula.desc.clone(),
@@ -70,19 +136,36 @@ pub async fn fetch_details_basic(pkg: &str) -> anyhow::Result<Details> {
                 .as_ref()
                 .and_then(|versions| versions.stable.clone()),
             installed,
+            linked_keg: formula.linked_keg.clone(),
+            keg_only: formula.keg_only,
+            keg_only_reason: formula
+                .keg_only_reason
+                .as_ref()
+                .map(|reason| describe_keg_only_reason(&reason.reason, &reason.explanation)),
+            caveats: nonempty_caveats(formula.caveats.as_deref()),
+            metadata: Some(metadata),
+            deprecation: formula.deprecation.into_deprecation(false),
+            keg_sizes: None,
             deps: None,
             uses: None,
-            artifacts: None,
+            cask: None,
+            paths: Some(paths),
         });
     }
 
     let doc: serde_json::Value = serde_json::from_slice(&output.stdout)?;
-    parse_cask_details(&doc).ok_or_else(|| anyhow::anyhow!("No package info for {pkg}"))
+    let dirs = CaskDirs::detect().await;
+    let mut details = parse_cask_details(&doc, &dirs)
+        .ok_or_else(|| anyhow::anyhow!("No package info for {pkg}"))?;
+    let token = pkg.rsplit('/').next().unwrap_or(pkg);
+    details.paths = Some(PackagePaths::cask(token).await);
+    Ok(details)
 }
 
 pub async fn fetch_details_full(pkg: &str) -> anyhow::Result<Details> {
     let mut details = fetch_details_basic(pkg).await?;
-    if details.artifacts.is_some() {
+    if let Some(cask) = details.cask.as_mut() {
+        measure_zap_paths(cask).await;
         return Ok(details);
     }
 
@@ -90,6 +173,7 @@ pub async fn fetch_details_full(pkg: &str) -> anyhow::Result<Details> {
     let uses = run_brew_lines_async(["uses", "--installed", pkg]).await?;
     details.deps = Some(deps);
     details.uses = Some(uses);
+    details.keg_sizes = fetch_keg_sizes(pkg).await.ok();
     Ok(details)
 }
 
@@ -99,7 +183,7 @@ async fn run_brew_lines_async<const N: usize>(args: [&str; N]) -> anyhow::Result
     Ok(nonempty_lines(&output.stdout))
 }
 
-fn parse_cask_details(doc: &serde_json::Value) -> Option<Details> {
+fn parse_cask_details(doc: &serde_json::Value, dirs: &CaskDirs) -> Option<Details> {
     let cask = doc.get("casks")?.as_array()?.first()?;
 
     let desc = cask
@@ -115,77 +199,55 @@ fn parse_cask_details(doc: &serde_json::Value) -> Option<Details> {
         .and_then(serde_json::Value::as_str)
         .map(str::to_string);
 
-    let installed = cask
-        .get("installed")
-        .and_then(serde_json::Value::as_array)
-        .map(|entries| {
-            entries
-                .iter()
-                .filter_map(|entry| {
-                    entry
-                        .get("version")
-                        .and_then(serde_json::Value::as_str)
-                        .or_else(|| entry.as_str())
-                        .map(str::to_string)
-                })
-                .collect::<Vec<String>>()
-        })
-        .unwrap_or_default();
-
-    let artifacts = cask
-        .get("artifacts")
-        .and_then(serde_json::Value::as_array)
-        .map(|entries| {
-            entries
-                .iter()
-                .filter_map(format_cask_artifact)
-                .collect::<Vec<String>>()
-        })
-        .unwrap_or_default();
+    // Current Homebrew reports the installed cask version as a plain string.
+    let installed = match cask.get("installed") {
+        Some(serde_json::Value::String(version)) => vec![version.clone()],
+        other => other
+            .and_then(serde_json::Value::as_array)
+            .map(|entries| {
+                entries
+                    .iter()
+                    .filter_map(|entry| {
+                        entry
+                            .get("version")
+                            .and_then(serde_json::Value::as_str)
+                            .or_else(|| entry.as_str())
+                            .map(str::to_string)
+                    })
+                    .collect::<Vec<String>>()
+            })
+            .unwrap_or_default(),
+    };
+
+    let deprecation = serde_json::from_value::<RawDeprecation>(cask.clone())
+        .ok()
+        .and_then(|raw| raw.into_deprecation(true));
+    let caveats = nonempty_caveats(cask.get("caveats").and_then(serde_json::Value::as_str));
+
+    let cask_info = parse_cask_info(cask, dirs, !installed.is_empty());
 
     Some(Details {
         desc,
         homepage,
         latest,
         installed,
+        linked_keg: None,
+        keg_only: false,
+        keg_only_reason: None,
+        caveats,
+        metadata: None,
+        deprecation,
+        keg_sizes: None,
         deps: None,
         uses: None,
-        artifacts: Some(artifacts),
+        cask: Some(cask_info),
+        paths: None,
     })
 }
 
-fn format_cask_artifact(value: &serde_json::Value) -> Option<String> {
-    if let Some(text) = value.as_str() {
-        return Some(text.to_string());
-    }
-
-    if let Some(array) = value.as_array() {
-        let kind = array
-            .first()
-            .and_then(serde_json::Value::as_str)
-            .unwrap_or("artifact");
-        let label = array
-            .get(1)
-            .and_then(serde_json::Value::as_str)
-            .unwrap_or("");
-        if label.is_empty() {
-            return Some(kind.to_string());
-        }
-        return Some(format!("{kind}: {label}"));
-    }
-
-    if let Some(object) = value.as_object() {
-        let key = object.keys().next()?.to_string();
-        let first_value = object
-            .values()
-            .next()
-            .and_then(serde_json::Value::as_str)
-            .unwrap_or("");
-        if first_value.is_empty() {
-            return Some(key);
-        }
-        return Some(format!("{key}: {first_value}"));
-    }
-
-    None
+fn nonempty_caveats(caveats: Option<&str>) -> Option<String> {
+    caveats
+        .map(|text| text.trim_matches('\n').trim_end())
+        .filter(|text| !text.trim().is_empty())
+        .map(str::to_string)
 }
@@ -0,0 +1,265 @@
+use serde::{Deserialize, Serialize};
+
+use super::commands::run_brew_command;
+
+/// First words that mark a body line as a command brew suggests running.
+const COMMAND_WORDS: &[&str] = &[
+    "brew",
+    "sudo",
+    "chmod",
+    "chown",
+    "rm",
+    "mkdir",
+    "ln",
+    "mv",
+    "git",
+    "xcode-select",
+    "softwareupdate",
+    "launchctl",
+    "export",
+    "echo",
+    "eval",
+    "curl",
+];
+
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct DoctorReport {
+    pub ok: bool,
+    /// Every issue block, in the order brew printed them.
+    pub issues: Vec<DoctorIssue>,
+    /// Everything brew printed, stdout then stderr.
+    pub output: String,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IssueSeverity {
+    Warning,
+    Error,
+}
+
+impl IssueSeverity {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Warning => "Warning",
+            Self::Error => "Error",
+        }
+    }
+}
+
+/// One `Warning:`/`Error:` block with its explanation.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DoctorIssue {
+    pub severity: IssueSeverity,
+    pub title: String,
+    /// Lines under the headline, with brew's indentation kept.
+    pub body: Vec<String>,
+    /// Absolute or home-relative paths mentioned anywhere in the block.
+    pub paths: Vec<String>,
+    /// Suggested shell commands, one per line as printed.
+    pub commands: Vec<String>,
+}
+
+impl DoctorIssue {
+    pub fn headline(&self) -> String {
+        format!("{}: {}", self.severity.label(), self.title)
+    }
+}
+
+pub async fn fetch_doctor() -> anyhow::Result<DoctorReport> {
+    let result = run_brew_command(&["doctor"]).await?;
+    let output = [result.stdout.trim_end(), result.stderr.trim_end()]
+        .into_iter()
+        .filter(|stream| !stream.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n");
+    Ok(DoctorReport {
+        ok: result.success,
+        issues: if result.success {
+            Vec::new()
+        } else {
+            parse_doctor_issues(&output)
+        },
+        output,
+    })
+}
+
+/// Splits doctor output into issue blocks; the preamble before the first headline is dropped.
+pub(super) fn parse_doctor_issues(output: &str) -> Vec<DoctorIssue> {
+    let mut issues: Vec<DoctorIssue> = Vec::new();
+    for line in output.lines() {
+        if let Some((severity, title)) = parse_headline(line) {
+            issues.push(DoctorIssue {
+                severity,
+                title: title.to_string(),
+                body: Vec::new(),
+                paths: Vec::new(),
+                commands: Vec::new(),
+            });
+        } else if let Some(issue) = issues.last_mut() {
+            issue.body.push(line.trim_end().to_string());
+        }
+    }
+
+    for issue in &mut issues {
+        while issue.body.last().is_some_and(|line| line.is_empty()) {
+            issue.body.pop();
+        }
+        let leading_blank = issue.body.iter().take_while(|line| line.is_empty()).count();
+        issue.body.drain(..leading_blank);
+
+        for line in std::iter::once(&issue.title).chain(&issue.body) {
+            for path in extract_paths(line) {
+                if !issue.paths.contains(&path) {
+                    issue.paths.push(path);
+                }
+            }
+        }
+        issue.commands = issue
+            .body
+            .iter()
+            .map(|line| line.trim())
+            .filter(|line| is_suggested_command(line))
+            .map(str::to_string)
+            .collect();
+    }
+    issues
+}
+
+fn parse_headline(line: &str) -> Option<(IssueSeverity, &str)> {
+    if let Some(title) = line.strip_prefix("Warning:") {
+        Some((IssueSeverity::Warning, title.trim()))
+    } else {
+        line.strip_prefix("Error:")
+            .map(|title| (IssueSeverity::Error, title.trim()))
+    }
+}
+
+fn extract_paths(line: &str) -> Vec<String> {
+    line.split_whitespace()
+        .map(|token| {
+            token
+                .trim_start_matches(['(', '"', '\'', '`'])
+                .trim_end_matches(['.', ',', ':', ';', ')', '"', '\'', '`'])
+        })
+        .filter(|token| (token.starts_with('/') && token.len() > 1) || token.starts_with("~/"))
+        .map(str::to_string)
+        .collect()
+}
+
+/// Why a suggested command is copy-only. Only plain `brew …` commands are run,
+/// split on whitespace and without a shell; everything else is for the user to
+/// check and paste.
+pub fn copy_only_reason(command: &str) -> Option<&'static str> {
+    match command.split_whitespace().next()? {
+        "brew" if command.contains(SHELL_SYNTAX) => Some("uses shell syntax"),
+        "brew" => None,
+        "sudo" => Some("needs a password"),
+        "export" | "eval" | "echo" => Some("only takes effect in your own shell"),
+        _ => Some("is not a brew command"),
+    }
+}
+
+/// The arguments to pass to `brew` for a runnable suggested command.
+pub fn brew_command_args(command: &str) -> Option<Vec<&str>> {
+    if copy_only_reason(command).is_some() {
+        return None;
+    }
+    Some(command.split_whitespace().skip(1).collect())
+}
+
+/// Characters that would mean something to a shell rather than to brew.
+const SHELL_SYNTAX: &[char] = &[
+    ';', '&', '|', '<', '>', '$', '`', '(', ')', '"', '\'', '\\', '*', '?', '~', '\n',
+];
+
+fn is_suggested_command(line: &str) -> bool {
+    line.split_whitespace()
+        .next()
+        .is_some_and(|word| COMMAND_WORDS.contains(&word))
+        && line.contains(' ')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{IssueSeverity, brew_command_args, copy_only_reason, parse_doctor_issues};
+
+    const OUTPUT: &str = "\
+Please note that these warnings are just used to help the Homebrew maintainers
+with debugging if you file an issue.
+
+Warning: The following directories are not writable by your user:
+/usr/local/share/man/man8
+
+You should change the ownership of these directories to your user.
+  sudo chown -R $(whoami) /usr/local/share/man/man8
+
+And make sure that your user has write permission.
+  chmod u+w /usr/local/share/man/man8
+
+Error: Unbrewed dylibs were found in /usr/local/lib.
+Unexpected dylibs:
+  /usr/local/lib/libfoo.dylib
+";
+
+    #[test]
+    fn splits_blocks_with_bodies_paths_and_commands() {
+        let issues = parse_doctor_issues(OUTPUT);
+        assert_eq!(issues.len(), 2);
+
+        let first = &issues[0];
+        assert_eq!(first.severity, IssueSeverity::Warning);
+        assert_eq!(
+            first.title,
+            "The following directories are not writable by your user:"
+        );
+        assert_eq!(first.body.first().unwrap(), "/usr/local/share/man/man8");
+        assert_eq!(
+            first.body.last().unwrap(),
+            "  chmod u+w /usr/local/share/man/man8"
+        );
+        assert_eq!(first.paths, vec!["/usr/local/share/man/man8".to_string()]);
+        assert_eq!(
+            first.commands,
+            vec![
+                "sudo chown -R $(whoami) /usr/local/share/man/man8".to_string(),
+                "chmod u+w /usr/local/share/man/man8".to_string(),
+            ]
+        );
+
+        let second = &issues[1];
+        assert_eq!(second.severity, IssueSeverity::Error);
+        assert_eq!(
+            second.paths,
+            vec![
+                "/usr/local/lib".to_string(),
+                "/usr/local/lib/libfoo.dylib".to_string()
+            ]
+        );
+        assert!(second.commands.is_empty());
+    }
+
+    #[test]
+    fn runs_only_plain_brew_commands() {
+        assert_eq!(
+            copy_only_reason("sudo chown -R $(whoami) /usr/local/share/man/man8"),
+            Some("needs a password")
+        );
+        for command in [
+            "export PATH=\"/opt/homebrew/bin:$PATH\"",
+            "eval \"$(/opt/homebrew/bin/brew shellenv)\"",
+            "echo 'export PATH=\"/usr/local/sbin:$PATH\"' >> ~/.zshrc",
+            "chmod u+w /usr/local/share/man/man8",
+            "rm -rf /usr/local/lib/libfoo.dylib",
+            "brew cleanup && rm -rf ~/Library/Caches",
+            "brew link $(brew leaves)",
+        ] {
+            assert!(copy_only_reason(command).is_some(), "{command}");
+            assert!(brew_command_args(command).is_none(), "{command}");
+        }
+        assert_eq!(
+            brew_command_args("brew link --overwrite node@20"),
+            Some(vec!["link", "--overwrite", "node@20"])
+        );
+    }
+}
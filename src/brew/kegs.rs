@@ -0,0 +1,135 @@
+//! Acting on individual installed versions (kegs) of a formula.
+//!
+//! Both scripts take the version as `$1` and the formula as `$2`, so the
+//! formula stays the last argument and names the command's target.
+
+/// `brew link` follows the `opt/<formula>` symlink when several kegs exist,
+/// so switching repoints it between an unlink and a link. If any step fails the
+/// previous `opt` target is put back and linked again.
+pub const SWITCH_KEG_SCRIPT: &str = concat!(
+    r#"cellar="$(brew --cellar "$2")" && opt="$(brew --prefix)/opt/$2" || exit 1; "#,
+    r#"[ -d "$cellar/$1" ] || { echo "No keg at $cellar/$1" >&2; exit 1; }; "#,
+    r#"previous="$(readlink "$opt")"; "#,
+    r#"brew unlink "$2" || { brew link "$2"; exit 1; }; "#,
+    r#"ln -sfn "$cellar/$1" "$opt" && brew link --overwrite "$2" && exit 0; "#,
+    r#"echo "Linking $2 $1 failed; restoring the previous version" >&2; "#,
+    r#"if [ -n "$previous" ]; then ln -sfn "$previous" "$opt"; else rm -f "$opt"; fi; "#,
+    r#"brew link "$2"; exit 1"#,
+);
+
+/// Deletes one keg directory, as `brew cleanup` does for outdated versions:
+/// never the keg that `opt`, the linked-keg record or a pin resolves to.
+pub const REMOVE_KEG_SCRIPT: &str = concat!(
+    r#"cellar="$(brew --cellar "$2")" && prefix="$(brew --prefix)" || exit 1; "#,
+    r#"keg="$cellar/$1"; [ -d "$keg" ] || { echo "No keg at $keg" >&2; exit 1; }; "#,
+    r#"target="$(cd "$keg" && pwd -P)" || exit 1; "#,
+    r#"for link in "$prefix/opt/$2" "$prefix/var/homebrew/linked/$2" "$prefix/var/homebrew/pinned/$2"; do "#,
+    r#"[ -e "$link" ] && [ "$(cd "$link" && pwd -P)" = "$target" ] "#,
+    r#"&& { echo "$2 $1 is in use by ${link#"$prefix/"}; switch to another version first" >&2; exit 1; }; "#,
+    r#"done; "#,
+    r#"rm -rf "$keg""#,
+);
+
+/// `sh` arguments running `script` against one keg.
+pub fn keg_script_args<'a>(script: &'a str, formula: &'a str, version: &'a str) -> [&'a str; 5] {
+    ["-c", script, "brewery", version, formula]
+}
+
+/// Versions are directory names under the Cellar; refuse anything that could escape it.
+pub fn is_keg_version(version: &str) -> bool {
+    !version.is_empty() && version != "." && version != ".." && !version.contains('/')
+}
+
+#[cfg(test)]
+mod tests {
+    use std::os::unix::fs::{PermissionsExt, symlink};
+    use std::path::{Path, PathBuf};
+    use std::process::Command;
+
+    use super::{REMOVE_KEG_SCRIPT, SWITCH_KEG_SCRIPT, is_keg_version, keg_script_args};
+
+    /// A prefix with `node` 18 and 20 in the Cellar, `opt/node` on 18, and a
+    /// stub `brew` whose `link --overwrite` fails when `BREW_LINK_FAILS` is set.
+    fn fake_prefix(name: &str) -> PathBuf {
+        let root = std::env::temp_dir().join(format!("brewery-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        for version in ["18.0.0", "20.1.0"] {
+            std::fs::create_dir_all(root.join("Cellar/node").join(version)).unwrap();
+        }
+        std::fs::create_dir_all(root.join("opt")).unwrap();
+        std::fs::create_dir_all(root.join("bin")).unwrap();
+        symlink("../Cellar/node/18.0.0", root.join("opt/node")).unwrap();
+        let brew = root.join("bin/brew");
+        std::fs::write(
+            &brew,
+            format!(
+                "#!/bin/sh\ncase \"$1\" in\n--cellar) echo \"{0}/Cellar/$2\" ;;\n--prefix) echo \"{0}\" ;;\nlink) [ \"$2\" = --overwrite ] && [ -n \"$BREW_LINK_FAILS\" ] && exit 1 ;;\nesac\nexit 0\n",
+                root.display()
+            ),
+        )
+        .unwrap();
+        std::fs::set_permissions(&brew, std::fs::Permissions::from_mode(0o755)).unwrap();
+        root
+    }
+
+    fn run_script(root: &Path, script: &str, version: &str, fail_link: bool) -> bool {
+        let path = format!(
+            "{}:{}",
+            root.join("bin").display(),
+            std::env::var("PATH").unwrap()
+        );
+        let mut command = Command::new("sh");
+        command
+            .args(keg_script_args(script, "node", version))
+            .env("PATH", path);
+        if fail_link {
+            command.env("BREW_LINK_FAILS", "1");
+        }
+        command.output().unwrap().status.success()
+    }
+
+    #[test]
+    fn switching_restores_the_previous_keg_when_linking_fails() {
+        let root = fake_prefix("switch-keg");
+        let opt = root.join("opt/node");
+
+        assert!(!run_script(&root, SWITCH_KEG_SCRIPT, "20.1.0", true));
+        assert_eq!(
+            std::fs::read_link(&opt).unwrap(),
+            Path::new("../Cellar/node/18.0.0")
+        );
+
+        assert!(run_script(&root, SWITCH_KEG_SCRIPT, "20.1.0", false));
+        assert_eq!(
+            std::fs::read_link(&opt).unwrap(),
+            root.join("Cellar/node/20.1.0")
+        );
+
+        std::fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn keeps_formula_last_and_rejects_path_versions() {
+        let args = keg_script_args(SWITCH_KEG_SCRIPT, "node", "20.1.0");
+        assert_eq!(args[3], "20.1.0");
+        assert_eq!(args.last(), Some(&"node"));
+
+        assert!(is_keg_version("1.7.1_1"));
+        assert!(!is_keg_version(".."));
+        assert!(!is_keg_version("../../bin"));
+        assert!(!is_keg_version(""));
+    }
+
+    #[test]
+    fn removal_refuses_the_keg_opt_points_at() {
+        let root = fake_prefix("remove-keg");
+
+        assert!(!run_script(&root, REMOVE_KEG_SCRIPT, "18.0.0", false));
+        assert!(root.join("Cellar/node/18.0.0").is_dir());
+
+        assert!(run_script(&root, REMOVE_KEG_SCRIPT, "20.1.0", false));
+        assert!(!root.join("Cellar/node/20.1.0").exists());
+
+        std::fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,109 @@
+/// Result of `brew link --overwrite --dry-run`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LinkPreview {
+    /// Existing files a real `--overwrite` link would delete.
+    pub conflicts: Vec<String>,
+    /// Symlinks a plain link would create.
+    pub would_link: usize,
+    /// Brew said the keg is already linked, so nothing was checked.
+    pub already_linked: bool,
+}
+
+impl LinkPreview {
+    pub fn headline(&self) -> String {
+        if self.already_linked {
+            return "already linked".to_string();
+        }
+        match self.conflicts.len() {
+            0 => "no conflicting files".to_string(),
+            1 => "1 conflicting file".to_string(),
+            count => format!("{count} conflicting files"),
+        }
+    }
+}
+
+pub fn parse_link_preview(output: &str) -> LinkPreview {
+    let mut preview = LinkPreview::default();
+    let mut in_conflicts = false;
+    let mut in_links = false;
+
+    for line in output.lines().map(str::trim) {
+        if line.starts_with("Warning: Already linked") {
+            preview.already_linked = true;
+        } else if line.starts_with("Would remove:") {
+            (in_conflicts, in_links) = (true, false);
+        } else if line.starts_with("Would link:") {
+            (in_conflicts, in_links) = (false, true);
+        } else if line.starts_with('/') {
+            if in_conflicts {
+                let path = line.split_once(" -> ").map_or(line, |(path, _)| path);
+                preview.conflicts.push(path.to_string());
+            } else if in_links {
+                preview.would_link += 1;
+            }
+        } else if !line.is_empty() {
+            (in_conflicts, in_links) = (false, false);
+        }
+    }
+    preview
+}
+
+/// Brew's wording for the symbolic keg-only reasons, plus any explanation.
+pub fn describe_keg_only_reason(reason: &str, explanation: &str) -> String {
+    let text = match reason {
+        ":provided_by_macos" => "macOS already provides this software".to_string(),
+        ":shadowed_by_macos" => "macOS provides similar software".to_string(),
+        ":versioned_formula" => "this is an alternate version of another formula".to_string(),
+        other => other.trim_start_matches(':').replace('_', " "),
+    };
+    let explanation = explanation.trim();
+    if explanation.is_empty() {
+        text
+    } else if text.is_empty() {
+        explanation.to_string()
+    } else {
+        format!("{text}; {explanation}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{describe_keg_only_reason, parse_link_preview};
+
+    #[test]
+    fn collects_conflicting_paths() {
+        let output = "\
+Would remove:
+/usr/local/bin/jq -> /usr/local/Cellar/other/1.0/bin/jq
+/usr/local/share/man/man1/jq.1
+";
+        let preview = parse_link_preview(output);
+        assert_eq!(
+            preview.conflicts,
+            vec!["/usr/local/bin/jq", "/usr/local/share/man/man1/jq.1"]
+        );
+        assert_eq!(preview.headline(), "2 conflicting files");
+
+        let linked = parse_link_preview(
+            "Warning: Already linked: /usr/local/Cellar/jq/1.7.1\nTo relink, run:\n  brew unlink jq && brew link jq\n",
+        );
+        assert!(linked.already_linked);
+        assert!(linked.conflicts.is_empty());
+    }
+
+    #[test]
+    fn describes_symbolic_and_custom_reasons() {
+        assert_eq!(
+            describe_keg_only_reason(":versioned_formula", ""),
+            "this is an alternate version of another formula"
+        );
+        assert_eq!(
+            describe_keg_only_reason(":shadowed_by_macos", "macOS provides libffi"),
+            "macOS provides similar software; macOS provides libffi"
+        );
+        assert_eq!(
+            describe_keg_only_reason("", "it conflicts with `curl`"),
+            "it conflicts with `curl`"
+        );
+    }
+}
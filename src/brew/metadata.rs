@@ -0,0 +1,198 @@
+use std::collections::BTreeMap;
+
+/// Formula facts from `brew info --json=v2` beyond description and versions.
+#[derive(Clone, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct FormulaMetadata {
+    pub license: Option<String>,
+    pub tap: Option<String>,
+    /// Bottle tags usable on this platform; `None` when the formula ships no bottles.
+    pub bottle_tags: Option<Vec<String>>,
+    pub options: Vec<String>,
+    pub conflicts_with: Vec<String>,
+    pub runtime_deps: Vec<String>,
+    pub build_deps: Vec<String>,
+    pub optional_deps: Vec<String>,
+    pub recommended_deps: Vec<String>,
+    pub requirements: Vec<String>,
+    pub aliases: Vec<String>,
+    pub oldnames: Vec<String>,
+    /// Unix time the linked (or newest) keg was installed.
+    pub installed_at: Option<u64>,
+    pub poured_from_bottle: Option<bool>,
+}
+
+/// The metadata fields of a formula entry, flattened into its parent.
+#[derive(Default, serde::Deserialize)]
+pub(super) struct RawMetadata {
+    license: Option<String>,
+    tap: Option<String>,
+    #[serde(default)]
+    bottle: BTreeMap<String, RawBottle>,
+    #[serde(default)]
+    options: Vec<RawOption>,
+    #[serde(default)]
+    conflicts_with: Vec<String>,
+    #[serde(default)]
+    dependencies: Vec<String>,
+    #[serde(default)]
+    build_dependencies: Vec<String>,
+    #[serde(default)]
+    optional_dependencies: Vec<String>,
+    #[serde(default)]
+    recommended_dependencies: Vec<String>,
+    #[serde(default)]
+    requirements: Vec<RawRequirement>,
+    #[serde(default)]
+    aliases: Vec<String>,
+    #[serde(default)]
+    oldnames: Vec<String>,
+    /// Pre-4.3 Homebrew reports a single `oldname`.
+    oldname: Option<String>,
+}
+
+#[derive(serde::Deserialize)]
+struct RawBottle {
+    #[serde(default)]
+    files: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(serde::Deserialize)]
+struct RawOption {
+    option: String,
+    #[serde(default)]
+    description: String,
+}
+
+#[derive(serde::Deserialize)]
+struct RawRequirement {
+    name: String,
+    version: Option<String>,
+    #[serde(default)]
+    contexts: Vec<String>,
+}
+
+impl RawMetadata {
+    pub(super) fn into_metadata(
+        self,
+        installed_at: Option<u64>,
+        poured_from_bottle: Option<bool>,
+    ) -> FormulaMetadata {
+        let bottle_tags = self.bottle.get("stable").map(|bottle| {
+            platform_bottle_tags(
+                bottle.files.keys().map(String::as_str),
+                std::env::consts::OS,
+                std::env::consts::ARCH,
+            )
+        });
+        let mut oldnames = self.oldnames;
+        if let Some(name) = self.oldname
+            && !oldnames.contains(&name)
+        {
+            oldnames.push(name);
+        }
+
+        FormulaMetadata {
+            license: self.license,
+            tap: self.tap,
+            bottle_tags,
+            options: self
+                .options
+                .into_iter()
+                .map(|option| {
+                    if option.description.is_empty() {
+                        option.option
+                    } else {
+                        format!("{}: {}", option.option, option.description)
+                    }
+                })
+                .collect(),
+            conflicts_with: self.conflicts_with,
+            runtime_deps: self.dependencies,
+            build_deps: self.build_dependencies,
+            optional_deps: self.optional_dependencies,
+            recommended_deps: self.recommended_dependencies,
+            requirements: self
+                .requirements
+                .into_iter()
+                .map(|requirement| {
+                    let mut text = requirement.name;
+                    if let Some(version) = requirement.version {
+                        text.push_str(&format!(" {version}"));
+                    }
+                    if !requirement.contexts.is_empty() {
+                        text.push_str(&format!(" ({})", requirement.contexts.join(", ")));
+                    }
+                    text
+                })
+                .collect(),
+            aliases: self.aliases,
+            oldnames,
+            installed_at,
+            poured_from_bottle,
+        }
+    }
+}
+
+/// Keeps the bottle tags that install on `os`/`arch`. macOS tags name the OS
+/// release, so any release for the right architecture counts.
+fn platform_bottle_tags<'a>(
+    tags: impl Iterator<Item = &'a str>,
+    os: &str,
+    arch: &str,
+) -> Vec<String> {
+    let arm = arch == "aarch64";
+    tags.filter(|tag| {
+        if *tag == "all" {
+            return true;
+        }
+        match os {
+            "linux" => *tag == if arm { "arm64_linux" } else { "x86_64_linux" },
+            "macos" => !tag.ends_with("_linux") && tag.starts_with("arm64_") == arm,
+            _ => false,
+        }
+    })
+    .map(str::to_string)
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{RawMetadata, platform_bottle_tags};
+
+    #[test]
+    fn parses_metadata_and_filters_bottles_by_platform() {
+        let raw: RawMetadata = serde_json::from_str(
+            r#"{
+                "license": "MIT",
+                "tap": "homebrew/core",
+                "bottle": {"stable": {"files": {"arm64_sonoma": {}, "sonoma": {}, "x86_64_linux": {}}}},
+                "options": [{"option": "--HEAD", "description": "Install HEAD version"}],
+                "conflicts_with": ["jq-legacy"],
+                "dependencies": ["oniguruma"],
+                "build_dependencies": ["autoconf"],
+                "requirements": [{"name": "xcode", "version": "9.3", "contexts": ["build"]}],
+                "aliases": ["jq@1"],
+                "oldname": "jq-old"
+            }"#,
+        )
+        .expect("metadata");
+        let metadata = raw.into_metadata(Some(1_700_000_000), Some(true));
+        assert_eq!(metadata.license.as_deref(), Some("MIT"));
+        assert_eq!(metadata.options, ["--HEAD: Install HEAD version"]);
+        assert_eq!(metadata.build_deps, ["autoconf"]);
+        assert_eq!(metadata.requirements, ["xcode 9.3 (build)"]);
+        assert_eq!(metadata.oldnames, ["jq-old"]);
+        assert!(metadata.bottle_tags.is_some());
+
+        let tags = ["all", "arm64_sonoma", "sonoma", "x86_64_linux"];
+        assert_eq!(
+            platform_bottle_tags(tags.into_iter(), "macos", "aarch64"),
+            ["all", "arm64_sonoma"]
+        );
+        assert_eq!(
+            platform_bottle_tags(tags.into_iter(), "linux", "x86_64"),
+            ["all", "x86_64_linux"]
+        );
+        assert!(platform_bottle_tags(["sonoma"].into_iter(), "linux", "aarch64").is_empty());
+    }
+}
@@ -1,16 +1,44 @@
+mod cask;
 mod casks;
+mod caveats;
 mod commands;
+mod deprecation;
 mod details;
+mod doctor;
+mod kegs;
 mod leaves;
+mod link;
+mod metadata;
+mod outdated;
+mod paths;
 mod process;
+mod service_logs;
 mod services;
 mod size;
 mod status;
+mod update;
 
+pub use cask::{ArtifactKind, CaskInfo, StanzaItem};
 pub use casks::{CasksMessage, fetch_casks};
-pub use commands::{CommandKind, CommandMessage, run_brew_command, run_command};
+pub use caveats::{extract_caveats, is_shell_snippet};
+pub use commands::{
+    CommandEvent, CommandKind, CommandMessage, run_brew_command, run_command, run_command_streaming,
+};
+pub use deprecation::{
+    DeprecatedMessage, DeprecatedPackage, Deprecation, DeprecationState, fetch_deprecated,
+};
 pub use details::{Details, DetailsLoad, DetailsMessage, fetch_details_basic, fetch_details_full};
+pub use doctor::{
+    DoctorIssue, DoctorReport, IssueSeverity, brew_command_args, copy_only_reason, fetch_doctor,
+};
+pub use kegs::{REMOVE_KEG_SCRIPT, SWITCH_KEG_SCRIPT, is_keg_version, keg_script_args};
 pub use leaves::{LeavesMessage, fetch_leaves};
-pub use services::ServiceEntry;
+pub use link::{LinkPreview, parse_link_preview};
+pub use metadata::FormulaMetadata;
+pub use outdated::{OutdatedPackage, fetch_outdated};
+pub use paths::PackagePaths;
+pub use service_logs::{LogSnapshot, ServiceLogsMessage, fetch_service_logs, read_log_from};
+pub use services::{ServiceEntry, ServiceRegistration, fetch_services, stale_service_files};
 pub use size::{SizeEntry, SizesMessage, fetch_sizes};
 pub use status::{StatusMessage, StatusSnapshot, fetch_status};
+pub use update::{UpdateSummary, parse_update_summary};
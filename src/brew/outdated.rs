@@ -0,0 +1,75 @@
+use serde::{Deserialize, Serialize};
+
+use super::process::{ensure_success, run_brew};
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct OutdatedPackage {
+    pub name: String,
+    pub cask: bool,
+    pub installed_versions: Vec<String>,
+    pub current_version: String,
+    pub pinned: bool,
+}
+
+#[derive(Deserialize)]
+struct OutdatedJson {
+    #[serde(default)]
+    formulae: Vec<OutdatedEntryJson>,
+    #[serde(default)]
+    casks: Vec<OutdatedEntryJson>,
+}
+
+#[derive(Deserialize)]
+struct OutdatedEntryJson {
+    name: String,
+    #[serde(default)]
+    installed_versions: Vec<String>,
+    current_version: String,
+    #[serde(default)]
+    pinned: bool,
+}
+
+/// Every outdated formula and cask, not just leaves.
+pub async fn fetch_outdated() -> anyhow::Result<Vec<OutdatedPackage>> {
+    let output = run_brew(&["outdated", "--json=v2"]).await?;
+    // `brew outdated` exits 1 whenever something is outdated, so only empty output is a failure.
+    if output.stdout.is_empty() {
+        ensure_success(&output, "brew outdated failed")?;
+    }
+    parse_outdated_json(&String::from_utf8_lossy(&output.stdout))
+}
+
+fn parse_outdated_json(stdout: &str) -> anyhow::Result<Vec<OutdatedPackage>> {
+    let parsed: OutdatedJson = serde_json::from_str(stdout)?;
+    let formulae = parsed.formulae.into_iter().map(|entry| (entry, false));
+    let casks = parsed.casks.into_iter().map(|entry| (entry, true));
+    Ok(formulae
+        .chain(casks)
+        .map(|(entry, cask)| OutdatedPackage {
+            name: entry.name,
+            cask,
+            installed_versions: entry.installed_versions,
+            current_version: entry.current_version,
+            pinned: entry.pinned,
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_outdated_json;
+
+    #[test]
+    fn parses_formulae_and_casks() {
+        let stdout = r#"{
+            "formulae": [{"name": "jq", "installed_versions": ["1.6"], "current_version": "1.7.1", "pinned": true, "pinned_version": "1.6"}],
+            "casks": [{"name": "firefox", "installed_versions": ["120.0"], "current_version": "121.0"}]
+        }"#;
+        let packages = parse_outdated_json(stdout).unwrap();
+        assert_eq!(packages.len(), 2);
+        assert!(!packages[0].cask && packages[0].pinned);
+        assert_eq!(packages[1].name, "firefox");
+        assert!(packages[1].cask);
+        assert_eq!(packages[1].installed_versions, vec!["120.0".to_string()]);
+    }
+}
@@ -0,0 +1,84 @@
+use std::sync::OnceLock;
+
+use super::process::run_brew;
+
+static BREW_PREFIX: OnceLock<String> = OnceLock::new();
+static BREW_CELLAR: OnceLock<String> = OnceLock::new();
+static BREW_CASKROOM: OnceLock<String> = OnceLock::new();
+
+/// Where an installed formula or cask lives on disk.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum PackagePaths {
+    /// `rack` holds one keg per installed version; `opt` links the current one.
+    Formula {
+        rack: String,
+        opt: String,
+    },
+    Cask {
+        caskroom: String,
+    },
+}
+
+impl PackagePaths {
+    pub async fn formula(name: &str) -> Self {
+        let cellar = brew_dir(&BREW_CELLAR, "--cellar", "Cellar").await;
+        let prefix = brew_prefix().await;
+        Self::Formula {
+            rack: format!("{cellar}/{name}"),
+            opt: format!("{prefix}/opt/{name}"),
+        }
+    }
+
+    pub async fn cask(token: &str) -> Self {
+        let caskroom = brew_dir(&BREW_CASKROOM, "--caskroom", "Caskroom").await;
+        Self::Cask {
+            caskroom: format!("{caskroom}/{token}"),
+        }
+    }
+
+    /// The version `opt` currently points at; keg-only formulae have one even
+    /// though nothing is linked.
+    pub fn opt_version(&self) -> Option<String> {
+        let Self::Formula { opt, .. } = self else {
+            return None;
+        };
+        let target = std::fs::read_link(opt).ok()?;
+        Some(target.file_name()?.to_string_lossy().into_owned())
+    }
+
+    /// The keg directory for one installed version of a formula.
+    pub fn keg(&self, version: &str) -> Option<String> {
+        match self {
+            Self::Formula { rack, .. } => Some(format!("{rack}/{version}")),
+            Self::Cask { .. } => None,
+        }
+    }
+}
+
+/// `brew --prefix`, asked once per session.
+pub(super) async fn brew_prefix() -> String {
+    if let Some(prefix) = BREW_PREFIX.get() {
+        return prefix.clone();
+    }
+    let prefix = match run_brew(&["--prefix"]).await {
+        Ok(output) if output.status.success() => {
+            String::from_utf8_lossy(&output.stdout).trim().to_string()
+        }
+        _ => return "/opt/homebrew".to_string(),
+    };
+    BREW_PREFIX.get_or_init(|| prefix).clone()
+}
+
+/// A prefix directory brew can relocate, falling back to its default under the prefix.
+async fn brew_dir(cell: &OnceLock<String>, flag: &str, default: &str) -> String {
+    if let Some(dir) = cell.get() {
+        return dir.clone();
+    }
+    let dir = match run_brew(&[flag]).await {
+        Ok(output) if output.status.success() => {
+            String::from_utf8_lossy(&output.stdout).trim().to_string()
+        }
+        _ => return format!("{}/{default}", brew_prefix().await),
+    };
+    cell.get_or_init(|| dir).clone()
+}
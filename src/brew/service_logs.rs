@@ -0,0 +1,284 @@
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+
+use super::paths::brew_prefix;
+use super::services::ServiceEntry;
+
+/// How much of a log is read at once: when the viewer opens, and after a
+/// rotation or a burst of output.
+const INITIAL_TAIL_BYTES: u64 = 256 * 1024;
+
+/// A log file a service writes, and whether it captures stderr.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ServiceLog {
+    pub path: PathBuf,
+    pub stderr: bool,
+}
+
+/// The end of one log file, read when the viewer opens.
+#[derive(Clone, Debug)]
+pub struct LogSnapshot {
+    pub log: ServiceLog,
+    /// `None` when the file does not exist yet.
+    pub text: Option<String>,
+    /// Where the next read continues from.
+    pub offset: u64,
+}
+
+pub struct ServiceLogsMessage {
+    pub service: String,
+    pub result: anyhow::Result<Vec<LogSnapshot>>,
+}
+
+/// Resolves the service's logs from its launchd plist or systemd unit, falling
+/// back to `HOMEBREW_PREFIX/var/log`, and reads the end of each.
+pub async fn fetch_service_logs(service: &ServiceEntry) -> anyhow::Result<Vec<LogSnapshot>> {
+    let prefix = PathBuf::from(brew_prefix().await);
+    let service = service.clone();
+    // Service definitions and logs are read with blocking file I/O.
+    tokio::task::spawn_blocking(move || {
+        let logs = resolve_service_logs(&service, &prefix);
+        if logs.is_empty() {
+            anyhow::bail!(
+                "No log files found for {} in its service definition or {}",
+                service.name,
+                prefix.join("var/log").display()
+            );
+        }
+        Ok(logs
+            .into_iter()
+            .map(|log| {
+                let (text, offset) = match read_log_from(&log.path, 0) {
+                    Ok((text, offset)) => (Some(text), offset),
+                    Err(_) => (None, 0),
+                };
+                LogSnapshot { log, text, offset }
+            })
+            .collect())
+    })
+    .await?
+}
+
+fn resolve_service_logs(service: &ServiceEntry, prefix: &Path) -> Vec<ServiceLog> {
+    let name = &service.name;
+    let home = PathBuf::from(std::env::var("HOME").unwrap_or_default());
+    let candidates = if cfg!(target_os = "macos") {
+        [
+            home.join(format!("Library/LaunchAgents/homebrew.mxcl.{name}.plist")),
+            prefix.join(format!("opt/{name}/homebrew.mxcl.{name}.plist")),
+        ]
+    } else {
+        [
+            home.join(format!(".config/systemd/user/homebrew.{name}.service")),
+            prefix.join(format!("opt/{name}/homebrew.{name}.service")),
+        ]
+    };
+    let definition = service
+        .file
+        .as_deref()
+        .map(PathBuf::from)
+        .into_iter()
+        .chain(candidates)
+        .find_map(|path| std::fs::read_to_string(path).ok());
+
+    let mut logs = definition
+        .map(|text| {
+            if text.trim_start().starts_with('<') {
+                parse_plist_logs(&text)
+            } else {
+                parse_unit_logs(&text)
+            }
+        })
+        .unwrap_or_default();
+    if logs.is_empty() {
+        logs = var_log_files(&prefix.join("var/log"), name);
+    }
+    logs
+}
+
+/// `StandardOutPath` / `StandardErrorPath` from a launchd plist.
+fn parse_plist_logs(text: &str) -> Vec<ServiceLog> {
+    let mut logs = Vec::new();
+    for (key, stderr) in [("StandardOutPath", false), ("StandardErrorPath", true)] {
+        let Some(rest) = text
+            .split_once(&format!("<key>{key}</key>"))
+            .map(|(_, rest)| rest)
+        else {
+            continue;
+        };
+        let value = rest
+            .split_once("<string>")
+            .and_then(|(_, value)| value.split_once("</string>"))
+            .map(|(value, _)| value.trim());
+        if let Some(value) = value.filter(|value| !value.is_empty()) {
+            push_log(&mut logs, PathBuf::from(value), stderr);
+        }
+    }
+    logs
+}
+
+/// `StandardOutput=append:/path` style targets from a systemd unit.
+fn parse_unit_logs(text: &str) -> Vec<ServiceLog> {
+    let mut logs = Vec::new();
+    for line in text.lines().map(str::trim) {
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let stderr = match key.trim() {
+            "StandardOutput" => false,
+            "StandardError" => true,
+            _ => continue,
+        };
+        let path = ["append:", "file:", "truncate:"]
+            .iter()
+            .find_map(|mode| value.trim().strip_prefix(mode));
+        if let Some(path) = path {
+            push_log(&mut logs, PathBuf::from(path), stderr);
+        }
+    }
+    logs
+}
+
+/// Files in `var/log` named after the service, e.g. `redis.log` or `nginx.error.log`.
+fn var_log_files(dir: &Path, name: &str) -> Vec<ServiceLog> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut logs = Vec::new();
+    for entry in entries.flatten() {
+        let file_name = entry.file_name().to_string_lossy().to_string();
+        let matches_name = file_name
+            .strip_prefix(name)
+            .is_some_and(|rest| rest.starts_with(['.', '-', '_']));
+        if matches_name && entry.file_type().is_ok_and(|kind| kind.is_file()) {
+            let stderr = file_name.contains("err");
+            push_log(&mut logs, entry.path(), stderr);
+        }
+    }
+    logs.sort_by(|left, right| left.path.cmp(&right.path));
+    logs
+}
+
+/// Stdout and stderr often share one file; keep it once, as stdout.
+fn push_log(logs: &mut Vec<ServiceLog>, path: PathBuf, stderr: bool) {
+    if !logs.iter().any(|log| log.path == path) {
+        logs.push(ServiceLog { path, stderr });
+    }
+}
+
+/// Complete lines appended since `offset`, and the offset after them. A file
+/// that shrank was rotated or truncated, so it is read again from the start.
+/// Only the last `INITIAL_TAIL_BYTES` are read; anything older is skipped.
+/// Output without newlines is returned once it fills a whole read.
+pub fn read_log_from(path: &Path, offset: u64) -> std::io::Result<(String, u64)> {
+    let mut file = std::fs::File::open(path)?;
+    let len = file.metadata()?.len();
+    let requested = if len < offset { 0 } else { offset };
+    let start = requested.max(len.saturating_sub(INITIAL_TAIL_BYTES));
+    if len == start {
+        return Ok((String::new(), start));
+    }
+    file.seek(SeekFrom::Start(start))?;
+    let mut bytes = Vec::new();
+    file.take(len - start).read_to_end(&mut bytes)?;
+    // A line still being written is picked up on a later read, unless a whole
+    // read went by without a newline; then it is passed on as it is.
+    let complete = match bytes.iter().rposition(|&byte| byte == b'\n') {
+        Some(end) => end + 1,
+        None if bytes.len() as u64 >= INITIAL_TAIL_BYTES => bytes.len(),
+        None => 0,
+    };
+    bytes.truncate(complete);
+    // Drop the partial first line when skipping ahead mid-file.
+    if start > requested {
+        let first_line = bytes
+            .iter()
+            .position(|&byte| byte == b'\n')
+            .map_or(0, |end| end + 1);
+        bytes.drain(..first_line);
+    }
+    Ok((
+        String::from_utf8_lossy(&bytes).into_owned(),
+        start + complete as u64,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::{INITIAL_TAIL_BYTES, parse_plist_logs, parse_unit_logs, read_log_from};
+
+    #[test]
+    fn finds_log_paths_and_reads_appended_text() {
+        let plist = r#"<?xml version="1.0" encoding="UTF-8"?>
+<plist version="1.0"><dict>
+    <key>Label</key><string>homebrew.mxcl.redis</string>
+    <key>StandardErrorPath</key>
+    <string>/opt/homebrew/var/log/redis.log</string>
+    <key>StandardOutPath</key>
+    <string>/opt/homebrew/var/log/redis.log</string>
+</dict></plist>"#;
+        let logs = parse_plist_logs(plist);
+        assert_eq!(logs.len(), 1);
+        assert_eq!(
+            logs[0].path,
+            PathBuf::from("/opt/homebrew/var/log/redis.log")
+        );
+        assert!(!logs[0].stderr);
+
+        let unit = "[Service]\nExecStart=/x/bin/redis-server\nStandardOutput=append:/x/var/log/redis.log\nStandardError=append:/x/var/log/redis.err.log\n";
+        let logs = parse_unit_logs(unit);
+        assert_eq!(logs.len(), 2);
+        assert!(logs[1].stderr);
+
+        let path = std::env::temp_dir().join(format!("brewery-log-{}", std::process::id()));
+        std::fs::write(&path, "one\n").expect("write log");
+        let (text, offset) = read_log_from(&path, 0).expect("read");
+        assert_eq!((text.as_str(), offset), ("one\n", 4));
+        std::fs::write(&path, "one\ntwo\nthr").expect("append log");
+        assert_eq!(read_log_from(&path, offset).expect("tail").0, "two\n");
+        std::fs::write(&path, "3\n").expect("truncate log");
+        assert_eq!(read_log_from(&path, 8).expect("rotated").0, "3\n");
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn clamps_reads_to_the_tail() {
+        let path = std::env::temp_dir().join(format!("brewery-big-log-{}", std::process::id()));
+        let line = format!("{}\n", "x".repeat(99));
+        let big = line.repeat(INITIAL_TAIL_BYTES as usize / 100 * 3);
+        std::fs::write(&path, &big).expect("write log");
+
+        let (text, offset) = read_log_from(&path, 0).expect("read");
+        assert_eq!(offset, big.len() as u64);
+        assert!(text.len() <= INITIAL_TAIL_BYTES as usize);
+        assert!(text.lines().all(|read| read.len() == 99));
+
+        // A rotated file is read again from the start, still clamped.
+        std::fs::write(&path, &big[..big.len() / 2]).expect("rotate log");
+        let (text, _) = read_log_from(&path, offset).expect("rotated");
+        assert!(text.len() <= INITIAL_TAIL_BYTES as usize);
+        assert!(text.starts_with('x'));
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn passes_on_output_without_newlines() {
+        let path =
+            std::env::temp_dir().join(format!("brewery-no-newline-log-{}", std::process::id()));
+        let long = "x".repeat(INITIAL_TAIL_BYTES as usize + 10);
+        std::fs::write(&path, &long).expect("write log");
+
+        let (text, offset) = read_log_from(&path, 0).expect("read");
+        assert_eq!(text.len(), INITIAL_TAIL_BYTES as usize);
+        assert_eq!(offset, long.len() as u64);
+
+        std::fs::write(&path, format!("{long}done\nnext")).expect("append log");
+        assert_eq!(
+            read_log_from(&path, offset).expect("tail"),
+            ("done\n".to_string(), offset + 5)
+        );
+        std::fs::remove_file(&path).ok();
+    }
+}
@@ -1,5 +1,7 @@
+use std::path::{Path, PathBuf};
+
 use super::commands::run_brew_command;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 enum ServiceState {
@@ -8,7 +10,27 @@ enum ServiceState {
     Error,
 }
 
-#[derive(Clone, Debug, Default)]
+/// Whether launchd/systemd will start the service again at login (or boot).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ServiceRegistration {
+    /// The service file is installed where launchd/systemd picks it up.
+    Login,
+    /// Running from the formula's own service file, as `brew services run` does.
+    Session,
+    None,
+}
+
+impl ServiceRegistration {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Login => "at login",
+            Self::Session => "this session only",
+            Self::None => "not registered",
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct ServiceEntry {
     pub name: String,
     pub status: String,
@@ -48,13 +70,87 @@ impl ServiceEntry {
         matches!(self.state(), ServiceState::Error)
     }
 
+    pub fn registration(&self) -> ServiceRegistration {
+        // Unregistered services report the file inside their keg's `opt` directory.
+        let registered = self.file.as_deref().is_some_and(|file| {
+            ["/LaunchAgents/", "/LaunchDaemons/", "/systemd/"]
+                .iter()
+                .any(|dir| file.contains(dir))
+        });
+        if registered {
+            ServiceRegistration::Login
+        } else if self.is_running() {
+            ServiceRegistration::Session
+        } else {
+            ServiceRegistration::None
+        }
+    }
+
     pub fn auto_start_enabled(&self) -> bool {
-        self.file
-            .as_deref()
-            .is_some_and(|value| !value.trim().is_empty())
+        self.registration() == ServiceRegistration::Login
+    }
+
+    /// Commands that show why the service failed on this platform, most useful first.
+    pub fn troubleshooting_commands(&self) -> Vec<String> {
+        let name = &self.name;
+        if cfg!(target_os = "macos") {
+            vec![
+                format!("brew services info {name}"),
+                format!("launchctl print gui/$UID/homebrew.mxcl.{name}"),
+                format!(
+                    "log show --style compact --predicate 'process CONTAINS \"{name}\"' --last 10m"
+                ),
+            ]
+        } else {
+            vec![
+                format!("systemctl --user status {name}.service"),
+                format!("journalctl --user-unit {name}.service -n 50 --no-pager"),
+            ]
+        }
     }
 }
 
+/// Homebrew service files in the user's launchd/systemd directory whose formula
+/// no longer lists a service; these are what `brew services cleanup` removes.
+pub fn stale_service_files(services: &[ServiceEntry]) -> Vec<PathBuf> {
+    let home = PathBuf::from(std::env::var("HOME").unwrap_or_default());
+    let (dir, prefix, suffix) = if cfg!(target_os = "macos") {
+        (
+            home.join("Library/LaunchAgents"),
+            "homebrew.mxcl.",
+            ".plist",
+        )
+    } else {
+        (home.join(".config/systemd/user"), "homebrew.", ".service")
+    };
+    stale_files_in(&dir, prefix, suffix, services)
+}
+
+/// Files named `<prefix><service><suffix>` in `dir` for services not in the list.
+fn stale_files_in(
+    dir: &Path,
+    prefix: &str,
+    suffix: &str,
+    services: &[ServiceEntry],
+) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut stale: Vec<PathBuf> = entries
+        .flatten()
+        .filter(|entry| {
+            let file_name = entry.file_name().to_string_lossy().to_string();
+            file_name
+                .strip_prefix(prefix)
+                .and_then(|rest| rest.strip_suffix(suffix))
+                .is_some_and(|name| !services.iter().any(|service| service.name == name))
+        })
+        .map(|entry| entry.path())
+        .collect();
+    stale.sort();
+    stale
+}
+
 #[derive(Deserialize)]
 struct ServiceEntryJson {
     name: String,
@@ -142,7 +238,20 @@ fn normalize_optional_field(value: &str) -> Option<String> {
 
 #[cfg(test)]
 mod tests {
-    use super::{ServiceEntry, ServiceState, parse_services_json, parse_services_list};
+    use super::{
+        ServiceEntry, ServiceRegistration, ServiceState, parse_services_json, parse_services_list,
+        stale_files_in,
+    };
+
+    fn service(name: &str, status: &str, file: Option<&str>) -> ServiceEntry {
+        ServiceEntry {
+            name: name.to_string(),
+            status: status.to_string(),
+            user: None,
+            file: file.map(str::to_string),
+            exit_code: None,
+        }
+    }
 
     #[test]
     fn parses_services_list_rows() {
@@ -189,6 +298,53 @@ mod tests {
         );
     }
 
+    #[test]
+    fn registration_follows_service_file_location() {
+        let agent = "/Users/me/Library/LaunchAgents/homebrew.mxcl.redis.plist";
+        assert_eq!(
+            service("redis", "started", Some(agent)).registration(),
+            ServiceRegistration::Login
+        );
+        let unit = "/home/me/.config/systemd/user/homebrew.redis.service";
+        assert_eq!(
+            service("redis", "none", Some(unit)).registration(),
+            ServiceRegistration::Login
+        );
+
+        // Linux kegs keep the unit next to the formula until it is registered.
+        let keg = "/home/linuxbrew/.linuxbrew/opt/redis/homebrew.redis.service";
+        assert_eq!(
+            service("redis", "none", Some(keg)).registration(),
+            ServiceRegistration::None
+        );
+        let session = service("redis", "started", Some(keg));
+        assert_eq!(session.registration(), ServiceRegistration::Session);
+        assert!(!session.auto_start_enabled());
+    }
+
+    #[test]
+    fn finds_stale_files_for_unlisted_services() {
+        let dir = std::env::temp_dir().join(format!("brewery-services-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        for name in [
+            "homebrew.redis.service",
+            "homebrew.oldsvc.service",
+            "homebrew.oldsvc.timer",
+            "other.oldsvc.service",
+        ] {
+            std::fs::write(dir.join(name), "").unwrap();
+        }
+
+        let stale = stale_files_in(
+            &dir,
+            "homebrew.",
+            ".service",
+            &[service("redis", "started", None)],
+        );
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(stale, vec![dir.join("homebrew.oldsvc.service")]);
+    }
+
     #[test]
     fn marks_non_zero_exit_as_error_state() {
         let entry = ServiceEntry {
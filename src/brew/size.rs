@@ -1,8 +1,11 @@
 use std::path::PathBuf;
 
+use serde::{Deserialize, Serialize};
+
 use super::process::{ensure_success, run_brew};
+use crate::storage::SizeHistory;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct SizeEntry {
     pub name: String,
     pub size_kb: u64,
@@ -10,6 +13,8 @@ pub struct SizeEntry {
 
 pub struct SizesMessage {
     pub result: anyhow::Result<Vec<SizeEntry>>,
+    /// Scan history including this result, when it could be recorded.
+    pub history: Option<SizeHistory>,
 }
 
 pub async fn fetch_sizes() -> anyhow::Result<Vec<SizeEntry>> {
@@ -23,16 +28,33 @@ pub async fn fetch_sizes() -> anyhow::Result<Vec<SizeEntry>> {
         }
     }
 
-    if entries.is_empty() {
-        return Ok(Vec::new());
+    let mut sizes = du_sizes(&entries).await?;
+    sizes.sort_by_key(|entry| std::cmp::Reverse(entry.size_kb));
+    Ok(sizes)
+}
+
+/// Disk usage of each installed version of `formula`, named by version.
+pub async fn fetch_keg_sizes(formula: &str) -> anyhow::Result<Vec<SizeEntry>> {
+    let output = run_brew(&["--cellar", formula]).await?;
+    ensure_success(&output, "brew --cellar failed")?;
+    let rack = PathBuf::from(String::from_utf8_lossy(&output.stdout).trim());
+
+    let mut kegs = Vec::new();
+    for dir in std::fs::read_dir(&rack)? {
+        let dir = dir?;
+        if dir.file_type()?.is_dir() {
+            kegs.push(dir.path());
+        }
     }
+    du_sizes(&kegs).await
+}
 
-    let output = tokio::process::Command::new("du")
-        .arg("-sk")
-        .args(&entries)
-        .output()
-        .await?;
+async fn du_sizes(paths: &[PathBuf]) -> anyhow::Result<Vec<SizeEntry>> {
+    if paths.is_empty() {
+        return Ok(Vec::new());
+    }
 
+    let output = run_du(paths).await?;
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
         let message = if stderr.is_empty() {
@@ -44,23 +66,45 @@ pub async fn fetch_sizes() -> anyhow::Result<Vec<SizeEntry>> {
     }
 
     let stdout = String::from_utf8_lossy(&output.stdout);
-    let mut sizes: Vec<SizeEntry> = stdout.lines().filter_map(parse_du_line).collect();
+    Ok(stdout.lines().filter_map(parse_du_line).collect())
+}
 
-    sizes.sort_by(|a, b| b.size_kb.cmp(&a.size_kb));
-    Ok(sizes)
+/// Disk usage by full path. Unreadable paths are skipped rather than failing
+/// the rest, since app data often has a few protected files.
+pub(super) async fn du_path_sizes(paths: &[PathBuf]) -> Vec<(PathBuf, u64)> {
+    if paths.is_empty() {
+        return Vec::new();
+    }
+    let Ok(output) = run_du(paths).await else {
+        return Vec::new();
+    };
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(split_du_line)
+        .map(|(size_kb, path)| (PathBuf::from(path), size_kb))
+        .collect()
+}
+
+async fn run_du(paths: &[PathBuf]) -> std::io::Result<std::process::Output> {
+    tokio::process::Command::new("du")
+        .arg("-sk")
+        .args(paths)
+        .output()
+        .await
+}
+
+/// `du` separates the size from the path with a tab; paths may contain spaces.
+fn split_du_line(line: &str) -> Option<(u64, &str)> {
+    let (size, path) = line.split_once('\t')?;
+    Some((size.trim().parse().ok()?, path))
 }
 
 fn parse_du_line(line: &str) -> Option<SizeEntry> {
-    let mut parts = line.split_whitespace();
-    let size = parts.next()?.parse::<u64>().ok()?;
-    let path = parts.next()?;
+    let (size_kb, path) = split_du_line(line)?;
     let name = PathBuf::from(path)
         .file_name()
         .map(|os| os.to_string_lossy().to_string())?;
-    Some(SizeEntry {
-        name,
-        size_kb: size,
-    })
+    Some(SizeEntry { name, size_kb })
 }
 
 async fn fetch_cellar_path() -> anyhow::Result<PathBuf> {
@@ -71,3 +115,17 @@ async fn fetch_cellar_path() -> anyhow::Result<PathBuf> {
     let path = stdout.trim();
     Ok(PathBuf::from(path))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::parse_du_line;
+
+    #[test]
+    fn parses_du_lines_with_spaces_in_paths() {
+        let entry =
+            parse_du_line("2048\t/Users/me/Library/Application Support/Firefox").expect("du line");
+        assert_eq!(entry.name, "Firefox");
+        assert_eq!(entry.size_kb, 2048);
+        assert!(parse_du_line("not a du line").is_none());
+    }
+}
@@ -1,3 +1,4 @@
+use super::doctor::{DoctorIssue, fetch_doctor};
 use super::services::{ServiceEntry, fetch_services};
 use crate::brew::{run_brew_command, run_command};
 use std::collections::HashSet;
@@ -15,10 +16,10 @@ struct LatestBreweryCacheEntry {
 
 static LATEST_BREWERY_CACHE: OnceLock<Mutex<Option<LatestBreweryCacheEntry>>> = OnceLock::new();
 
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug, Default, serde::Serialize, serde::Deserialize)]
 pub struct StatusSnapshot {
     pub doctor_ok: Option<bool>,
-    pub doctor_issues: Vec<String>,
+    pub doctor_issues: Vec<DoctorIssue>,
     pub outdated_count: Option<usize>,
     pub outdated_packages: Vec<String>,
     pub brew_version: Option<String>,
@@ -51,7 +52,7 @@ pub async fn fetch_status() -> anyhow::Result<StatusSnapshot> {
         run_brew_command(&["--version"]),
         run_brew_command(&["info"]),
         run_brew_command(&["leaves"]),
-        run_brew_command(&["doctor"]),
+        fetch_doctor(),
         run_brew_command(&["--repository"]),
         run_brew_command(&["--repository", "homebrew/core"]),
         fetch_services(),
@@ -83,22 +84,9 @@ pub async fn fetch_status() -> anyhow::Result<StatusSnapshot> {
     }
 
     // Process doctor result
-    if let Ok(result) = doctor_result {
-        status.doctor_ok = Some(result.success);
-        if !result.success {
-            // Parse warnings/errors from stderr or stdout
-            let output = if result.stderr.is_empty() {
-                &result.stdout
-            } else {
-                &result.stderr
-            };
-            status.doctor_issues = output
-                .lines()
-                .filter(|line| line.starts_with("Warning:") || line.starts_with("Error:"))
-                .take(5)
-                .map(|s| s.to_string())
-                .collect();
-        }
+    if let Ok(report) = doctor_result {
+        status.doctor_ok = Some(report.ok);
+        status.doctor_issues = report.issues;
     }
 
     // Process last brew update time from repository metadata
@@ -0,0 +1,127 @@
+/// What a `brew update` run brought in, parsed from its `==>` sections.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct UpdateSummary {
+    pub new_formulae: Vec<String>,
+    pub new_casks: Vec<String>,
+    pub updated_formulae: Vec<String>,
+    pub updated_casks: Vec<String>,
+    /// Installed formulae and casks brew now reports as outdated.
+    pub outdated: usize,
+}
+
+#[derive(Clone, Copy)]
+enum Section {
+    NewFormulae,
+    NewCasks,
+    UpdatedFormulae,
+    UpdatedCasks,
+    Outdated,
+}
+
+impl UpdateSummary {
+    pub fn is_empty(&self) -> bool {
+        self.new_formulae.is_empty()
+            && self.new_casks.is_empty()
+            && self.updated_formulae.is_empty()
+            && self.updated_casks.is_empty()
+            && self.outdated == 0
+    }
+
+    pub fn headline(&self) -> String {
+        if self.is_empty() {
+            return "Already up-to-date".to_string();
+        }
+
+        let parts = [
+            (self.new_formulae.len(), "new formula", "new formulae"),
+            (self.new_casks.len(), "new cask", "new casks"),
+            (
+                self.updated_formulae.len(),
+                "updated formula",
+                "updated formulae",
+            ),
+            (self.updated_casks.len(), "updated cask", "updated casks"),
+            (self.outdated, "outdated", "outdated"),
+        ];
+        parts
+            .into_iter()
+            .filter(|(count, _, _)| *count > 0)
+            .map(|(count, one, many)| format!("{count} {}", if count == 1 { one } else { many }))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+pub fn parse_update_summary(output: &str) -> UpdateSummary {
+    let mut summary = UpdateSummary::default();
+    let mut section = None;
+
+    for line in output.lines().map(str::trim) {
+        if let Some(heading) = line.strip_prefix("==> ") {
+            section = match heading {
+                "New Formulae" => Some(Section::NewFormulae),
+                "New Casks" => Some(Section::NewCasks),
+                "Updated Formulae" => Some(Section::UpdatedFormulae),
+                "Updated Casks" => Some(Section::UpdatedCasks),
+                "Outdated Formulae" | "Outdated Casks" => Some(Section::Outdated),
+                _ => None,
+            };
+            continue;
+        }
+        let Some(section) = section else {
+            continue;
+        };
+        if line.is_empty() {
+            continue;
+        }
+
+        // Newer brew prints `name: description`, older brew prints names in columns.
+        let names: Vec<String> = match line.split_once(": ") {
+            Some((name, _)) => vec![name.to_string()],
+            None => line.split_whitespace().map(str::to_string).collect(),
+        };
+        match section {
+            Section::NewFormulae => summary.new_formulae.extend(names),
+            Section::NewCasks => summary.new_casks.extend(names),
+            Section::UpdatedFormulae => summary.updated_formulae.extend(names),
+            Section::UpdatedCasks => summary.updated_casks.extend(names),
+            Section::Outdated => summary.outdated += names.len(),
+        }
+    }
+    summary
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_update_summary;
+
+    #[test]
+    fn counts_sections_in_both_output_styles() {
+        let output = "\
+==> Updated Homebrew from 4.4.0 to 4.4.1.
+Updated 2 taps (homebrew/core and homebrew/cask).
+==> New Formulae
+uv: Extremely fast Python package installer
+ruff: Extremely fast Python linter
+==> New Casks
+ghostty
+==> Updated Formulae
+jq           node         openssl@3
+==> Outdated Formulae
+node
+";
+        let summary = parse_update_summary(output);
+        assert_eq!(summary.new_formulae, vec!["uv", "ruff"]);
+        assert_eq!(summary.new_casks, vec!["ghostty"]);
+        assert_eq!(summary.updated_formulae, vec!["jq", "node", "openssl@3"]);
+        assert_eq!(summary.outdated, 1);
+        assert_eq!(
+            summary.headline(),
+            "2 new formulae, 1 new cask, 3 updated formulae, 1 outdated"
+        );
+        assert_eq!(
+            parse_update_summary("Already up-to-date.\n").headline(),
+            "Already up-to-date"
+        );
+    }
+}
@@ -0,0 +1,291 @@
+use std::path::PathBuf;
+
+use super::{Invocation, Subcommand};
+use crate::app::{App, FocusedPanel, IconMode, InputMode, PackageKind};
+use crate::config::Config;
+use crate::theme::ThemeMode;
+
+pub const HELP: &str = "\
+brewery - a terminal UI for Homebrew
+
+Usage:
+  brewery [OPTIONS]
+  brewery <COMMAND> [--json]
+  brewery report [--json] [--redact] [--output <path>]
+
+Commands:
+  status     Brew version, doctor, outdated leaves, services and last update
+  outdated   Every outdated formula and cask
+  sizes      Installed formulae by disk usage
+  services   Service states and exit codes
+  doctor     brew doctor warnings and errors
+  report     Health report in Markdown (or --json); --redact hides paths and
+             usernames, --output <path> writes to a file
+
+Options:
+  --theme <name>           Theme for this session (auto, light, dark, ...)
+  --ascii                  ASCII icons instead of Nerd Font glyphs
+  --no-mouse               Don't capture the mouse
+  --config <path>          Read settings from <path> instead of the default config file
+  --start-in <view>        Open the cask list (casks) or package search (search)
+  --filter <query>         Pre-fill the installed-list filter, or the search box with --start-in search
+  --focus <panel>          Focus leaves, sizes, status or details
+  --read-only              Disable install, uninstall, upgrade, service and cleanup actions
+  --no-background-refresh  Turn off periodic status checks
+  -h, --help               Print this help
+  -V, --version            Print the version
+
+Commands exit 0 when healthy, 1 when something needs attention, and 2 on errors.
+";
+
+/// What the command line asked for.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Launch {
+    Tui(LaunchOptions),
+    Headless(Invocation),
+    Help,
+    Version,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StartIn {
+    Casks,
+    Search,
+}
+
+/// Per-session overrides; they win over the config file and env vars and are never saved.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LaunchOptions {
+    pub theme: Option<ThemeMode>,
+    pub ascii: bool,
+    pub no_mouse: bool,
+    pub config: Option<PathBuf>,
+    pub start_in: Option<StartIn>,
+    pub filter: Option<String>,
+    pub focus: Option<FocusedPanel>,
+    pub read_only: bool,
+    pub no_background_refresh: bool,
+}
+
+/// Parses the arguments after the binary name.
+pub fn parse_args(args: &[String]) -> anyhow::Result<Launch> {
+    if args.iter().any(|arg| arg == "-h" || arg == "--help") {
+        return Ok(Launch::Help);
+    }
+    if args.iter().any(|arg| arg == "-V" || arg == "--version") {
+        return Ok(Launch::Version);
+    }
+    if let Some((first, rest)) = args.split_first()
+        && !first.starts_with('-')
+    {
+        return parse_subcommand(first, rest).map(Launch::Headless);
+    }
+
+    let mut options = LaunchOptions::default();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let (flag, inline) = match arg.split_once('=') {
+            Some((flag, value)) => (flag, Some(value.to_string())),
+            None => (arg.as_str(), None),
+        };
+        let mut value = || {
+            inline
+                .clone()
+                .or_else(|| args.next().cloned())
+                .ok_or_else(|| anyhow::anyhow!("{flag} needs a value (see --help)"))
+        };
+
+        match flag {
+            "--theme" => options.theme = Some(ThemeMode::from(value()?)),
+            "--config" => options.config = Some(PathBuf::from(value()?)),
+            "--start-in" => {
+                options.start_in = Some(match value()?.as_str() {
+                    "casks" => StartIn::Casks,
+                    "search" => StartIn::Search,
+                    other => anyhow::bail!("--start-in: expected casks or search, got '{other}'"),
+                });
+            }
+            "--filter" => options.filter = Some(value()?),
+            "--focus" => {
+                options.focus = Some(match value()?.as_str() {
+                    "leaves" => FocusedPanel::Leaves,
+                    "sizes" => FocusedPanel::Sizes,
+                    "status" => FocusedPanel::Status,
+                    "details" => FocusedPanel::Details,
+                    other => anyhow::bail!(
+                        "--focus: expected leaves, sizes, status or details, got '{other}'"
+                    ),
+                });
+            }
+            "--ascii" | "--no-mouse" | "--read-only" | "--no-background-refresh"
+                if inline.is_some() =>
+            {
+                anyhow::bail!("{flag} does not take a value");
+            }
+            "--ascii" => options.ascii = true,
+            "--no-mouse" => options.no_mouse = true,
+            "--read-only" => options.read_only = true,
+            "--no-background-refresh" => options.no_background_refresh = true,
+            _ => anyhow::bail!("unknown option '{arg}' (see --help)"),
+        }
+    }
+    Ok(Launch::Tui(options))
+}
+
+fn parse_subcommand(name: &str, rest: &[String]) -> anyhow::Result<Invocation> {
+    let command = Subcommand::from_name(name).ok_or_else(|| {
+        let names: Vec<&str> = Subcommand::ALL
+            .iter()
+            .map(|command| command.name())
+            .collect();
+        anyhow::anyhow!("unknown command '{name}' (expected {})", names.join(", "))
+    })?;
+
+    let mut invocation = Invocation {
+        command,
+        json: false,
+        redact: false,
+        output: None,
+    };
+    let is_report = command == Subcommand::Report;
+    let mut args = rest.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--json" => invocation.json = true,
+            "--redact" if is_report => invocation.redact = true,
+            "--output" | "-o" if is_report => {
+                let path = args
+                    .next()
+                    .ok_or_else(|| anyhow::anyhow!("{arg} needs a path"))?;
+                invocation.output = Some(PathBuf::from(path));
+            }
+            other => anyhow::bail!("unknown option '{other}' for 'brewery {}'", command.name()),
+        }
+    }
+    Ok(invocation)
+}
+
+impl LaunchOptions {
+    /// Overrides applied before the app is built; fails on an unknown theme.
+    pub fn apply_to_config(&self, config: &mut Config) -> anyhow::Result<()> {
+        if let Some(theme) = self.theme.as_ref() {
+            if *theme != ThemeMode::Auto {
+                theme
+                    .resolve()
+                    .map_err(|err| anyhow::anyhow!("--theme: {err}"))?;
+            }
+            config.theme = theme.clone();
+        }
+        if self.no_background_refresh {
+            config.refresh.background_status_secs = 0;
+            config.refresh.auto_update_secs = 0;
+        }
+        Ok(())
+    }
+
+    /// Overrides that must beat the `BREWERY_*` env vars, plus the starting view.
+    pub fn apply_to_app(&self, app: &mut App) {
+        if self.ascii {
+            app.icon_mode = IconMode::Ascii;
+            app.icons_ascii = true;
+        }
+        if self.no_mouse {
+            app.mouse_enabled = false;
+        }
+        app.read_only = self.read_only;
+
+        match self.start_in {
+            Some(StartIn::Casks) => app.active_package_kind = PackageKind::Cask,
+            Some(StartIn::Search) => app.input_mode = InputMode::PackageSearch,
+            None => {}
+        }
+        if let Some(filter) = self.filter.as_ref() {
+            if self.start_in == Some(StartIn::Search) {
+                app.package_query = filter.clone();
+            } else {
+                app.leaves_query = filter.clone();
+                app.update_all_installed_filters();
+            }
+        }
+        if let Some(panel) = self.focus {
+            app.focus_panel = panel;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::{Launch, LaunchOptions, StartIn, parse_args};
+    use crate::app::FocusedPanel;
+    use crate::cli::{Invocation, Subcommand};
+    use crate::theme::ThemeMode;
+
+    fn args(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    #[test]
+    fn parses_launch_flags_in_both_forms() {
+        let launch = parse_args(&args(&[
+            "--theme=dark",
+            "--start-in",
+            "casks",
+            "--filter",
+            "py",
+            "--focus=status",
+            "--read-only",
+        ]))
+        .unwrap();
+        assert_eq!(
+            launch,
+            Launch::Tui(LaunchOptions {
+                theme: Some(ThemeMode::Named("dark".to_string())),
+                start_in: Some(StartIn::Casks),
+                filter: Some("py".to_string()),
+                focus: Some(FocusedPanel::Status),
+                read_only: true,
+                ..LaunchOptions::default()
+            })
+        );
+        assert_eq!(
+            parse_args(&[]).unwrap(),
+            Launch::Tui(LaunchOptions::default())
+        );
+        assert_eq!(parse_args(&args(&["--ascii", "-h"])).unwrap(), Launch::Help);
+    }
+
+    #[test]
+    fn rejects_bad_flags_and_values() {
+        assert!(parse_args(&args(&["--focus", "sidebar"])).is_err());
+        assert!(parse_args(&args(&["--config"])).is_err());
+        assert!(parse_args(&args(&["--ascii=yes"])).is_err());
+        assert!(parse_args(&args(&["--nope"])).is_err());
+    }
+
+    #[test]
+    fn parses_subcommands_and_json_flag() {
+        assert_eq!(
+            parse_args(&args(&["outdated", "--json"])).unwrap(),
+            Launch::Headless(Invocation {
+                command: Subcommand::Outdated,
+                json: true,
+                redact: false,
+                output: None,
+            })
+        );
+        assert_eq!(
+            parse_args(&args(&["report", "--redact", "-o", "health.md"])).unwrap(),
+            Launch::Headless(Invocation {
+                command: Subcommand::Report,
+                json: false,
+                redact: true,
+                output: Some(PathBuf::from("health.md")),
+            })
+        );
+        assert!(parse_args(&args(&["status", "--redact"])).is_err());
+        assert!(parse_args(&args(&["upgrade"])).is_err());
+        assert!(parse_args(&args(&["sizes", "--all"])).is_err());
+    }
+}
@@ -0,0 +1,210 @@
+use std::fmt::Write;
+
+use serde::Serialize;
+
+use super::Outcome;
+use crate::brew::{
+    DoctorReport, OutdatedPackage, ServiceEntry, SizeEntry, StatusSnapshot, fetch_doctor,
+    fetch_outdated, fetch_services, fetch_sizes, fetch_status,
+};
+use crate::storage::record_size_snapshot;
+use crate::ui::util::{format_elapsed, format_size};
+
+/// Doctor issues, failed services, or outdated leaves.
+pub(super) async fn status(json: bool) -> anyhow::Result<Outcome> {
+    let status = fetch_status().await?;
+    emit(json, &status, render_status)?;
+    let attention = status.doctor_ok == Some(false)
+        || status.outdated_count.is_some_and(|count| count > 0)
+        || status.services.iter().any(ServiceEntry::has_failed);
+    Ok(outcome(attention))
+}
+
+/// Anything outdated, formula or cask.
+pub(super) async fn outdated(json: bool) -> anyhow::Result<Outcome> {
+    let packages = fetch_outdated().await?;
+    emit(json, packages.as_slice(), render_outdated)?;
+    Ok(outcome(!packages.is_empty()))
+}
+
+/// Always healthy; the scan is also added to the size history like a TUI scan.
+pub(super) async fn sizes(json: bool) -> anyhow::Result<Outcome> {
+    let sizes = fetch_sizes().await?;
+    let recorded = sizes.clone();
+    let _ = tokio::task::spawn_blocking(move || record_size_snapshot(&recorded)).await;
+    emit(json, sizes.as_slice(), render_sizes)?;
+    Ok(Outcome::Healthy)
+}
+
+/// Any service in an error state.
+pub(super) async fn services(json: bool) -> anyhow::Result<Outcome> {
+    let services = fetch_services().await?;
+    emit(json, services.as_slice(), render_services)?;
+    Ok(outcome(services.iter().any(ServiceEntry::has_failed)))
+}
+
+/// Any doctor warning or error.
+pub(super) async fn doctor(json: bool) -> anyhow::Result<Outcome> {
+    let report = fetch_doctor().await?;
+    emit(json, &report, render_doctor)?;
+    Ok(outcome(!report.ok))
+}
+
+fn outcome(attention: bool) -> Outcome {
+    if attention {
+        Outcome::Attention
+    } else {
+        Outcome::Healthy
+    }
+}
+
+fn emit<T: Serialize + ?Sized>(
+    json: bool,
+    value: &T,
+    render: impl FnOnce(&T) -> String,
+) -> anyhow::Result<()> {
+    if json {
+        println!("{}", serde_json::to_string_pretty(value)?);
+    } else {
+        print!("{}", render(value));
+    }
+    Ok(())
+}
+
+fn render_status(status: &StatusSnapshot) -> String {
+    let mut out = String::new();
+    let brew = status.brew_version.as_deref().unwrap_or("unknown");
+    let _ = writeln!(out, "brew:      {brew}");
+
+    let doctor = match status.doctor_ok {
+        Some(true) => "ok".to_string(),
+        Some(false) => format!("{} issue(s)", status.doctor_issues.len()),
+        None => "unknown".to_string(),
+    };
+    let _ = writeln!(out, "doctor:    {doctor}");
+
+    let outdated = match status.outdated_count {
+        Some(0) => "none".to_string(),
+        Some(count) => format!("{count} ({})", status.outdated_packages.join(", ")),
+        None => "unknown".to_string(),
+    };
+    let _ = writeln!(out, "outdated:  {outdated}");
+
+    let running = status.services.iter().filter(|s| s.is_running()).count();
+    let failed = status.services.iter().filter(|s| s.has_failed()).count();
+    let _ = writeln!(out, "services:  {running} running, {failed} failed");
+
+    let updated = match status.last_brew_update_secs_ago {
+        Some(secs) => format!("{} ago", format_elapsed(secs)),
+        None => "unknown".to_string(),
+    };
+    let _ = writeln!(out, "updated:   {updated}");
+    out
+}
+
+fn render_outdated(packages: &[OutdatedPackage]) -> String {
+    if packages.is_empty() {
+        return "Everything is up to date.\n".to_string();
+    }
+
+    let width = packages.iter().map(|p| p.name.len()).max().unwrap_or(0);
+    let mut out = String::new();
+    for package in packages {
+        let installed = package.installed_versions.join(", ");
+        let _ = write!(
+            out,
+            "{:width$}  {installed} -> {}",
+            package.name, package.current_version
+        );
+        if package.cask {
+            out.push_str(" (cask)");
+        }
+        if package.pinned {
+            out.push_str(" (pinned)");
+        }
+        out.push('\n');
+    }
+    out
+}
+
+fn render_sizes(sizes: &[SizeEntry]) -> String {
+    let mut out = String::new();
+    for entry in sizes {
+        let _ = writeln!(out, "{:>10}  {}", format_size(entry.size_kb), entry.name);
+    }
+    let total: u64 = sizes.iter().map(|entry| entry.size_kb).sum();
+    let _ = writeln!(out, "{:>10}  total", format_size(total));
+    out
+}
+
+fn render_services(services: &[ServiceEntry]) -> String {
+    if services.is_empty() {
+        return "No services.\n".to_string();
+    }
+
+    let width = services.iter().map(|s| s.name.len()).max().unwrap_or(0);
+    let mut out = String::new();
+    for service in services {
+        let _ = write!(out, "{:width$}  {:7}", service.name, service.state_label());
+        if let Some(user) = service.user.as_deref() {
+            let _ = write!(out, "  {user}");
+        }
+        if let Some(code) = service.exit_code.filter(|code| *code != 0) {
+            let _ = write!(out, "  (exit {code})");
+        }
+        out.push('\n');
+    }
+    out
+}
+
+fn render_doctor(report: &DoctorReport) -> String {
+    if report.ok {
+        return "Your system is ready to brew.\n".to_string();
+    }
+
+    let mut out = String::new();
+    for (index, issue) in report.issues.iter().enumerate() {
+        if index > 0 {
+            out.push('\n');
+        }
+        let _ = writeln!(out, "{}", issue.headline());
+        for line in &issue.body {
+            if line.is_empty() {
+                out.push('\n');
+            } else {
+                let _ = writeln!(out, "  {line}");
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::render_outdated;
+    use crate::brew::OutdatedPackage;
+
+    #[test]
+    fn aligns_outdated_rows_and_marks_casks() {
+        let packages = vec![
+            OutdatedPackage {
+                name: "jq".to_string(),
+                cask: false,
+                installed_versions: vec!["1.6".to_string()],
+                current_version: "1.7.1".to_string(),
+                pinned: true,
+            },
+            OutdatedPackage {
+                name: "firefox".to_string(),
+                cask: true,
+                installed_versions: vec!["120.0".to_string()],
+                current_version: "121.0".to_string(),
+                pinned: false,
+            },
+        ];
+        assert_eq!(
+            render_outdated(&packages),
+            "jq       1.6 -> 1.7.1 (pinned)\nfirefox  120.0 -> 121.0 (cask)\n"
+        );
+    }
+}
@@ -0,0 +1,93 @@
+//! Command-line parsing and the headless subcommands that print a report and exit.
+
+mod args;
+mod commands;
+mod report;
+
+pub use args::{HELP, Launch, parse_args};
+
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+/// The command ran and found something that needs attention.
+const EXIT_ATTENTION: u8 = 1;
+/// Usage errors and brew invocations that could not run.
+const EXIT_ERROR: u8 = 2;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Subcommand {
+    Status,
+    Outdated,
+    Sizes,
+    Services,
+    Doctor,
+    Report,
+}
+
+impl Subcommand {
+    const ALL: [Subcommand; 6] = [
+        Self::Status,
+        Self::Outdated,
+        Self::Sizes,
+        Self::Services,
+        Self::Doctor,
+        Self::Report,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Status => "status",
+            Self::Outdated => "outdated",
+            Self::Sizes => "sizes",
+            Self::Services => "services",
+            Self::Doctor => "doctor",
+            Self::Report => "report",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|command| command.name() == name)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Invocation {
+    pub command: Subcommand,
+    pub json: bool,
+    /// `report` only: hide the home directory and username.
+    pub redact: bool,
+    /// `report` only: write here instead of stdout.
+    pub output: Option<PathBuf>,
+}
+
+/// What a command found, mapped onto the process exit code.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Outcome {
+    Healthy,
+    Attention,
+}
+
+pub async fn run(invocation: Invocation) -> ExitCode {
+    let result = match invocation.command {
+        Subcommand::Status => commands::status(invocation.json).await,
+        Subcommand::Outdated => commands::outdated(invocation.json).await,
+        Subcommand::Sizes => commands::sizes(invocation.json).await,
+        Subcommand::Services => commands::services(invocation.json).await,
+        Subcommand::Doctor => commands::doctor(invocation.json).await,
+        Subcommand::Report => report::report(&invocation).await,
+    };
+
+    match result {
+        Ok(Outcome::Healthy) => ExitCode::SUCCESS,
+        Ok(Outcome::Attention) => ExitCode::from(EXIT_ATTENTION),
+        Err(err) => {
+            eprintln!("brewery {}: {err}", invocation.command.name());
+            ExitCode::from(EXIT_ERROR)
+        }
+    }
+}
+
+pub fn usage_error(err: &anyhow::Error) -> ExitCode {
+    eprintln!("brewery: {err}");
+    ExitCode::from(EXIT_ERROR)
+}
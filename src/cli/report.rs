@@ -0,0 +1,341 @@
+use std::fmt::Write;
+
+use serde::Serialize;
+
+use super::{Invocation, Outcome};
+use crate::brew::{
+    DoctorReport, OutdatedPackage, ServiceEntry, SizeEntry, fetch_doctor, fetch_outdated,
+    fetch_services, fetch_sizes, run_brew_command,
+};
+use crate::storage::{format_timestamp, load_command_history, unix_now};
+use crate::ui::util::format_size;
+
+const TOP_SIZES: usize = 10;
+const RECENT_COMMANDS: usize = 10;
+
+/// Everything someone debugging a broken Homebrew usually asks for, in one place.
+#[derive(Debug, Default, Serialize)]
+struct HealthReport {
+    generated_at: String,
+    brewery_version: String,
+    brew_version: Option<String>,
+    /// `brew config` as key/value pairs, in brew's order.
+    #[serde(serialize_with = "serialize_pairs")]
+    brew_config: Option<Vec<(String, String)>>,
+    doctor: Option<DoctorReport>,
+    outdated: Option<Vec<OutdatedPackage>>,
+    services: Option<Vec<ServiceEntry>>,
+    top_sizes: Option<Vec<SizeEntry>>,
+    recent_commands: Vec<RecentCommand>,
+    /// Sections that could not be collected, with the reason.
+    errors: Vec<SectionError>,
+}
+
+#[derive(Debug, Serialize)]
+struct RecentCommand {
+    started_at: String,
+    command: String,
+    success: bool,
+    exit_code: Option<i32>,
+    duration: String,
+}
+
+#[derive(Debug, Serialize)]
+struct SectionError {
+    section: &'static str,
+    message: String,
+}
+
+/// Writes the report to `--output` or stdout; a failing section is reported, not fatal.
+pub(super) async fn report(invocation: &Invocation) -> anyhow::Result<Outcome> {
+    let report = collect_report().await;
+    let mut text = if invocation.json {
+        serde_json::to_string_pretty(&report)? + "\n"
+    } else {
+        render_markdown(&report)
+    };
+    if invocation.redact {
+        let home = std::env::var("HOME").ok();
+        let user = std::env::var("USER")
+            .or_else(|_| std::env::var("LOGNAME"))
+            .ok()
+            .or_else(|| {
+                let home = std::path::Path::new(home.as_deref()?);
+                Some(home.file_name()?.to_string_lossy().into_owned())
+            });
+        text = redact(&text, home.as_deref(), user.as_deref());
+    }
+
+    match invocation.output.as_ref() {
+        Some(path) => {
+            std::fs::write(path, text)?;
+            eprintln!("Wrote report to {}", path.display());
+        }
+        None => print!("{text}"),
+    }
+    Ok(Outcome::Healthy)
+}
+
+async fn collect_report() -> HealthReport {
+    let (version, config, doctor, outdated, services, sizes) = tokio::join!(
+        run_brew_command(&["--version"]),
+        run_brew_command(&["config"]),
+        fetch_doctor(),
+        fetch_outdated(),
+        fetch_services(),
+        fetch_sizes(),
+    );
+
+    let mut report = HealthReport {
+        generated_at: format_timestamp(unix_now()),
+        brewery_version: env!("CARGO_PKG_VERSION").to_string(),
+        ..HealthReport::default()
+    };
+    let errors = &mut report.errors;
+    report.brew_version = section(
+        errors,
+        "brew version",
+        version.map(|result| result.stdout.lines().next().unwrap_or_default().to_string()),
+    );
+    report.brew_config = section(
+        errors,
+        "brew config",
+        config.map(|result| parse_brew_config(&result.stdout)),
+    );
+    report.doctor = section(errors, "doctor", doctor);
+    report.outdated = section(errors, "outdated", outdated);
+    report.services = section(errors, "services", services);
+    report.top_sizes = section(
+        errors,
+        "sizes",
+        sizes.map(|mut sizes| {
+            sizes.truncate(TOP_SIZES);
+            sizes
+        }),
+    );
+
+    report.recent_commands = load_command_history(RECENT_COMMANDS)
+        .into_iter()
+        .map(|record| RecentCommand {
+            started_at: format_timestamp(record.started_at),
+            command: record.command_line(),
+            success: record.success,
+            exit_code: record.exit_code,
+            duration: record.duration_label(),
+        })
+        .collect();
+    report
+}
+
+fn section<T>(
+    errors: &mut Vec<SectionError>,
+    name: &'static str,
+    result: anyhow::Result<T>,
+) -> Option<T> {
+    match result {
+        Ok(value) => Some(value),
+        Err(err) => {
+            errors.push(SectionError {
+                section: name,
+                message: err.to_string(),
+            });
+            None
+        }
+    }
+}
+
+/// Serializes as a JSON object while keeping brew's key order.
+fn serialize_pairs<S: serde::Serializer>(
+    pairs: &Option<Vec<(String, String)>>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    match pairs {
+        Some(pairs) => serializer.collect_map(pairs.iter().map(|(key, value)| (key, value))),
+        None => serializer.serialize_none(),
+    }
+}
+
+fn parse_brew_config(stdout: &str) -> Vec<(String, String)> {
+    stdout
+        .lines()
+        .filter_map(|line| line.split_once(": "))
+        .map(|(key, value)| (key.trim().to_string(), value.trim().to_string()))
+        .collect()
+}
+
+fn render_markdown(report: &HealthReport) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "# Homebrew health report\n");
+    let _ = writeln!(
+        out,
+        "Generated {} by brewery {}.\n",
+        report.generated_at, report.brewery_version
+    );
+
+    let _ = writeln!(out, "## Homebrew\n");
+    if let Some(version) = report.brew_version.as_deref() {
+        let _ = writeln!(out, "{version}\n");
+    }
+    if let Some(config) = report.brew_config.as_ref() {
+        out.push_str("| Key | Value |\n| --- | --- |\n");
+        for (key, value) in config {
+            let _ = writeln!(out, "| {key} | {} |", value.replace('|', "\\|"));
+        }
+        out.push('\n');
+    }
+
+    let _ = writeln!(out, "## Doctor\n");
+    if let Some(doctor) = report.doctor.as_ref() {
+        if doctor.ok {
+            out.push_str("No issues.\n\n");
+        } else {
+            let _ = writeln!(out, "{} issue(s).\n", doctor.issues.len());
+        }
+        if !doctor.output.is_empty() {
+            let _ = writeln!(out, "```\n{}\n```\n", doctor.output);
+        }
+    }
+
+    let _ = writeln!(out, "## Outdated\n");
+    if let Some(outdated) = report.outdated.as_ref() {
+        if outdated.is_empty() {
+            out.push_str("Everything is up to date.\n\n");
+        } else {
+            out.push_str("| Package | Installed | Latest |\n| --- | --- | --- |\n");
+            for package in outdated {
+                let kind = if package.cask { " (cask)" } else { "" };
+                let pinned = if package.pinned { " (pinned)" } else { "" };
+                let _ = writeln!(
+                    out,
+                    "| {}{kind}{pinned} | {} | {} |",
+                    package.name,
+                    package.installed_versions.join(", "),
+                    package.current_version
+                );
+            }
+            out.push('\n');
+        }
+    }
+
+    let _ = writeln!(out, "## Services\n");
+    if let Some(services) = report.services.as_ref() {
+        if services.is_empty() {
+            out.push_str("No services.\n\n");
+        } else {
+            out.push_str("| Service | State | User | Exit |\n| --- | --- | --- | --- |\n");
+            for service in services {
+                let _ = writeln!(
+                    out,
+                    "| {} | {} | {} | {} |",
+                    service.name,
+                    service.state_label(),
+                    service.user.as_deref().unwrap_or("-"),
+                    service
+                        .exit_code
+                        .map(|code| code.to_string())
+                        .unwrap_or_else(|| "-".to_string())
+                );
+            }
+            out.push('\n');
+        }
+    }
+
+    let _ = writeln!(out, "## Largest packages\n");
+    if let Some(sizes) = report.top_sizes.as_ref() {
+        out.push_str("| Package | Size |\n| --- | --- |\n");
+        for entry in sizes {
+            let _ = writeln!(out, "| {} | {} |", entry.name, format_size(entry.size_kb));
+        }
+        out.push('\n');
+    }
+
+    let _ = writeln!(out, "## Recent commands\n");
+    if report.recent_commands.is_empty() {
+        out.push_str("None recorded.\n\n");
+    } else {
+        out.push_str("| Started | Command | Result | Took |\n| --- | --- | --- | --- |\n");
+        for command in &report.recent_commands {
+            let result = match (command.success, command.exit_code) {
+                (true, _) => "ok".to_string(),
+                (false, Some(code)) => format!("exit {code}"),
+                (false, None) => "failed".to_string(),
+            };
+            let _ = writeln!(
+                out,
+                "| {} | `{}` | {result} | {} |",
+                command.started_at, command.command, command.duration
+            );
+        }
+        out.push('\n');
+    }
+
+    if !report.errors.is_empty() {
+        let _ = writeln!(out, "## Unavailable sections\n");
+        for error in &report.errors {
+            let _ = writeln!(out, "- {}: {}", error.section, error.message);
+        }
+        out.push('\n');
+    }
+    out
+}
+
+/// Replaces the home directory with `~` and the username, as a whole word, with `<user>`.
+fn redact(text: &str, home: Option<&str>, user: Option<&str>) -> String {
+    let mut text = match home.filter(|home| home.len() > 1) {
+        Some(home) => text.replace(home.trim_end_matches('/'), "~"),
+        None => text.to_string(),
+    };
+    if let Some(user) = user.filter(|user| !user.is_empty()) {
+        text = replace_word(&text, user, "<user>");
+    }
+    text
+}
+
+fn replace_word(text: &str, word: &str, replacement: &str) -> String {
+    let is_word_char = |ch: char| ch.is_alphanumeric() || ch == '_' || ch == '-' || ch == '.';
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(index) = rest.find(word) {
+        let before = rest[..index].chars().next_back();
+        let after = rest[index + word.len()..].chars().next();
+        out.push_str(&rest[..index]);
+        if before.is_some_and(is_word_char) || after.is_some_and(is_word_char) {
+            out.push_str(word);
+        } else {
+            out.push_str(replacement);
+        }
+        rest = &rest[index + word.len()..];
+    }
+    out.push_str(rest);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_brew_config, redact};
+
+    #[test]
+    fn redacts_home_and_whole_word_usernames() {
+        let text = "HOMEBREW_PREFIX: /Users/al/homebrew\nowner al, also alpine";
+        assert_eq!(
+            redact(text, Some("/Users/al"), Some("al")),
+            "HOMEBREW_PREFIX: ~/homebrew\nowner <user>, also alpine"
+        );
+    }
+
+    #[test]
+    fn parses_brew_config_pairs() {
+        let stdout =
+            "HOMEBREW_VERSION: 4.4.0\nORIGIN: https://github.com/Homebrew/brew\nnot a pair\n";
+        assert_eq!(
+            parse_brew_config(stdout),
+            vec![
+                ("HOMEBREW_VERSION".to_string(), "4.4.0".to_string()),
+                (
+                    "ORIGIN".to_string(),
+                    "https://github.com/Homebrew/brew".to_string()
+                ),
+            ]
+        );
+    }
+}
@@ -0,0 +1,429 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use serde::Deserialize;
+
+use crate::app::{IconMode, PackageKind};
+use crate::keymap::{KeySpec, Keymap};
+use crate::storage::config_dir;
+use crate::theme::{ColorMode, ThemeMode, theme_names};
+
+const CONFIG_FILE: &str = "config.toml";
+
+/// Shortest background refresh we accept; anything lower hammers `brew doctor`.
+const MIN_BACKGROUND_REFRESH_SECS: u64 = 30;
+const MAX_DETAILS_DEBOUNCE_MS: u64 = 5_000;
+const MAX_CACHE_CAPACITY: usize = 10_000;
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub theme: ThemeMode,
+    /// Colour depth override; `auto` reads `NO_COLOR`, `COLORTERM` and `TERM`.
+    pub colors: ColorMode,
+    pub icons: IconMode,
+    pub mouse: bool,
+    pub default_list: PackageKind,
+    pub confirm: ConfirmPolicy,
+    /// How homepages and package directories are opened.
+    pub opener: Opener,
+    /// Write theme/icon/mouse toggles made in the UI back to the config file.
+    pub save_ui_toggles: bool,
+    pub refresh: RefreshConfig,
+    pub cache: CacheConfig,
+    /// Key overrides by action name, e.g. `install = "I"` or `move-down = ["j", "ctrl-n"]`.
+    pub keys: BTreeMap<String, KeySpec>,
+    /// Built from `keys` when the config is parsed.
+    #[serde(skip)]
+    pub keymap: Keymap,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            theme: ThemeMode::Auto,
+            colors: ColorMode::Auto,
+            icons: IconMode::Auto,
+            mouse: true,
+            default_list: PackageKind::Formula,
+            confirm: ConfirmPolicy::default(),
+            opener: Opener::Auto,
+            save_ui_toggles: true,
+            refresh: RefreshConfig::default(),
+            cache: CacheConfig::default(),
+            keys: BTreeMap::new(),
+            keymap: Keymap::default(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RefreshConfig {
+    /// Periodic status refresh (doctor/outdated/services); 0 disables it.
+    pub background_status_secs: u64,
+    /// Delay before auto-fetching details after the selection settles.
+    pub details_debounce_ms: u64,
+    /// Scheduled `brew update` while Brewery is open; 0 disables it.
+    pub auto_update_secs: u64,
+}
+
+impl Default for RefreshConfig {
+    fn default() -> Self {
+        Self {
+            background_status_secs: 5 * 60,
+            details_debounce_ms: 300,
+            auto_update_secs: 0,
+        }
+    }
+}
+
+impl RefreshConfig {
+    pub fn background_status_interval(&self) -> Option<Duration> {
+        (self.background_status_secs > 0).then(|| Duration::from_secs(self.background_status_secs))
+    }
+
+    pub fn auto_update_interval(&self) -> Option<Duration> {
+        (self.auto_update_secs > 0).then(|| Duration::from_secs(self.auto_update_secs))
+    }
+
+    pub fn details_debounce(&self) -> Duration {
+        Duration::from_millis(self.details_debounce_ms)
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CacheConfig {
+    /// Maximum number of package details to cache.
+    pub details_capacity: usize,
+    /// Finished commands kept in the on-disk history.
+    pub command_history: usize,
+    /// How long the startup snapshot stays usable; 0 disables it.
+    pub snapshot_ttl_secs: u64,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            details_capacity: 64,
+            command_history: 100,
+            snapshot_ttl_secs: 7 * 24 * 60 * 60,
+        }
+    }
+}
+
+impl CacheConfig {
+    pub fn snapshot_ttl(&self) -> Option<Duration> {
+        (self.snapshot_ttl_secs > 0).then(|| Duration::from_secs(self.snapshot_ttl_secs))
+    }
+}
+
+/// Which actions need a second keypress before they run.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ConfirmPolicy {
+    #[default]
+    Always,
+    Destructive,
+    Never,
+}
+
+impl ConfirmPolicy {
+    pub fn requires_confirmation(self, destructive: bool) -> bool {
+        match self {
+            Self::Always => true,
+            Self::Destructive => destructive,
+            Self::Never => false,
+        }
+    }
+}
+
+/// `auto` uses the desktop opener when there is a display, `print` shows the
+/// target in the status line, `copy` puts it on the clipboard, and anything
+/// else is a command the target is appended to.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(from = "String")]
+pub enum Opener {
+    #[default]
+    Auto,
+    Print,
+    Copy,
+    Command(Vec<String>),
+}
+
+impl From<String> for Opener {
+    fn from(value: String) -> Self {
+        match value.trim() {
+            "auto" => Self::Auto,
+            "print" => Self::Print,
+            "copy" => Self::Copy,
+            command => Self::Command(command.split_whitespace().map(str::to_string).collect()),
+        }
+    }
+}
+
+pub fn default_config_path() -> Option<PathBuf> {
+    config_dir().map(|dir| dir.join(CONFIG_FILE))
+}
+
+/// Loads the config at `path`; a missing file yields the defaults.
+pub fn load_config(path: Option<&Path>) -> anyhow::Result<Config> {
+    match path {
+        Some(path) => read_config(path, true),
+        None => Ok(Config::default()),
+    }
+}
+
+/// Loads a config named with `--config`, which must exist.
+pub fn load_config_file(path: &Path) -> anyhow::Result<Config> {
+    read_config(path, false)
+}
+
+fn read_config(path: &Path, missing_ok: bool) -> anyhow::Result<Config> {
+    let text = match std::fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(err) if missing_ok && err.kind() == std::io::ErrorKind::NotFound => {
+            return Ok(Config::default());
+        }
+        Err(err) => {
+            return Err(anyhow::anyhow!(
+                "Could not read config {}: {err}",
+                path.display()
+            ));
+        }
+    };
+
+    parse_config(&text).map_err(|err| anyhow::anyhow!("Invalid config {}: {err}", path.display()))
+}
+
+fn parse_config(text: &str) -> anyhow::Result<Config> {
+    let mut config: Config = toml::from_str(text)?;
+    validate_config(&config)?;
+    config.keymap = Keymap::with_overrides(&config.keys)?;
+    Ok(config)
+}
+
+fn validate_config(config: &Config) -> anyhow::Result<()> {
+    if config.theme != ThemeMode::Auto {
+        config.theme.resolve().map_err(|err| {
+            anyhow::anyhow!("theme: {err} (available: {})", theme_names().join(", "))
+        })?;
+    }
+
+    if config.opener == Opener::Command(Vec::new()) {
+        anyhow::bail!("opener must be auto, print, copy, or a command");
+    }
+
+    let refresh_secs = config.refresh.background_status_secs;
+    if refresh_secs != 0 && refresh_secs < MIN_BACKGROUND_REFRESH_SECS {
+        anyhow::bail!(
+            "refresh.background_status_secs must be 0 (disabled) or at least {MIN_BACKGROUND_REFRESH_SECS}, got {refresh_secs}"
+        );
+    }
+
+    if config.refresh.details_debounce_ms > MAX_DETAILS_DEBOUNCE_MS {
+        anyhow::bail!(
+            "refresh.details_debounce_ms must be at most {MAX_DETAILS_DEBOUNCE_MS}, got {}",
+            config.refresh.details_debounce_ms
+        );
+    }
+
+    for (key, value) in [
+        ("cache.details_capacity", config.cache.details_capacity),
+        ("cache.command_history", config.cache.command_history),
+    ] {
+        if value == 0 || value > MAX_CACHE_CAPACITY {
+            anyhow::bail!("{key} must be between 1 and {MAX_CACHE_CAPACITY}, got {value}");
+        }
+    }
+
+    Ok(())
+}
+
+/// Updates a single top-level key in place, keeping the user's comments and layout.
+pub fn write_config_value(path: &Path, key: &str, value: toml_edit::Value) -> anyhow::Result<()> {
+    let text = match std::fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(err) => return Err(err.into()),
+    };
+
+    let updated = set_config_value(&text, key, value)?;
+    // Replace the file a symlinked config points at, not the link itself.
+    let path = std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    // Through a temporary sibling, like `storage::write_json`, so a crash never
+    // leaves a truncated config behind.
+    let tmp = path.with_extension("toml.tmp");
+    std::fs::write(&tmp, updated)?;
+    std::fs::rename(&tmp, &path)?;
+    Ok(())
+}
+
+fn set_config_value(text: &str, key: &str, value: toml_edit::Value) -> anyhow::Result<String> {
+    let mut document: toml_edit::DocumentMut = text.parse()?;
+    let mut value = value;
+    if let Some(existing) = document.get(key).and_then(toml_edit::Item::as_value) {
+        *value.decor_mut() = existing.decor().clone();
+    }
+    document[key] = toml_edit::Item::Value(value);
+    Ok(document.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        ConfirmPolicy, Opener, load_config, load_config_file, parse_config, set_config_value,
+        write_config_value,
+    };
+    use crate::app::{IconMode, PackageKind};
+    use crate::keymap::Action;
+    use crate::theme::{ColorMode, ThemeMode};
+
+    #[test]
+    fn empty_config_uses_defaults() {
+        let config = parse_config("").expect("empty config should parse");
+        assert_eq!(config.theme, ThemeMode::Auto);
+        assert!(config.mouse);
+        assert_eq!(config.cache.details_capacity, 64);
+        assert_eq!(config.refresh.background_status_secs, 300);
+    }
+
+    #[test]
+    fn parses_all_sections() {
+        let config = parse_config(
+            r#"
+            theme = "dark"
+            colors = "256"
+            icons = "ascii"
+            mouse = false
+            default_list = "casks"
+            confirm = "destructive"
+            opener = "firefox --new-tab"
+
+            [refresh]
+            background_status_secs = 0
+            details_debounce_ms = 150
+
+            [cache]
+            details_capacity = 128
+            command_history = 50
+            "#,
+        )
+        .expect("config should parse");
+
+        assert_eq!(config.theme, ThemeMode::Named("dark".to_string()));
+        assert_eq!(config.colors, ColorMode::Ansi256);
+        assert_eq!(config.icons, IconMode::Ascii);
+        assert!(!config.mouse);
+        assert_eq!(config.default_list, PackageKind::Cask);
+        assert_eq!(config.confirm, ConfirmPolicy::Destructive);
+        assert_eq!(
+            config.opener,
+            Opener::Command(vec!["firefox".to_string(), "--new-tab".to_string()])
+        );
+        assert_eq!(config.refresh.background_status_interval(), None);
+        assert_eq!(config.cache.command_history, 50);
+    }
+
+    #[test]
+    fn rejects_unknown_keys_with_location() {
+        let err = parse_config("thme = \"dark\"\n").unwrap_err().to_string();
+        assert!(err.contains("unknown field `thme`"), "{err}");
+        assert!(err.contains("line 1"), "{err}");
+    }
+
+    #[test]
+    fn rejects_out_of_range_values() {
+        let err = parse_config("[refresh]\nbackground_status_secs = 5\n")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("refresh.background_status_secs"), "{err}");
+
+        let err = parse_config("[cache]\ndetails_capacity = 0\n")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("cache.details_capacity"), "{err}");
+    }
+
+    #[test]
+    fn rejects_unknown_theme() {
+        let err = parse_config("theme = \"no-such-theme\"\n")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("Unknown theme `no-such-theme`"), "{err}");
+        assert!(err.contains("gruvbox-dark"), "{err}");
+    }
+
+    #[test]
+    fn builds_keymap_from_keys_table() {
+        let config = parse_config("[keys]\ninstall = \"I\"\nservice-info = \"ctrl-i\"\n")
+            .expect("keys should parse");
+        assert_eq!(config.keymap.label(Action::Install, true), "I");
+
+        let err = parse_config("[keys]\ninstall = \"q\"\n")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("bound to both `quit` and `install`"), "{err}");
+    }
+
+    #[test]
+    fn confirm_policy_gates_by_destructiveness() {
+        assert!(ConfirmPolicy::Always.requires_confirmation(false));
+        assert!(ConfirmPolicy::Destructive.requires_confirmation(true));
+        assert!(!ConfirmPolicy::Destructive.requires_confirmation(false));
+        assert!(!ConfirmPolicy::Never.requires_confirmation(true));
+    }
+
+    #[test]
+    fn write_back_keeps_comments() {
+        let original =
+            "# my settings\ntheme = \"light\" # daytime\n\n[cache]\ndetails_capacity = 10\n";
+        let updated = set_config_value(original, "theme", "dark".into()).expect("should update");
+        assert!(updated.contains("# my settings"));
+        assert!(updated.contains("theme = \"dark\" # daytime"));
+        assert!(updated.contains("details_capacity = 10"));
+
+        let created = set_config_value("", "mouse", false.into()).expect("should create");
+        assert_eq!(created.trim(), "mouse = false");
+    }
+
+    #[test]
+    fn write_back_replaces_the_file_behind_a_symlink() {
+        let dir = std::env::temp_dir().join(format!("brewery-config-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let target = dir.join("dotfiles.toml");
+        let link = dir.join("config.toml");
+        std::fs::write(&target, "mouse = true\n").unwrap();
+        std::os::unix::fs::symlink(&target, &link).unwrap();
+
+        write_config_value(&link, "mouse", false.into()).unwrap();
+        assert!(
+            std::fs::symlink_metadata(&link)
+                .unwrap()
+                .file_type()
+                .is_symlink()
+        );
+        assert_eq!(
+            std::fs::read_to_string(&target).unwrap().trim(),
+            "mouse = false"
+        );
+        assert!(!dir.join("dotfiles.toml.tmp").exists());
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn only_the_default_config_may_be_missing() {
+        let missing =
+            std::env::temp_dir().join(format!("brewery-missing-{}.toml", std::process::id()));
+        assert!(load_config(Some(&missing)).is_ok());
+        let err = load_config_file(&missing).unwrap_err().to_string();
+        assert!(err.starts_with("Could not read config"), "{err}");
+    }
+}
@@ -0,0 +1,626 @@
+use std::collections::{BTreeMap, HashMap};
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::Deserialize;
+
+/// Named commands that can be bound to keys in normal mode.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Action {
+    MoveDown,
+    MoveUp,
+    NextPanel,
+    PrevPanel,
+    PrevStatusTab,
+    NextStatusTab,
+    FilterInstalled,
+    FindPackages,
+    ToggleListKind,
+    LoadDetails,
+    LoadDeps,
+    Install,
+    Uninstall,
+    Upgrade,
+    SelfUpdate,
+    SwitchKeg,
+    RemoveKeg,
+    ToggleLink,
+    LinkPreview,
+    Migrate,
+    Zap,
+    OpenHomepage,
+    Reveal,
+    RevealOpt,
+    ServiceStart,
+    ServiceStop,
+    ServiceRestart,
+    ServiceRun,
+    ServiceKill,
+    ServiceAll,
+    ServiceCleanup,
+    ServiceInfo,
+    ServiceLogs,
+    FilterFailedServices,
+    FilterAutostartServices,
+    CycleServiceKind,
+    ToggleOutdated,
+    RunIssueCommand,
+    Yank,
+    YankInstall,
+    Refresh,
+    LoadSizes,
+    SizeBaseline,
+    StatusCheck,
+    BrewUpdate,
+    OpenOutput,
+    ThemePicker,
+    ToggleMouse,
+    ToggleIcons,
+    Cleanup,
+    Autoremove,
+    BundleDump,
+    ToggleView,
+    Help,
+    Quit,
+    Cancel,
+    PagerPageDown,
+    PagerPageUp,
+    PagerTop,
+    PagerBottom,
+    PagerSearch,
+    PagerNextMatch,
+    PagerPrevMatch,
+    PagerSave,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HelpSection {
+    Navigation,
+    Search,
+    Actions,
+    Data,
+    Other,
+    /// Keys only read while the output pager is open; they may reuse main-view keys.
+    Pager,
+}
+
+impl HelpSection {
+    pub const ALL: [HelpSection; 6] = [
+        Self::Navigation,
+        Self::Search,
+        Self::Actions,
+        Self::Data,
+        Self::Other,
+        Self::Pager,
+    ];
+
+    pub fn title(self) -> &'static str {
+        match self {
+            Self::Navigation => "Navigation",
+            Self::Search => "Search",
+            Self::Actions => "Actions",
+            Self::Data => "Data",
+            Self::Other => "Other",
+            Self::Pager => "Pager",
+        }
+    }
+}
+
+pub struct ActionSpec {
+    pub action: Action,
+    /// Name used in the `[keys]` config table.
+    pub name: &'static str,
+    pub description: &'static str,
+    pub section: HelpSection,
+    default_keys: &'static [&'static str],
+}
+
+const fn spec(
+    action: Action,
+    name: &'static str,
+    description: &'static str,
+    section: HelpSection,
+    default_keys: &'static [&'static str],
+) -> ActionSpec {
+    ActionSpec {
+        action,
+        name,
+        description,
+        section,
+        default_keys,
+    }
+}
+
+/// The keymap table, in help-popup order.
+#[rustfmt::skip]
+pub const ACTIONS: &[ActionSpec] = &[
+    spec(Action::MoveDown, "move-down", "Move down", HelpSection::Navigation, &["j", "down"]),
+    spec(Action::MoveUp, "move-up", "Move up", HelpSection::Navigation, &["k", "up"]),
+    spec(Action::NextPanel, "next-panel", "Next panel", HelpSection::Navigation, &["tab"]),
+    spec(Action::PrevPanel, "prev-panel", "Previous panel", HelpSection::Navigation, &["backtab"]),
+    spec(Action::PrevStatusTab, "prev-status-tab", "Previous status tab", HelpSection::Navigation, &["l", "left"]),
+    spec(Action::NextStatusTab, "next-status-tab", "Next status tab", HelpSection::Navigation, &[";", "right"]),
+    spec(Action::FilterInstalled, "filter-installed", "Search installed list", HelpSection::Search, &["/"]),
+    spec(Action::FindPackages, "find-packages", "Find packages", HelpSection::Search, &["f"]),
+    spec(Action::ToggleListKind, "toggle-list-kind", "Toggle formula/cask list", HelpSection::Search, &["C"]),
+    spec(Action::LoadDetails, "load-details", "Load details / open transcript", HelpSection::Actions, &["enter"]),
+    spec(Action::LoadDeps, "load-deps", "Load deps/uses", HelpSection::Actions, &["d"]),
+    spec(Action::Install, "install", "Install selected (confirm)", HelpSection::Actions, &["i"]),
+    spec(Action::Uninstall, "uninstall", "Uninstall selected (confirm)", HelpSection::Actions, &["u"]),
+    spec(Action::Upgrade, "upgrade", "Upgrade selected or all outdated (confirm)", HelpSection::Actions, &["U"]),
+    spec(Action::SelfUpdate, "self-update", "Update Brewery via cargo (confirm)", HelpSection::Actions, &["P"]),
+    spec(Action::SwitchKeg, "switch-keg", "Link highlighted installed version (confirm)", HelpSection::Actions, &["V"]),
+    spec(Action::RemoveKeg, "remove-keg", "Remove highlighted old version (confirm)", HelpSection::Actions, &["x"]),
+    spec(Action::ToggleLink, "toggle-link", "Link or unlink selected formula (confirm)", HelpSection::Actions, &["L"]),
+    spec(Action::LinkPreview, "link-preview", "Preview link conflicts (dry run)", HelpSection::Actions, &["D"]),
+    spec(Action::Migrate, "migrate", "Install replacement for deprecated package (confirm)", HelpSection::Actions, &["M"]),
+    spec(Action::Zap, "zap", "Zap selected cask and its app data (preview, confirm twice)", HelpSection::Actions, &["Z"]),
+    spec(Action::OpenHomepage, "open-homepage", "Open homepage (or print/copy it, per opener)", HelpSection::Actions, &["g"]),
+    spec(Action::Reveal, "reveal", "Reveal highlighted keg or cask app in the file manager", HelpSection::Actions, &["E"]),
+    spec(Action::RevealOpt, "reveal-opt", "Reveal opt link or Caskroom folder", HelpSection::Actions, &["G"]),
+    spec(Action::ServiceStart, "service-start", "Start selected service (confirm)", HelpSection::Actions, &["S"]),
+    spec(Action::ServiceStop, "service-stop", "Stop selected service (confirm)", HelpSection::Actions, &["X"]),
+    spec(Action::ServiceRestart, "service-restart", "Restart selected service (confirm)", HelpSection::Actions, &["R"]),
+    spec(Action::ServiceRun, "service-run", "Run selected service without registering at login (confirm)", HelpSection::Actions, &["N"]),
+    spec(Action::ServiceKill, "service-kill", "Kill selected service, keeping it registered (confirm)", HelpSection::Actions, &["ctrl-x"]),
+    spec(Action::ServiceAll, "service-all", "Apply the next service action to all services (--all)", HelpSection::Actions, &["*"]),
+    spec(Action::ServiceCleanup, "service-cleanup", "Remove unused service files (confirm)", HelpSection::Actions, &["W"]),
+    spec(Action::ServiceInfo, "service-info", "Show selected service info", HelpSection::Actions, &["I"]),
+    spec(Action::ServiceLogs, "service-logs", "Follow selected service's logs", HelpSection::Actions, &["T"]),
+    spec(Action::FilterFailedServices, "filter-failed-services", "Filter failed services / commands", HelpSection::Actions, &["F"]),
+    spec(Action::FilterAutostartServices, "filter-autostart-services", "Filter auto-start services", HelpSection::Actions, &["A"]),
+    spec(Action::CycleServiceKind, "cycle-service-kind", "Cycle service / command kind filter", HelpSection::Actions, &["K"]),
+    spec(Action::ToggleOutdated, "toggle-outdated", "Toggle outdated-only formula filter", HelpSection::Actions, &["o"]),
+    spec(Action::RunIssueCommand, "run-issue-command", "Run suggested doctor command (confirm)", HelpSection::Actions, &["e"]),
+    spec(Action::Yank, "yank", "Copy focused package, command or output", HelpSection::Actions, &["y"]),
+    spec(Action::YankInstall, "yank-install", "Copy `brew install` line for selected package", HelpSection::Actions, &["Y"]),
+    spec(Action::Refresh, "refresh", "Refresh formulae + casks", HelpSection::Data, &["r"]),
+    spec(Action::LoadSizes, "load-sizes", "Load sizes", HelpSection::Data, &["s"]),
+    spec(Action::SizeBaseline, "size-baseline", "Size delta: last scan/week", HelpSection::Data, &["w"]),
+    spec(Action::StatusCheck, "status-check", "Status check", HelpSection::Data, &["h"]),
+    spec(Action::BrewUpdate, "brew-update", "Update Homebrew metadata (brew update)", HelpSection::Data, &["p"]),
+    spec(Action::OpenOutput, "open-output", "Open last command output", HelpSection::Data, &["O"]),
+    spec(Action::ThemePicker, "theme-picker", "Pick theme (live preview)", HelpSection::Other, &["t"]),
+    spec(Action::ToggleMouse, "toggle-mouse", "Toggle mouse", HelpSection::Other, &["m"]),
+    spec(Action::ToggleIcons, "toggle-icons", "Toggle icons", HelpSection::Other, &["alt-i"]),
+    spec(Action::Cleanup, "cleanup", "Cleanup", HelpSection::Other, &["c"]),
+    spec(Action::Autoremove, "autoremove", "Autoremove", HelpSection::Other, &["a"]),
+    spec(Action::BundleDump, "bundle-dump", "Bundle dump", HelpSection::Other, &["b"]),
+    spec(Action::ToggleView, "toggle-view", "Toggle view", HelpSection::Other, &["v"]),
+    spec(Action::Help, "help", "Toggle help", HelpSection::Other, &["?"]),
+    spec(Action::Quit, "quit", "Quit", HelpSection::Other, &["q"]),
+    spec(Action::Cancel, "cancel", "Cancel action", HelpSection::Other, &["esc"]),
+    spec(Action::PagerPageDown, "pager-page-down", "Scroll one page down", HelpSection::Pager, &["pagedown", "space"]),
+    spec(Action::PagerPageUp, "pager-page-up", "Scroll one page up", HelpSection::Pager, &["pageup"]),
+    spec(Action::PagerTop, "pager-top", "Jump to top", HelpSection::Pager, &["g", "home"]),
+    spec(Action::PagerBottom, "pager-bottom", "Jump to bottom (follows logs)", HelpSection::Pager, &["G", "end"]),
+    spec(Action::PagerSearch, "pager-search", "Search output", HelpSection::Pager, &["/"]),
+    spec(Action::PagerNextMatch, "pager-next-match", "Next match", HelpSection::Pager, &["n"]),
+    spec(Action::PagerPrevMatch, "pager-prev-match", "Previous match", HelpSection::Pager, &["N"]),
+    spec(Action::PagerSave, "pager-save", "Save output to a log file", HelpSection::Pager, &["s"]),
+];
+
+impl Action {
+    /// Actions refused in `--read-only` sessions.
+    pub fn modifies_system(self) -> bool {
+        matches!(
+            self,
+            Self::Install
+                | Self::Uninstall
+                | Self::Upgrade
+                | Self::SelfUpdate
+                | Self::ServiceStart
+                | Self::ServiceStop
+                | Self::ServiceRestart
+                | Self::ServiceRun
+                | Self::ServiceKill
+                | Self::ServiceCleanup
+                | Self::Cleanup
+                | Self::Autoremove
+                | Self::BundleDump
+                | Self::RunIssueCommand
+                | Self::BrewUpdate
+                | Self::SwitchKeg
+                | Self::RemoveKeg
+                | Self::ToggleLink
+                | Self::Migrate
+                | Self::Zap
+        )
+    }
+
+    /// Read only by the output pager, so its keys do not clash with the main view.
+    pub fn is_pager(self) -> bool {
+        self.spec().section == HelpSection::Pager
+    }
+
+    pub fn spec(self) -> &'static ActionSpec {
+        ACTIONS
+            .iter()
+            .find(|spec| spec.action == self)
+            .expect("every action has a keymap entry")
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        ACTIONS
+            .iter()
+            .find(|spec| spec.name == name)
+            .map(|spec| spec.action)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct KeyBinding {
+    code: KeyCode,
+    modifiers: KeyModifiers,
+}
+
+impl KeyBinding {
+    /// Shift is implied by the character case (and by BackTab), so it is dropped there.
+    fn from_event(key: KeyEvent) -> Self {
+        let mut modifiers = key.modifiers & (KeyModifiers::CONTROL | KeyModifiers::ALT);
+        if !matches!(key.code, KeyCode::Char(_) | KeyCode::BackTab) {
+            modifiers |= key.modifiers & KeyModifiers::SHIFT;
+        }
+        Self {
+            code: key.code,
+            modifiers,
+        }
+    }
+
+    pub fn parse(spec: &str) -> Option<Self> {
+        let mut rest = spec.trim();
+        let mut modifiers = KeyModifiers::NONE;
+        loop {
+            let lower = rest.to_ascii_lowercase();
+            let (modifier, prefix_len) = if lower.starts_with("ctrl-") || lower.starts_with("ctrl+")
+            {
+                (KeyModifiers::CONTROL, 5)
+            } else if lower.starts_with("alt-") || lower.starts_with("alt+") {
+                (KeyModifiers::ALT, 4)
+            } else if lower.starts_with("shift-") || lower.starts_with("shift+") {
+                (KeyModifiers::SHIFT, 6)
+            } else {
+                break;
+            };
+            if rest.len() == prefix_len {
+                break;
+            }
+            modifiers |= modifier;
+            rest = &rest[prefix_len..];
+        }
+
+        let code = match rest.to_ascii_lowercase().as_str() {
+            "enter" | "return" => KeyCode::Enter,
+            "esc" | "escape" => KeyCode::Esc,
+            "tab" if modifiers.contains(KeyModifiers::SHIFT) => KeyCode::BackTab,
+            "tab" => KeyCode::Tab,
+            "backtab" => KeyCode::BackTab,
+            "space" => KeyCode::Char(' '),
+            "backspace" => KeyCode::Backspace,
+            "delete" | "del" => KeyCode::Delete,
+            "up" => KeyCode::Up,
+            "down" => KeyCode::Down,
+            "left" => KeyCode::Left,
+            "right" => KeyCode::Right,
+            "home" => KeyCode::Home,
+            "end" => KeyCode::End,
+            "pageup" => KeyCode::PageUp,
+            "pagedown" => KeyCode::PageDown,
+            name if name.len() > 1 && name.starts_with('f') => {
+                KeyCode::F(name[1..].parse().ok().filter(|n| (1..=12).contains(n))?)
+            }
+            _ => {
+                let mut chars = rest.chars();
+                let ch = chars.next()?;
+                if chars.next().is_some() {
+                    return None;
+                }
+                if modifiers.contains(KeyModifiers::SHIFT) {
+                    KeyCode::Char(ch.to_ascii_uppercase())
+                } else {
+                    KeyCode::Char(ch)
+                }
+            }
+        };
+
+        Some(Self::from_event(KeyEvent::new(code, modifiers)))
+    }
+
+    pub fn label(self, ascii: bool) -> String {
+        let key = match self.code {
+            KeyCode::Char(' ') => "Space".to_string(),
+            KeyCode::Char(ch) => ch.to_string(),
+            KeyCode::Enter => "Enter".to_string(),
+            KeyCode::Esc => "Esc".to_string(),
+            KeyCode::Tab => "Tab".to_string(),
+            KeyCode::BackTab => "S-Tab".to_string(),
+            KeyCode::Backspace => "Bksp".to_string(),
+            KeyCode::Delete => "Del".to_string(),
+            KeyCode::Up => arrow(ascii, "↑", "up"),
+            KeyCode::Down => arrow(ascii, "↓", "down"),
+            KeyCode::Left => arrow(ascii, "←", "left"),
+            KeyCode::Right => arrow(ascii, "→", "right"),
+            KeyCode::Home => "Home".to_string(),
+            KeyCode::End => "End".to_string(),
+            KeyCode::PageUp => "PgUp".to_string(),
+            KeyCode::PageDown => "PgDn".to_string(),
+            KeyCode::F(n) => format!("F{n}"),
+            other => format!("{other:?}"),
+        };
+
+        let mut label = String::new();
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            label.push_str("Ctrl+");
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            label.push_str("Alt+");
+        }
+        if self.modifiers.contains(KeyModifiers::SHIFT) {
+            label.push_str("Shift+");
+        }
+        label.push_str(&key);
+        label
+    }
+}
+
+fn arrow(ascii: bool, nerd: &str, plain: &str) -> String {
+    if ascii { plain } else { nerd }.to_string()
+}
+
+/// One key or a list of keys for an action in the `[keys]` config table.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(untagged)]
+pub enum KeySpec {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl KeySpec {
+    fn keys(&self) -> Vec<&str> {
+        match self {
+            Self::One(key) => vec![key.as_str()],
+            Self::Many(keys) => keys.iter().map(String::as_str).collect(),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Keymap {
+    bindings: Vec<(KeyBinding, Action)>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        let bindings = ACTIONS
+            .iter()
+            .flat_map(|spec| {
+                spec.default_keys.iter().map(move |key| {
+                    let binding = KeyBinding::parse(key).expect("default keys are valid");
+                    (binding, spec.action)
+                })
+            })
+            .collect();
+        Self { bindings }
+    }
+}
+
+impl Keymap {
+    /// Applies `[keys]` overrides; each listed action replaces all of its default keys.
+    pub fn with_overrides(overrides: &BTreeMap<String, KeySpec>) -> anyhow::Result<Self> {
+        let mut keymap = Self::default();
+        let mut errors = Vec::new();
+
+        for (name, spec) in overrides {
+            let Some(action) = Action::from_name(name) else {
+                errors.push(format!("unknown action `{name}` in [keys]"));
+                continue;
+            };
+
+            keymap.bindings.retain(|(_, bound)| *bound != action);
+            for key in spec.keys() {
+                match KeyBinding::parse(key) {
+                    Some(binding) => keymap.bindings.push((binding, action)),
+                    None => errors.push(format!("invalid key `{key}` for `{name}`")),
+                }
+            }
+        }
+
+        errors.extend(keymap.conflicts());
+        if !errors.is_empty() {
+            anyhow::bail!("{}", errors.join("; "));
+        }
+        Ok(keymap)
+    }
+
+    /// Keys bound twice within the main view or within the pager.
+    fn conflicts(&self) -> Vec<String> {
+        let mut seen: HashMap<(KeyBinding, bool), Action> = HashMap::new();
+        let mut conflicts = Vec::new();
+        for (binding, action) in &self.bindings {
+            let key = (*binding, action.is_pager());
+            match seen.get(&key) {
+                Some(existing) if existing != action => conflicts.push(format!(
+                    "key `{}` is bound to both `{}` and `{}`",
+                    binding.label(true),
+                    existing.spec().name,
+                    action.spec().name
+                )),
+                Some(_) => {}
+                None => {
+                    seen.insert(key, *action);
+                }
+            }
+        }
+        conflicts
+    }
+
+    pub fn action_for(&self, key: KeyEvent) -> Option<Action> {
+        self.find_action(key, |action| !action.is_pager())
+    }
+
+    /// Pager actions first, then the main-view actions the pager shares.
+    pub fn pager_action_for(&self, key: KeyEvent) -> Option<Action> {
+        self.find_action(key, Action::is_pager)
+            .or_else(|| self.action_for(key))
+    }
+
+    fn find_action(&self, key: KeyEvent, scope: impl Fn(Action) -> bool) -> Option<Action> {
+        let binding = KeyBinding::from_event(key);
+        self.bindings
+            .iter()
+            .find(|(bound, action)| *bound == binding && scope(*action))
+            .map(|(_, action)| *action)
+    }
+
+    pub fn is_bound(&self, action: Action) -> bool {
+        self.bindings.iter().any(|(_, bound)| *bound == action)
+    }
+
+    /// All keys for `action`, e.g. `j / ↓`.
+    pub fn label(&self, action: Action, ascii: bool) -> String {
+        self.bindings
+            .iter()
+            .filter(|(_, bound)| *bound == action)
+            .map(|(binding, _)| binding.label(ascii))
+            .collect::<Vec<_>>()
+            .join(" / ")
+    }
+
+    /// The first key for `action`, used in inline hints.
+    pub fn primary_label(&self, action: Action, ascii: bool) -> String {
+        self.bindings
+            .iter()
+            .find(|(_, bound)| *bound == action)
+            .map(|(binding, _)| binding.label(ascii))
+            .unwrap_or_else(|| "unbound".to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    use super::{Action, KeyBinding, KeySpec, Keymap};
+
+    fn overrides(entries: &[(&str, KeySpec)]) -> BTreeMap<String, KeySpec> {
+        entries
+            .iter()
+            .map(|(name, spec)| ((*name).to_string(), spec.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn default_keymap_has_no_conflicts() {
+        assert!(Keymap::default().conflicts().is_empty());
+    }
+
+    #[test]
+    fn matches_shifted_chars_and_modifiers() {
+        let keymap = Keymap::default();
+        let shifted = KeyEvent::new(KeyCode::Char('C'), KeyModifiers::SHIFT);
+        assert_eq!(keymap.action_for(shifted), Some(Action::ToggleListKind));
+
+        let alt_i = KeyEvent::new(KeyCode::Char('i'), KeyModifiers::ALT);
+        assert_eq!(keymap.action_for(alt_i), Some(Action::ToggleIcons));
+
+        let plain_i = KeyEvent::new(KeyCode::Char('i'), KeyModifiers::NONE);
+        assert_eq!(keymap.action_for(plain_i), Some(Action::Install));
+
+        let backtab = KeyEvent::new(KeyCode::BackTab, KeyModifiers::SHIFT);
+        assert_eq!(keymap.action_for(backtab), Some(Action::PrevPanel));
+    }
+
+    #[test]
+    fn parses_named_keys() {
+        assert_eq!(
+            KeyBinding::parse("ctrl-n").map(|b| b.label(true)),
+            Some("Ctrl+n".to_string())
+        );
+        assert_eq!(
+            KeyBinding::parse("shift-tab").map(|b| b.label(true)),
+            Some("S-Tab".to_string())
+        );
+        assert_eq!(
+            KeyBinding::parse("shift-x").map(|b| b.label(true)),
+            Some("X".to_string())
+        );
+        assert_eq!(
+            KeyBinding::parse("F5").map(|b| b.label(true)),
+            Some("F5".to_string())
+        );
+        assert_eq!(
+            KeyBinding::parse("-").map(|b| b.label(true)),
+            Some("-".to_string())
+        );
+        assert!(KeyBinding::parse("nope").is_none());
+        assert!(KeyBinding::parse("f13").is_none());
+    }
+
+    #[test]
+    fn overrides_replace_default_keys() {
+        let keymap = Keymap::with_overrides(&overrides(&[
+            ("install", KeySpec::One("I".to_string())),
+            ("service-info", KeySpec::Many(vec!["ctrl-i".to_string()])),
+        ]))
+        .expect("overrides should apply");
+
+        assert_eq!(keymap.label(Action::Install, true), "I");
+        let plain_i = KeyEvent::new(KeyCode::Char('i'), KeyModifiers::NONE);
+        assert_eq!(keymap.action_for(plain_i), None);
+    }
+
+    #[test]
+    fn reports_conflicts_and_unknown_names() {
+        let err = Keymap::with_overrides(&overrides(&[
+            ("install", KeySpec::One("q".to_string())),
+            ("instal", KeySpec::One("x".to_string())),
+            ("quit", KeySpec::One("hyper-q".to_string())),
+        ]))
+        .unwrap_err()
+        .to_string();
+
+        assert!(err.contains("unknown action `instal`"), "{err}");
+        assert!(err.contains("invalid key `hyper-q` for `quit`"), "{err}");
+
+        let err = Keymap::with_overrides(&overrides(&[("install", KeySpec::One("q".to_string()))]))
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.contains("key `q` is bound to both `quit` and `install`"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn pager_keys_shadow_main_view_keys_only_in_the_pager() {
+        let keymap = Keymap::default();
+        let g = KeyEvent::new(KeyCode::Char('g'), KeyModifiers::NONE);
+        assert_eq!(keymap.action_for(g), Some(Action::OpenHomepage));
+        assert_eq!(keymap.pager_action_for(g), Some(Action::PagerTop));
+
+        let n = KeyEvent::new(KeyCode::Char('n'), KeyModifiers::NONE);
+        assert_eq!(keymap.action_for(n), None);
+        assert_eq!(keymap.pager_action_for(n), Some(Action::PagerNextMatch));
+
+        let y = KeyEvent::new(KeyCode::Char('y'), KeyModifiers::NONE);
+        assert_eq!(keymap.pager_action_for(y), Some(Action::Yank));
+
+        let err =
+            Keymap::with_overrides(&overrides(&[("pager-save", KeySpec::One("n".to_string()))]))
+                .unwrap_err()
+                .to_string();
+        assert!(
+            err.contains("key `n` is bound to both `pager-next-match` and `pager-save`"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn labels_follow_icon_mode() {
+        let keymap = Keymap::default();
+        assert_eq!(keymap.label(Action::MoveDown, false), "j / ↓");
+        assert_eq!(keymap.label(Action::MoveDown, true), "j / down");
+        assert_eq!(keymap.primary_label(Action::LoadDetails, true), "Enter");
+    }
+}
@@ -1,20 +1,61 @@
 mod app;
 mod brew;
+mod cli;
+mod config;
+mod keymap;
 mod runtime;
+mod storage;
 mod theme;
 mod ui;
 
+use std::process::ExitCode;
+
 use app::App;
+use cli::Launch;
 use runtime::event_loop::run_app;
 use runtime::terminal::{restore_terminal, setup_terminal};
+use runtime::tty::ColorReports;
+use theme::ThemeMode;
 
 #[tokio::main]
-async fn main() -> anyhow::Result<()> {
-    let app = App::new();
+async fn main() -> anyhow::Result<ExitCode> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let options = match cli::parse_args(&args) {
+        Ok(Launch::Tui(options)) => options,
+        Ok(Launch::Headless(invocation)) => return Ok(cli::run(invocation).await),
+        Ok(Launch::Help) => {
+            print!("{}", cli::HELP);
+            return Ok(ExitCode::SUCCESS);
+        }
+        Ok(Launch::Version) => {
+            println!("brewery {}", env!("CARGO_PKG_VERSION"));
+            return Ok(ExitCode::SUCCESS);
+        }
+        Err(err) => return Ok(cli::usage_error(&err)),
+    };
+
+    let (mut config, config_path) = match &options.config {
+        Some(path) => (config::load_config_file(path)?, Some(path.clone())),
+        None => {
+            let path = config::default_config_path();
+            (config::load_config(path.as_deref())?, path)
+        }
+    };
+    if let Err(err) = options.apply_to_config(&mut config) {
+        return Ok(cli::usage_error(&err));
+    }
+    let mut app = App::with_config(config, config_path);
+    options.apply_to_app(&mut app);
+    // macOS answers from the system appearance; elsewhere the terminal reports
+    // its colours. Installed before raw mode and dropped after it is undone.
+    let mut color_reports = (cfg!(not(target_os = "macos")) && app.theme_mode == ThemeMode::Auto)
+        .then(ColorReports::install)
+        .and_then(Result::ok);
     let mut terminal = setup_terminal(app.mouse_enabled)?;
 
-    let result = run_app(&mut terminal, app).await;
+    let result = run_app(&mut terminal, app, color_reports.as_mut()).await;
     restore_terminal(&mut terminal)?;
+    drop(color_reports);
 
-    result
+    result.map(|()| ExitCode::SUCCESS)
 }
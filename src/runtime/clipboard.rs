@@ -0,0 +1,159 @@
+//! Clipboard writes through the terminal.
+//!
+//! OSC 52 asks the terminal emulator to set the system clipboard, so a copy
+//! works the same locally and over SSH; inside tmux it is also sent as
+//! passthrough. On a local desktop the text goes to `wl-copy`, `xclip` or
+//! `pbcopy` too, for terminals that ignore OSC 52.
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use crate::runtime::opener::has_desktop;
+
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Many terminals drop OSC 52 payloads past roughly this size.
+const OSC52_MAX_BYTES: usize = 100_000;
+
+pub fn copy(text: &str) -> anyhow::Result<()> {
+    let helper = has_desktop() && copy_with_helper(text);
+    let sequence = osc52_sequence(text);
+    if !needs_osc52(text.len(), sequence.len(), helper)? {
+        return Ok(());
+    }
+
+    let mut stdout = std::io::stdout();
+    stdout.write_all(sequence.as_bytes())?;
+    if std::env::var_os("TMUX").is_some() {
+        stdout.write_all(tmux_passthrough(&sequence).as_bytes())?;
+    }
+    stdout.flush()?;
+    Ok(())
+}
+
+/// Whether OSC 52 should still be sent; oversized payloads are left to the
+/// helper and fail only when no helper took them.
+fn needs_osc52(text_len: usize, sequence_len: usize, helper: bool) -> anyhow::Result<bool> {
+    if sequence_len <= OSC52_MAX_BYTES {
+        return Ok(true);
+    }
+    if helper {
+        return Ok(false);
+    }
+    anyhow::bail!(
+        "{} KB is too large for OSC 52; install wl-copy or xclip",
+        text_len / 1024
+    )
+}
+
+fn osc52_sequence(text: &str) -> String {
+    format!("\x1b]52;c;{}\x07", base64_encode(text.as_bytes()))
+}
+
+/// tmux forwards DCS passthrough when `allow-passthrough` is on, even if
+/// `set-clipboard` is off.
+fn tmux_passthrough(sequence: &str) -> String {
+    format!("\x1bPtmux;{}\x1b\\", sequence.replace('\x1b', "\x1b\x1b"))
+}
+
+fn copy_with_helper(text: &str) -> bool {
+    let helpers: &[&[&str]] = if cfg!(target_os = "macos") {
+        &[&["pbcopy"]]
+    } else if std::env::var_os("WAYLAND_DISPLAY").is_some() {
+        &[&["wl-copy"], &["xclip", "-selection", "clipboard"]]
+    } else {
+        &[&["xclip", "-selection", "clipboard"]]
+    };
+    pipe_to_first_helper(helpers, text)
+}
+
+/// Pipes `text` into the first clipboard tool that starts.
+fn pipe_to_first_helper(helpers: &[&[&str]], text: &str) -> bool {
+    for helper in helpers {
+        let Ok(mut child) = Command::new(helper[0])
+            .args(&helper[1..])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+        else {
+            continue;
+        };
+        let written = child
+            .stdin
+            .take()
+            .is_some_and(|mut stdin| stdin.write_all(text.as_bytes()).is_ok());
+        // xclip keeps serving the selection in the background; reap it off-thread.
+        std::thread::spawn(move || child.wait());
+        if written {
+            return true;
+        }
+    }
+    false
+}
+
+fn base64_encode(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let b = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let n = (u32::from(b[0]) << 16) | (u32::from(b[1]) << 8) | u32::from(b[2]);
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(BASE64_ALPHABET[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        OSC52_MAX_BYTES, base64_encode, needs_osc52, osc52_sequence, pipe_to_first_helper,
+        tmux_passthrough,
+    };
+
+    #[test]
+    fn encodes_padding_and_wraps_in_osc52() {
+        assert_eq!(base64_encode(b"f"), "Zg==");
+        assert_eq!(base64_encode(b"fo"), "Zm8=");
+        assert_eq!(base64_encode(b"foo"), "Zm9v");
+        assert_eq!(osc52_sequence("brew"), "\x1b]52;c;YnJldw==\x07");
+    }
+
+    #[test]
+    fn wraps_osc52_for_tmux_passthrough() {
+        assert_eq!(
+            tmux_passthrough(&osc52_sequence("brew")),
+            "\x1bPtmux;\x1b\x1b]52;c;YnJldw==\x07\x1b\\"
+        );
+    }
+
+    #[test]
+    fn leaves_oversized_copies_to_the_helper() {
+        assert!(needs_osc52(10, OSC52_MAX_BYTES, false).unwrap());
+        assert!(needs_osc52(10, OSC52_MAX_BYTES, true).unwrap());
+        assert!(!needs_osc52(200_000, OSC52_MAX_BYTES + 1, true).unwrap());
+        let err = needs_osc52(200_000, OSC52_MAX_BYTES + 1, false).unwrap_err();
+        assert!(err.to_string().starts_with("195 KB is too large"));
+    }
+
+    #[test]
+    fn falls_through_to_the_next_helper() {
+        assert!(pipe_to_first_helper(
+            &[&["brewery-no-such-clipboard"], &["cat"]],
+            "brew"
+        ));
+        assert!(!pipe_to_first_helper(
+            &[&["brewery-no-such-clipboard"]],
+            "brew"
+        ));
+    }
+}
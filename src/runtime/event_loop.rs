@@ -9,8 +9,11 @@ use ratatui::backend::CrosstermBackend;
 use ratatui::layout::Rect;
 
 use crate::app::App;
+use crate::brew::CommandKind;
 use crate::runtime::input::{handle_key_event, handle_mouse_event};
 use crate::runtime::messages::{create_channels, handle_auto_details, process_pending_messages};
+use crate::runtime::tty::ColorReports;
+use crate::theme::set_terminal_appearance;
 use crate::ui::{draw, help, layout};
 
 /// Poll interval while spinner/progress is active.
@@ -19,28 +22,43 @@ const ACTIVE_TICK_RATE: Duration = Duration::from_millis(80);
 /// Maximum poll interval while app is idle.
 const IDLE_TICK_RATE: Duration = Duration::from_secs(1);
 
-/// Debounce delay for auto-fetching details when selection changes.
-/// This prevents rapid-fire requests when scrolling quickly through lists.
-/// Details will only be fetched after the user has stopped on an item for this duration.
-const DETAILS_DEBOUNCE: Duration = Duration::from_millis(300);
-
-/// Periodic background status refresh (doctor/outdated/services).
-const BACKGROUND_STATUS_REFRESH: Duration = Duration::from_secs(5 * 60);
+/// How long to wait for the terminal to answer the background colour query.
+const BACKGROUND_QUERY_TIMEOUT: Duration = Duration::from_millis(200);
 
 pub async fn run_app(
     terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
     mut app: App,
+    mut color_reports: Option<&mut ColorReports>,
 ) -> anyhow::Result<()> {
     let mut channels = create_channels();
     let mut last_uptime_second = app.started_at.elapsed().as_secs();
     let mut mouse_capture_enabled = app.mouse_enabled;
 
+    // Ask the terminal for its background before the first draw.
+    if let Some(appearance) = color_reports
+        .as_deref_mut()
+        .and_then(|reports| reports.query_appearance(BACKGROUND_QUERY_TIMEOUT))
+    {
+        set_terminal_appearance(appearance);
+        app.redetect_auto_theme();
+    }
+
     let mut last_fetched_leaf: Option<String> = None;
 
+    // Debounce delay for auto-fetching details when selection changes.
+    // Details are only fetched after the user has stopped on an item for this duration.
+    let details_debounce = app.config.refresh.details_debounce();
+    let background_status_refresh = app.config.refresh.background_status_interval();
+    let auto_update = app.config.refresh.auto_update_interval();
+
+    app.load_command_history();
+    app.restore_snapshot();
+
     // Kick off all startup fetches in parallel (non-blocking)
     app.request_leaves(&channels.leaves_tx);
     app.request_casks(&channels.casks_tx);
     app.request_status(&channels.status_tx);
+    app.request_deprecated(&channels.deprecated_tx);
     app.request_sizes(&channels.sizes_tx);
 
     loop {
@@ -58,25 +76,38 @@ pub async fn run_app(
 
         process_pending_messages(&mut app, &mut channels);
 
-        if !app.pending_command
+        if let Some(interval) = background_status_refresh
+            && !app.pending_command
             && !app.pending_status
             && app
                 .last_status_check
-                .is_some_and(|last| last.elapsed() >= BACKGROUND_STATUS_REFRESH)
+                .is_some_and(|last| last.elapsed() >= interval)
         {
             app.request_status(&channels.status_tx);
         }
 
+        if let Some(interval) = auto_update
+            && app.auto_update_due(interval)
+        {
+            app.request_command(CommandKind::Update, &["update"], &channels.command_tx);
+        }
+
         // Debounced auto-fetch details for package search results
         // Skip if user is rapidly scrolling to reduce CPU load
         handle_auto_details(
             &mut app,
             &mut last_fetched_leaf,
             &channels.details_tx,
-            DETAILS_DEBOUNCE,
+            details_debounce,
         );
 
+        let (cols, rows) = size().unwrap_or((0, 0));
+        app.poll_log_tail(layout::pager_visible_line_capacity(Rect::new(
+            0, 0, cols, rows,
+        )));
+
         let tick_rate = if app.pending_command
+            || app.pager.as_ref().is_some_and(|pager| pager.tail.is_some())
             || app.pending_leaves
             || app.pending_casks
             || app.pending_sizes
@@ -92,13 +123,12 @@ pub async fn run_app(
             match event::read()? {
                 Event::Key(key) if key.kind == KeyEventKind::Press => {
                     if let Some(result) = handle_key_event(&mut app, key, &channels, max_offset) {
+                        app.save_snapshot();
                         return result;
                     }
                 }
-                Event::Mouse(mouse) => {
-                    if app.mouse_enabled {
-                        handle_mouse_event(&mut app, mouse, max_offset);
-                    }
+                Event::Mouse(mouse) if app.mouse_enabled => {
+                    handle_mouse_event(&mut app, mouse, max_offset);
                 }
                 Event::Resize(_, _) => {
                     app.needs_redraw = true;
@@ -107,6 +137,14 @@ pub async fn run_app(
             }
         }
 
+        if let Some(appearance) = color_reports
+            .as_deref()
+            .and_then(ColorReports::latest_change)
+        {
+            set_terminal_appearance(appearance);
+            app.redetect_auto_theme();
+        }
+
         sync_mouse_capture(terminal, &mut mouse_capture_enabled, app.mouse_enabled)?;
 
         app.on_tick();
@@ -1,19 +1,30 @@
+use std::path::Path;
 use std::time::Instant;
 
-use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use crossterm::event::{KeyCode, KeyEvent};
+use crossterm::terminal::size;
+use ratatui::layout::Rect;
 
 use crate::app::{
-    App, FocusedPanel, InputMode, PackageAction, PackageKind, PendingPackageAction,
-    PendingServiceAction, ServiceAction, StatusTab, ViewMode,
+    App, FocusedPanel, InputMode, KegAction, Migration, PackageAction, PackageKind,
+    PendingKegAction, PendingMigration, PendingPackageAction, PendingServiceAction, PendingZap,
+    ServiceAction, ServiceTarget, StatusTab, ViewMode,
 };
-use crate::brew::{CommandKind, DetailsLoad};
+use crate::brew::{
+    ArtifactKind, CommandKind, DetailsLoad, PackagePaths, REMOVE_KEG_SCRIPT, SWITCH_KEG_SCRIPT,
+    brew_command_args, copy_only_reason, is_keg_version, keg_script_args, stale_service_files,
+};
+use crate::keymap::Action;
+use crate::runtime::clipboard;
 use crate::runtime::messages::{RuntimeChannels, handle_focus_backtab};
-use crate::ui::help;
+use crate::runtime::opener::{self, OpenTarget, Opened};
+use crate::ui::util::format_size;
+use crate::ui::{help, layout, pager};
 
 enum HelpPopupAction {
     NotHandled,
     Handled,
-    Execute(KeyEvent),
+    Execute(Action),
 }
 
 pub fn handle_key_event(
@@ -24,6 +35,16 @@ pub fn handle_key_event(
 ) -> Option<anyhow::Result<()>> {
     app.needs_redraw = true;
 
+    if app.theme_picker.is_some() {
+        handle_theme_picker_input(app, key);
+        return None;
+    }
+
+    if app.pager.is_some() {
+        handle_pager_input(app, key);
+        return None;
+    }
+
     if handle_global_keymaps(app, key) {
         return None;
     }
@@ -31,10 +52,10 @@ pub fn handle_key_event(
     match handle_help_popup_input(app, key, help_max_offset) {
         HelpPopupAction::NotHandled => {}
         HelpPopupAction::Handled => return None,
-        HelpPopupAction::Execute(command_key) => {
+        HelpPopupAction::Execute(action) => {
             app.show_help_popup = false;
             app.help_scroll_offset = 0;
-            return handle_key_event(app, command_key, channels, help_max_offset);
+            return run_normal_action(app, action, channels);
         }
     }
 
@@ -43,6 +64,7 @@ pub fn handle_key_event(
         InputMode::SearchLeaves => handle_search_leaves_mode_key(app, key),
         InputMode::PackageSearch => handle_package_search_mode_key(app, key, channels),
         InputMode::PackageResults => handle_package_results_mode_key(app, key, channels),
+        InputMode::SearchHistory => handle_search_history_mode_key(app, key),
     }
 }
 
@@ -51,17 +73,12 @@ fn handle_global_keymaps(app: &mut App, key: KeyEvent) -> bool {
         return false;
     }
 
-    if key.code == KeyCode::Char('?') {
-        app.toggle_help();
-        return true;
-    }
-
-    if key.code == KeyCode::Char('i') && key.modifiers.contains(KeyModifiers::ALT) {
-        app.toggle_icons();
-        return true;
+    match app.keymap.action_for(key) {
+        Some(Action::Help) => app.toggle_help(),
+        Some(Action::ToggleIcons) => app.toggle_icons(),
+        _ => return false,
     }
-
-    false
+    true
 }
 
 fn handle_help_popup_input(
@@ -80,32 +97,99 @@ fn handle_help_popup_input(
         app.help_selected_command = app.help_selected_command.min(command_count - 1);
     }
 
-    match key.code {
-        KeyCode::Esc | KeyCode::Char('?') => {
+    if key.code == KeyCode::Enter {
+        if let Some(action) = help::help_selected_action(app).filter(|action| !action.is_pager()) {
+            return HelpPopupAction::Execute(action);
+        }
+        return HelpPopupAction::Handled;
+    }
+
+    match app.keymap.action_for(key) {
+        Some(Action::Cancel | Action::Help) => {
             app.show_help_popup = false;
             app.help_scroll_offset = 0;
         }
-        KeyCode::Down | KeyCode::Char('j') => {
-            if command_count > 0 {
-                app.help_selected_command = (app.help_selected_command + 1).min(command_count - 1);
-                sync_help_scroll_to_selection(app, help_max_offset);
-            }
+        Some(Action::MoveDown) if command_count > 0 => {
+            app.help_selected_command = (app.help_selected_command + 1).min(command_count - 1);
+            sync_help_scroll_to_selection(app, help_max_offset);
         }
-        KeyCode::Up | KeyCode::Char('k') => {
-            if command_count > 0 {
-                app.help_selected_command = app.help_selected_command.saturating_sub(1);
-                sync_help_scroll_to_selection(app, help_max_offset);
-            }
+        Some(Action::MoveUp) if command_count > 0 => {
+            app.help_selected_command = app.help_selected_command.saturating_sub(1);
+            sync_help_scroll_to_selection(app, help_max_offset);
         }
-        KeyCode::Enter => {
-            if let Some(command_key) = help::help_selected_command_key(app) {
-                return HelpPopupAction::Execute(command_key);
+        _ => {}
+    }
+
+    HelpPopupAction::Handled
+}
+
+fn handle_theme_picker_input(app: &mut App, key: KeyEvent) {
+    if key.code == KeyCode::Enter {
+        app.apply_theme_picker();
+        return;
+    }
+
+    match app.keymap.action_for(key) {
+        Some(Action::Cancel | Action::ThemePicker) => app.cancel_theme_picker(),
+        Some(Action::MoveDown) => app.theme_picker_move(1),
+        Some(Action::MoveUp) => app.theme_picker_move(-1),
+        _ => {}
+    }
+}
+
+fn handle_pager_input(app: &mut App, key: KeyEvent) {
+    let (cols, rows) = size().unwrap_or((0, 0));
+    let area = Rect::new(0, 0, cols, rows);
+    let max_offset = pager::pager_max_offset(app, area);
+    let page = layout::pager_visible_line_capacity(area).max(1) as isize;
+
+    if let Some(input) = app
+        .pager
+        .as_mut()
+        .and_then(|pager| pager.search_input.as_mut())
+    {
+        match key.code {
+            KeyCode::Enter => app.commit_pager_search(max_offset),
+            KeyCode::Esc => {
+                if let Some(pager) = app.pager.as_mut() {
+                    pager.search_input = None;
+                }
+            }
+            KeyCode::Backspace => {
+                input.pop();
             }
+            KeyCode::Char(c) => input.push(c),
+            _ => {}
         }
+        return;
+    }
+
+    match app.keymap.pager_action_for(key) {
+        Some(Action::PagerSearch) => app.start_pager_search(),
+        Some(Action::PagerNextMatch) => app.step_pager_match(true, max_offset),
+        Some(Action::PagerPrevMatch) => app.step_pager_match(false, max_offset),
+        Some(Action::PagerTop) => app.scroll_pager(isize::MIN, max_offset),
+        Some(Action::PagerBottom) => app.scroll_pager(isize::MAX, max_offset),
+        Some(Action::PagerSave) => app.save_pager_output(),
+        Some(Action::PagerPageDown) => app.scroll_pager(page, max_offset),
+        Some(Action::PagerPageUp) => app.scroll_pager(-page, max_offset),
+        Some(Action::Cancel | Action::Quit) => app.close_pager(),
+        Some(Action::MoveDown) => app.scroll_pager(1, max_offset),
+        Some(Action::MoveUp) => app.scroll_pager(-1, max_offset),
+        Some(Action::Yank) => yank_pager(app),
         _ => {}
     }
+}
 
-    HelpPopupAction::Handled
+fn yank_pager(app: &mut App) {
+    let text = app.pager.as_ref().map(|pager| {
+        let lines: Vec<&str> = pager.lines.iter().map(|line| line.text.as_str()).collect();
+        lines.join("\n")
+    });
+    if let Some(text) = text {
+        let label = format!("{} lines", text.lines().count());
+        copy_to_clipboard(app, &text, &label);
+    }
 }
 
 fn sync_help_scroll_to_selection(app: &mut App, help_max_offset: usize) {
@@ -134,9 +218,22 @@ fn handle_normal_mode_key(
     key: KeyEvent,
     channels: &RuntimeChannels,
 ) -> Option<anyhow::Result<()>> {
-    match key.code {
-        KeyCode::Char('q') => Some(Ok(())),
-        KeyCode::Esc => {
+    let action = app.keymap.action_for(key)?;
+    run_normal_action(app, action, channels)
+}
+
+fn run_normal_action(
+    app: &mut App,
+    action: Action,
+    channels: &RuntimeChannels,
+) -> Option<anyhow::Result<()>> {
+    if refuse_in_read_only(app, action) {
+        return None;
+    }
+
+    match action {
+        Action::Quit => Some(Ok(())),
+        Action::Cancel => {
             if has_pending_confirmation(app) {
                 clear_pending_confirmations(app);
                 set_status(app, "Canceled");
@@ -147,42 +244,59 @@ fn handle_normal_mode_key(
             }
             None
         }
-        KeyCode::Char('r') => {
+        Action::Refresh => {
             app.request_leaves(&channels.leaves_tx);
             app.request_casks(&channels.casks_tx);
             None
         }
-        KeyCode::Char('t') => {
-            app.cycle_theme();
+        Action::BrewUpdate => {
+            app.request_command(CommandKind::Update, &["update"], &channels.command_tx);
+            None
+        }
+        Action::OpenOutput => {
+            app.open_last_output();
+            None
+        }
+        Action::ThemePicker => {
+            app.open_theme_picker();
             None
         }
-        KeyCode::Char('m') => {
+        Action::ToggleMouse => {
             app.toggle_mouse();
             None
         }
-        KeyCode::Char('s') => {
+        Action::LoadSizes => {
             app.request_sizes(&channels.sizes_tx);
             None
         }
-        KeyCode::Char('h') => {
+        Action::SizeBaseline => {
+            app.cycle_size_baseline();
+            None
+        }
+        Action::StatusCheck => {
             app.request_status(&channels.status_tx);
             None
         }
-        KeyCode::Char('v') => {
+        Action::ToggleView => {
             app.view_mode = match app.view_mode {
                 ViewMode::Details => ViewMode::PackageResults,
                 ViewMode::PackageResults => ViewMode::Details,
             };
             None
         }
-        KeyCode::Char('/') => {
+        Action::FilterInstalled if is_history_focused(app) => {
+            app.input_mode = InputMode::SearchHistory;
+            set_status(app, "Search history");
+            None
+        }
+        Action::FilterInstalled => {
             app.input_mode = InputMode::SearchLeaves;
             app.leaves_query.clear();
             app.update_active_installed_filter();
             set_status(app, "Search");
             None
         }
-        KeyCode::Char('o') => {
+        Action::ToggleOutdated => {
             clear_pending_confirmations(app);
             app.toggle_outdated_filter();
             if app.leaves_outdated_only
@@ -194,7 +308,7 @@ fn handle_normal_mode_key(
             }
             None
         }
-        KeyCode::Char('C') => {
+        Action::ToggleListKind => {
             clear_pending_confirmations(app);
             app.toggle_installed_kind();
             if app.is_cask_mode() && app.casks.is_empty() && !app.pending_casks {
@@ -203,14 +317,14 @@ fn handle_normal_mode_key(
             app.update_active_installed_filter();
             None
         }
-        KeyCode::Char('f') => {
+        Action::FindPackages => {
             app.input_mode = InputMode::PackageSearch;
             app.package_query.clear();
             app.clear_package_results();
             set_status(app, "Search packages");
             None
         }
-        KeyCode::Char('i') => {
+        Action::Install => {
             if app.focus_panel == FocusedPanel::Leaves {
                 let Some(pkg) = app.selected_installed_package().map(str::to_string) else {
                     set_status(
@@ -234,7 +348,7 @@ fn handle_normal_mode_key(
             }
             None
         }
-        KeyCode::Char('u') => {
+        Action::Uninstall => {
             if app.focus_panel == FocusedPanel::Leaves {
                 let Some(pkg) = app.selected_installed_package().map(str::to_string) else {
                     set_status(
@@ -261,7 +375,7 @@ fn handle_normal_mode_key(
             }
             None
         }
-        KeyCode::Char('U') => {
+        Action::Upgrade => {
             if app.focus_panel == FocusedPanel::Status && app.status_tab == StatusTab::Outdated {
                 run_or_confirm_upgrade_all_outdated(app, channels);
             } else if app.focus_panel == FocusedPanel::Leaves {
@@ -287,7 +401,7 @@ fn handle_normal_mode_key(
             }
             None
         }
-        KeyCode::Char('c') => {
+        Action::Cleanup => {
             app.request_command(
                 CommandKind::Cleanup,
                 &["cleanup", "-s"],
@@ -295,7 +409,7 @@ fn handle_normal_mode_key(
             );
             None
         }
-        KeyCode::Char('a') => {
+        Action::Autoremove => {
             app.request_command(
                 CommandKind::Autoremove,
                 &["autoremove"],
@@ -303,7 +417,7 @@ fn handle_normal_mode_key(
             );
             None
         }
-        KeyCode::Char('b') => {
+        Action::BundleDump => {
             app.request_command(
                 CommandKind::BundleDump,
                 &["bundle", "dump", "--force"],
@@ -311,37 +425,97 @@ fn handle_normal_mode_key(
             );
             None
         }
-        KeyCode::Char('S') => {
-            if app.focus_panel == FocusedPanel::Status && app.status_tab == StatusTab::Services {
-                let Some(service) = app.selected_service().map(str::to_string) else {
-                    set_status(app, "No service selected");
-                    return None;
-                };
-                run_or_confirm_service_action(app, channels, ServiceAction::Start, service);
+        Action::ToggleLink => {
+            let Some((formula, details)) = app.keg_formula() else {
+                set_status(app, "Select an installed formula and load its details");
+                return None;
+            };
+            let action = if details.linked_keg.is_some() {
+                PackageAction::Unlink
+            } else {
+                PackageAction::Link
+            };
+            let formula = formula.to_string();
+            run_or_confirm_package_action(app, channels, action, PackageKind::Formula, formula);
+            None
+        }
+        Action::LinkPreview => {
+            let Some((formula, details)) = app.keg_formula() else {
+                set_status(app, "Select an installed formula and load its details");
+                return None;
+            };
+            let formula = formula.to_string();
+            let mut args = vec!["link", "--overwrite", "--dry-run"];
+            if details.keg_only {
+                args.push("--force");
             }
+            args.push(&formula);
+            clear_pending_confirmations(app);
+            app.request_command(CommandKind::LinkPreview, &args, &channels.command_tx);
+            set_status(app, format!("Checking link conflicts for {formula}..."));
             None
         }
-        KeyCode::Char('X') => {
-            if app.focus_panel == FocusedPanel::Status && app.status_tab == StatusTab::Services {
-                let Some(service) = app.selected_service().map(str::to_string) else {
-                    set_status(app, "No service selected");
-                    return None;
-                };
-                run_or_confirm_service_action(app, channels, ServiceAction::Stop, service);
+        Action::Migrate => {
+            run_or_confirm_migration(app, channels);
+            None
+        }
+        Action::Zap => {
+            run_or_confirm_zap(app, channels);
+            None
+        }
+        Action::OpenHomepage => {
+            open_homepage(app);
+            None
+        }
+        Action::Reveal => {
+            reveal_package_path(app, false);
+            None
+        }
+        Action::RevealOpt => {
+            reveal_package_path(app, true);
+            None
+        }
+        Action::SwitchKeg => {
+            run_or_confirm_keg_action(app, channels, KegAction::Switch);
+            None
+        }
+        Action::RemoveKeg => {
+            run_or_confirm_keg_action(app, channels, KegAction::Remove);
+            None
+        }
+        Action::ServiceStart => {
+            service_action_key(app, channels, ServiceAction::Start);
+            None
+        }
+        Action::ServiceStop => {
+            service_action_key(app, channels, ServiceAction::Stop);
+            None
+        }
+        Action::ServiceRestart => {
+            service_action_key(app, channels, ServiceAction::Restart);
+            None
+        }
+        Action::ServiceRun => {
+            service_action_key(app, channels, ServiceAction::Run);
+            None
+        }
+        Action::ServiceKill => {
+            service_action_key(app, channels, ServiceAction::Kill);
+            None
+        }
+        Action::ServiceAll => {
+            if is_services_focused(app) {
+                choose_all_services(app);
             }
             None
         }
-        KeyCode::Char('R') => {
-            if app.focus_panel == FocusedPanel::Status && app.status_tab == StatusTab::Services {
-                let Some(service) = app.selected_service().map(str::to_string) else {
-                    set_status(app, "No service selected");
-                    return None;
-                };
-                run_or_confirm_service_action(app, channels, ServiceAction::Restart, service);
+        Action::ServiceCleanup => {
+            if is_services_focused(app) {
+                run_or_confirm_services_cleanup(app, channels);
             }
             None
         }
-        KeyCode::Char('I') => {
+        Action::ServiceInfo => {
             if app.focus_panel == FocusedPanel::Status && app.status_tab == StatusTab::Services {
                 let Some(service) = app.selected_service().map(str::to_string) else {
                     set_status(app, "No service selected");
@@ -357,29 +531,48 @@ fn handle_normal_mode_key(
             }
             None
         }
-        KeyCode::Char('F') => {
+        Action::ServiceLogs => {
+            if app.focus_panel == FocusedPanel::Status && app.status_tab == StatusTab::Services {
+                let Some(service) = app.selected_service_entry().cloned() else {
+                    set_status(app, "No service selected");
+                    return None;
+                };
+                clear_pending_confirmations(app);
+                app.request_service_logs(&service, &channels.logs_tx);
+            }
+            None
+        }
+        Action::FilterFailedServices if is_history_focused(app) => {
+            app.cycle_history_outcome_filter();
+            None
+        }
+        Action::FilterFailedServices => {
             if app.focus_panel == FocusedPanel::Status && app.status_tab == StatusTab::Services {
                 clear_pending_confirmations(app);
                 app.toggle_services_failed_filter();
             }
             None
         }
-        KeyCode::Char('A') => {
+        Action::FilterAutostartServices => {
             if app.focus_panel == FocusedPanel::Status && app.status_tab == StatusTab::Services {
                 clear_pending_confirmations(app);
                 app.toggle_services_autostart_filter();
             }
             None
         }
-        KeyCode::Char('K') => {
+        Action::CycleServiceKind if is_history_focused(app) => {
+            app.cycle_history_kind_filter();
+            None
+        }
+        Action::CycleServiceKind => {
             if app.focus_panel == FocusedPanel::Status && app.status_tab == StatusTab::Services {
                 clear_pending_confirmations(app);
                 app.cycle_services_kind_filter();
             }
             None
         }
-        KeyCode::Char('P') => {
-            if app.pending_self_update {
+        Action::SelfUpdate => {
+            if app.pending_self_update || !app.config.confirm.requires_confirmation(false) {
                 app.request_command(
                     CommandKind::SelfUpdate,
                     &["install", "brewery", "--locked", "--force"],
@@ -390,18 +583,74 @@ fn handle_normal_mode_key(
             } else {
                 clear_pending_confirmations(app);
                 app.pending_self_update = true;
-                set_status(
-                    app,
-                    "Update Brewery via `cargo install brewery --locked --force`? [P] confirm, [Esc] cancel",
+                let status = format!(
+                    "Update Brewery via `cargo install brewery --locked --force`? {}",
+                    confirm_hint(app, Action::SelfUpdate)
                 );
+                set_status(app, status);
             }
             None
         }
-        KeyCode::Enter => {
+        Action::LoadDetails if is_issues_focused(app) => {
+            if app.selected_issue().is_none()
+                && let Some(entry) = app.selected_deprecated_package()
+            {
+                let pkg = entry.name.clone();
+                let status = format!(
+                    "{pkg}: {}, [{}] migrate",
+                    entry.deprecation.summary(),
+                    app.key_hint(Action::Migrate)
+                );
+                app.request_details_for(&pkg, DetailsLoad::Basic, &channels.details_tx);
+                app.focus_panel = FocusedPanel::Details;
+                app.details_scroll_offset = 0;
+                set_status(app, status);
+                return None;
+            }
+            let Some(issue) = app.selected_issue() else {
+                set_status(app, "No doctor issues");
+                return None;
+            };
+            let status = if issue.commands.is_empty() {
+                "Issue details".to_string()
+            } else {
+                format!(
+                    "{} suggested command(s): [{}] copy, [{}] run",
+                    issue.commands.len(),
+                    app.key_hint(Action::Yank),
+                    app.key_hint(Action::RunIssueCommand)
+                )
+            };
+            app.focus_panel = FocusedPanel::Details;
+            app.details_scroll_offset = 0;
+            set_status(app, status);
+            None
+        }
+        Action::Yank => {
+            match yank_target(app) {
+                Ok((text, label)) => copy_to_clipboard(app, &text, &label),
+                Err(status) => set_status(app, status),
+            }
+            None
+        }
+        Action::YankInstall => {
+            yank_install_line(app);
+            None
+        }
+        Action::RunIssueCommand => {
+            let command = selected_issue_command(app)?;
+            run_or_confirm_issue_command(app, channels, command);
+            None
+        }
+        Action::LoadDetails if is_history_focused(app) => {
+            app.open_history_transcript();
+            None
+        }
+        Action::LoadDetails => {
             app.request_details(DetailsLoad::Basic, &channels.details_tx);
             None
         }
-        KeyCode::Char('d') => {
+        Action::LoadDeps => {
             if app.is_cask_mode() {
                 set_status(app, "Deps/uses are formula-only");
                 return None;
@@ -409,15 +658,15 @@ fn handle_normal_mode_key(
             app.request_details(DetailsLoad::Full, &channels.details_tx);
             None
         }
-        KeyCode::Tab => {
+        Action::NextPanel => {
             app.cycle_focus();
             None
         }
-        KeyCode::BackTab => {
+        Action::PrevPanel => {
             handle_focus_backtab(app);
             None
         }
-        KeyCode::Up | KeyCode::Char('k') => {
+        Action::MoveUp => {
             app.scroll_focused_up();
             if app.focus_panel == FocusedPanel::Leaves {
                 clear_pending_confirmations(app);
@@ -425,7 +674,7 @@ fn handle_normal_mode_key(
             }
             None
         }
-        KeyCode::Down | KeyCode::Char('j') => {
+        Action::MoveDown => {
             app.scroll_focused_down();
             if app.focus_panel == FocusedPanel::Leaves {
                 clear_pending_confirmations(app);
@@ -433,15 +682,31 @@ fn handle_normal_mode_key(
             }
             None
         }
-        KeyCode::Left | KeyCode::Char('l') if app.focus_panel == FocusedPanel::Status => {
+        Action::PrevStatusTab if app.focus_panel == FocusedPanel::Status => {
             app.status_tab_prev();
             None
         }
-        KeyCode::Right | KeyCode::Char(';') if app.focus_panel == FocusedPanel::Status => {
+        Action::NextStatusTab if app.focus_panel == FocusedPanel::Status => {
             app.status_tab_next();
             None
         }
-        _ => None,
+        Action::Help => {
+            app.toggle_help();
+            None
+        }
+        Action::ToggleIcons => {
+            app.toggle_icons();
+            None
+        }
+        Action::PrevStatusTab | Action::NextStatusTab => None,
+        Action::PagerPageDown
+        | Action::PagerPageUp
+        | Action::PagerTop
+        | Action::PagerBottom
+        | Action::PagerSearch
+        | Action::PagerNextMatch
+        | Action::PagerPrevMatch
+        | Action::PagerSave => None,
     }
 }
 
@@ -480,6 +745,33 @@ fn handle_search_leaves_mode_key(app: &mut App, key: KeyEvent) -> Option<anyhow:
     None
 }
 
+fn handle_search_history_mode_key(app: &mut App, key: KeyEvent) -> Option<anyhow::Result<()>> {
+    match key.code {
+        KeyCode::Enter => {
+            app.input_mode = InputMode::Normal;
+            set_status(app, "Ready");
+        }
+        KeyCode::Esc => {
+            app.history_query.clear();
+            app.reconcile_history_selection();
+            app.input_mode = InputMode::Normal;
+            set_status(app, "Ready");
+        }
+        KeyCode::Up => app.select_prev_history(),
+        KeyCode::Down => app.select_next_history(),
+        KeyCode::Backspace => {
+            app.history_query.pop();
+            app.reconcile_history_selection();
+        }
+        KeyCode::Char(ch) => {
+            app.history_query.push(ch);
+            app.reconcile_history_selection();
+        }
+        _ => {}
+    }
+    None
+}
+
 fn handle_package_search_mode_key(
     app: &mut App,
     key: KeyEvent,
@@ -533,8 +825,13 @@ fn handle_package_results_mode_key(
     key: KeyEvent,
     channels: &RuntimeChannels,
 ) -> Option<anyhow::Result<()>> {
-    match key.code {
-        KeyCode::Esc => {
+    let action = app.keymap.action_for(key)?;
+    if refuse_in_read_only(app, action) {
+        return None;
+    }
+
+    match action {
+        Action::Cancel => {
             if has_pending_confirmation(app) {
                 clear_pending_confirmations(app);
                 set_status(app, "Canceled");
@@ -545,24 +842,24 @@ fn handle_package_results_mode_key(
                 set_status(app, "Ready");
             }
         }
-        KeyCode::Char('f') => {
+        Action::FindPackages => {
             app.input_mode = InputMode::PackageSearch;
             app.package_query.clear();
             app.clear_package_results();
             clear_pending_confirmations(app);
             set_status(app, "Search packages");
         }
-        KeyCode::Up | KeyCode::Char('k') => {
+        Action::MoveUp => {
             app.select_prev_result();
             clear_pending_confirmations(app);
             app.on_selection_change();
         }
-        KeyCode::Down | KeyCode::Char('j') => {
+        Action::MoveDown => {
             app.select_next_result();
             clear_pending_confirmations(app);
             app.on_selection_change();
         }
-        KeyCode::Char('i') => {
+        Action::Install => {
             let Some(pkg) = app.selected_package_result().map(str::to_string) else {
                 set_status(app, "No result selected");
                 return None;
@@ -575,7 +872,7 @@ fn handle_package_results_mode_key(
                 pkg,
             );
         }
-        KeyCode::Char('u') => {
+        Action::Uninstall => {
             let Some(pkg) = app.selected_package_result().map(str::to_string) else {
                 set_status(app, "No result selected");
                 return None;
@@ -588,11 +885,28 @@ fn handle_package_results_mode_key(
                 pkg,
             );
         }
+        Action::OpenHomepage => open_homepage(app),
+        Action::Yank => match app.selected_package_result().map(str::to_string) {
+            Some(pkg) => copy_to_clipboard(app, &pkg, &format!("`{pkg}`")),
+            None => set_status(app, "No result selected"),
+        },
+        Action::YankInstall => yank_install_line(app),
         _ => {}
     }
     None
 }
 
+fn refuse_in_read_only(app: &mut App, action: Action) -> bool {
+    if !(app.read_only && action.modifies_system()) {
+        return false;
+    }
+    set_status(
+        app,
+        format!("Read-only session: {} is disabled", action.spec().name),
+    );
+    true
+}
+
 fn run_or_confirm_package_action(
     app: &mut App,
     channels: &RuntimeChannels,
@@ -600,25 +914,271 @@ fn run_or_confirm_package_action(
     kind: PackageKind,
     pkg: String,
 ) {
-    let (command_kind, verb_ing, verb_title, confirm_key) = package_action_labels(action);
+    let (command_kind, verb_ing, verb_title, confirm_action) = package_action_labels(action);
     let noun = package_kind_noun(kind);
 
-    if matches!(app.pending_package_action.as_ref(), Some(pending) if pending.action == action && pending.kind == kind && pending.pkg == pkg)
-    {
-        let args = package_action_args(action, kind, &pkg);
+    let keg_only = action == PackageAction::Link
+        && app
+            .details_cache
+            .peek(&pkg)
+            .is_some_and(|details| details.keg_only);
+    let confirmed = matches!(app.pending_package_action.as_ref(), Some(pending) if pending.action == action && pending.kind == kind && pending.pkg == pkg);
+    let destructive = action == PackageAction::Uninstall;
+    // Force-linking keg-only software always asks, whatever the policy.
+    if confirmed || (!keg_only && !app.config.confirm.requires_confirmation(destructive)) {
+        let mut args = package_action_args(action, kind, &pkg);
+        if keg_only {
+            args.insert(1, "--force");
+        }
         app.request_command(command_kind, &args, &channels.command_tx);
         clear_pending_confirmations(app);
         set_status(app, format!("{verb_ing} {noun}..."));
         return;
     }
 
-    let confirmation_status =
-        format!("{verb_title} {noun} {pkg}? [{confirm_key}] confirm, [Esc] cancel");
+    let reason = app
+        .details_cache
+        .peek(&pkg)
+        .and_then(|details| details.keg_only_reason.clone())
+        .filter(|_| keg_only)
+        .map(|reason| format!(" It is keg-only ({reason}) and will be linked with --force."))
+        .unwrap_or_default();
+    let confirmation_status = format!(
+        "{verb_title} {noun} {pkg}?{reason} {}",
+        confirm_hint(app, confirm_action)
+    );
     app.pending_upgrade_all_outdated = false;
     app.pending_package_action = Some(PendingPackageAction { action, kind, pkg });
     set_status(app, confirmation_status);
 }
 
+/// First press installs the replacement; once it is in, the next press
+/// uninstalls the deprecated package.
+fn run_or_confirm_migration(app: &mut App, channels: &RuntimeChannels) {
+    if let Some(PendingMigration::UninstallOld(migration)) = app.pending_migration.clone() {
+        let args = package_action_args(
+            PackageAction::Uninstall,
+            migration.from_kind,
+            &migration.from,
+        );
+        app.request_command(CommandKind::Uninstall, &args, &channels.command_tx);
+        clear_pending_confirmations(app);
+        set_status(app, format!("Uninstalling {}...", migration.from));
+        return;
+    }
+
+    // The Issues tab selection wins over the package list.
+    let (pkg, kind) = if app.status_tab == StatusTab::Issues
+        && app.focus_panel != FocusedPanel::Leaves
+        && let Some(entry) = app.selected_deprecated_package()
+    {
+        let kind = if entry.cask {
+            PackageKind::Cask
+        } else {
+            PackageKind::Formula
+        };
+        (entry.name.clone(), kind)
+    } else {
+        let Some(pkg) = app.selected_installed_package().map(str::to_string) else {
+            set_status(
+                app,
+                format!("No {} selected", app.active_kind_label_singular()),
+            );
+            return;
+        };
+        (pkg, app.active_package_kind)
+    };
+    let Some(deprecation) = app.deprecation_for(&pkg, kind) else {
+        set_status(app, format!("{pkg} is not deprecated or disabled"));
+        return;
+    };
+    let Some(replacement) = deprecation.replacement.clone() else {
+        set_status(
+            app,
+            format!(
+                "{pkg} is {} with no suggested replacement",
+                deprecation.state.label()
+            ),
+        );
+        return;
+    };
+    let migration = Migration {
+        from: pkg,
+        from_kind: kind,
+        to: replacement,
+        to_kind: if deprecation.replacement_is_cask {
+            PackageKind::Cask
+        } else {
+            PackageKind::Formula
+        },
+    };
+
+    let confirmed = matches!(
+        app.pending_migration.as_ref(),
+        Some(PendingMigration::InstallReplacement(pending)) if *pending == migration
+    );
+    if confirmed || !app.config.confirm.requires_confirmation(false) {
+        let args = package_action_args(PackageAction::Install, migration.to_kind, &migration.to);
+        app.request_command(CommandKind::Install, &args, &channels.command_tx);
+        clear_pending_confirmations(app);
+        set_status(app, format!("Installing replacement {}...", migration.to));
+        app.active_migration = Some(migration);
+        return;
+    }
+
+    let status = format!(
+        "Install {} {} to replace {}? {}",
+        package_kind_noun(migration.to_kind),
+        migration.to,
+        migration.from,
+        confirm_hint(app, Action::Migrate)
+    );
+    clear_pending_confirmations(app);
+    app.pending_migration = Some(PendingMigration::InstallReplacement(migration));
+    set_status(app, status);
+}
+
+/// Zap always asks twice, whatever the confirmation policy: the first press
+/// measures and previews the zap paths, the second arms it, the third runs it.
+fn run_or_confirm_zap(app: &mut App, channels: &RuntimeChannels) {
+    let Some(cask) = app
+        .selected_installed_package()
+        .filter(|_| app.active_package_kind == PackageKind::Cask)
+        .map(str::to_string)
+    else {
+        set_status(app, "Select an installed cask to zap");
+        return;
+    };
+    let zap_key = app.key_hint(Action::Zap);
+    let cancel_key = app.key_hint(Action::Cancel);
+
+    match app.pending_zap.clone() {
+        Some(PendingZap::Confirm(pending)) if pending == cask => {
+            app.request_command(
+                CommandKind::Uninstall,
+                &["uninstall", "--zap", "--cask", &cask],
+                &channels.command_tx,
+            );
+            clear_pending_confirmations(app);
+            set_status(app, format!("Zapping cask {cask}..."));
+        }
+        Some(PendingZap::Preview(pending)) if pending == cask => {
+            let Some(info) = app
+                .details_cache
+                .peek(&cask)
+                .and_then(|details| details.cask.as_ref())
+                .filter(|info| info.zap_measured)
+            else {
+                set_status(app, format!("Still measuring zap paths for {cask}..."));
+                return;
+            };
+            let paths = info.zap_present().count();
+            let size = format_size(info.zap_size_kb());
+            app.pending_zap = Some(PendingZap::Confirm(cask.clone()));
+            set_status(
+                app,
+                format!(
+                    "Zap permanently deletes {cask} and {paths} app data paths ({size}); this cannot be undone. Press [{zap_key}] once more to zap, [{cancel_key}] cancel"
+                ),
+            );
+        }
+        _ => {
+            clear_pending_confirmations(app);
+            app.pending_zap = Some(PendingZap::Preview(cask.clone()));
+            app.view_mode = ViewMode::Details;
+            app.details_scroll_offset = 0;
+            app.request_details_forced(&cask, DetailsLoad::Full, &channels.details_tx);
+            set_status(
+                app,
+                format!(
+                    "Zap preview for {cask} in Details: [{zap_key}] continue, [{cancel_key}] cancel"
+                ),
+            );
+        }
+    }
+}
+
+fn open_homepage(app: &mut App) {
+    let Some(pkg) = app.selected_package_name().map(str::to_string) else {
+        set_status(app, "No package selected");
+        return;
+    };
+    let Some(homepage) = app
+        .details_cache
+        .peek(&pkg)
+        .and_then(|details| details.homepage.clone())
+    else {
+        set_status(app, format!("No homepage loaded for {pkg}"));
+        return;
+    };
+    open_with_opener(app, &OpenTarget::Url(&homepage), "homepage");
+}
+
+/// Reveals the highlighted keg or the cask's app; `opt` picks the opt link or
+/// the Caskroom folder instead.
+fn reveal_package_path(app: &mut App, opt: bool) {
+    let Some(pkg) = app.selected_installed_package().map(str::to_string) else {
+        set_status(app, "No installed package selected");
+        return;
+    };
+    let keg = app.selected_keg().map(str::to_string);
+    let Some(details) = app.details_cache.peek(&pkg) else {
+        set_status(app, format!("Load details for {pkg} first"));
+        return;
+    };
+    if details.installed.is_empty() {
+        set_status(app, format!("{pkg} is not installed"));
+        return;
+    }
+    let app_path = details
+        .cask
+        .as_ref()
+        .and_then(|cask| {
+            cask.artifacts.iter().find(|artifact| {
+                matches!(artifact.kind, ArtifactKind::App | ArtifactKind::Suite)
+                    && artifact.present == Some(true)
+            })
+        })
+        .and_then(|artifact| artifact.path.clone());
+    let target = match (details.paths.as_ref(), opt) {
+        (Some(PackagePaths::Formula { opt: path, .. }), true) => Some((path.clone(), "opt link")),
+        (Some(paths @ PackagePaths::Formula { .. }), false) => keg
+            .or_else(|| details.linked_keg.clone())
+            .or_else(|| details.installed.last().cloned())
+            .and_then(|version| paths.keg(&version))
+            .map(|path| (path, "keg")),
+        (Some(PackagePaths::Cask { caskroom }), true) => {
+            Some((caskroom.clone(), "Caskroom folder"))
+        }
+        (Some(PackagePaths::Cask { caskroom }), false) => Some(match app_path {
+            Some(path) => (path, "app"),
+            None => (caskroom.clone(), "Caskroom folder"),
+        }),
+        (None, _) => None,
+    };
+    let Some((path, what)) = target else {
+        set_status(app, format!("No path known for {pkg}; reload its details"));
+        return;
+    };
+    open_with_opener(app, &OpenTarget::Reveal(Path::new(&path)), what);
+}
+
+fn open_with_opener(app: &mut App, target: &OpenTarget, what: &str) {
+    let status = match opener::open(&app.config.opener, target) {
+        Ok(Opened::Launched(program)) => format!("Opened {what} with {program}"),
+        Ok(Opened::Printed) => {
+            let mut label = what.to_string();
+            if let Some(first) = label.get_mut(..1) {
+                first.make_ascii_uppercase();
+            }
+            format!("{label}: {}", target.text())
+        }
+        Ok(Opened::Copied) => format!("Copied {what} {}", target.text()),
+        Err(err) => format!("Could not open {what}: {err}"),
+    };
+    set_status(app, status);
+}
+
 fn run_or_confirm_upgrade_all_outdated(app: &mut App, channels: &RuntimeChannels) {
     let outdated = app
         .system_status
@@ -629,7 +1189,7 @@ fn run_or_confirm_upgrade_all_outdated(app: &mut App, channels: &RuntimeChannels
         return;
     }
 
-    if app.pending_upgrade_all_outdated {
+    if app.pending_upgrade_all_outdated || !app.config.confirm.requires_confirmation(false) {
         app.request_command(CommandKind::UpgradeAll, &["upgrade"], &channels.command_tx);
         clear_pending_confirmations(app);
         set_status(app, format!("Upgrading {outdated} outdated packages..."));
@@ -638,42 +1198,423 @@ fn run_or_confirm_upgrade_all_outdated(app: &mut App, channels: &RuntimeChannels
 
     app.pending_package_action = None;
     app.pending_upgrade_all_outdated = true;
-    set_status(
-        app,
-        format!("Upgrade all {outdated} outdated packages? [U] confirm, [Esc] cancel"),
+    let status = format!(
+        "Upgrade all {outdated} outdated packages? {}",
+        confirm_hint(app, Action::Upgrade)
     );
+    set_status(app, status);
+}
+
+/// Resolves the target for a service key: every service after `--all` was
+/// chosen (or while confirming a bulk action), otherwise the selected one.
+fn service_action_key(app: &mut App, channels: &RuntimeChannels, action: ServiceAction) {
+    if !is_services_focused(app) {
+        return;
+    }
+    let confirming_all = matches!(
+        app.pending_service_action.as_ref(),
+        Some(pending) if pending.action == action && pending.target == ServiceTarget::All
+    );
+    let target = if app.pending_service_all || confirming_all {
+        ServiceTarget::All
+    } else {
+        let Some(service) = app.selected_service().map(str::to_string) else {
+            set_status(app, "No service selected");
+            return;
+        };
+        ServiceTarget::One(service)
+    };
+    run_or_confirm_service_action(app, channels, action, target);
 }
 
 fn run_or_confirm_service_action(
     app: &mut App,
     channels: &RuntimeChannels,
     action: ServiceAction,
-    service: String,
+    target: ServiceTarget,
 ) {
-    let (command_kind, verb_ing, verb_title, confirm_key) = service_action_labels(action);
+    let (command_kind, verb_ing, verb_title, confirm_action) = service_action_labels(action);
 
-    if matches!(app.pending_service_action.as_ref(), Some(pending) if pending.action == action && pending.service == service)
-    {
-        let args = service_action_args(action, &service);
+    let confirmed = matches!(app.pending_service_action.as_ref(), Some(pending) if pending.action == action && pending.target == target);
+    let destructive = matches!(
+        action,
+        ServiceAction::Stop | ServiceAction::Restart | ServiceAction::Kill
+    );
+    // Bulk actions always show which services they touch before running.
+    let bulk = target == ServiceTarget::All;
+    if confirmed || (!bulk && !app.config.confirm.requires_confirmation(destructive)) {
+        let args = service_action_args(action, &target);
         app.request_command(command_kind, &args, &channels.command_tx);
         clear_pending_confirmations(app);
-        set_status(app, format!("{verb_ing} service..."));
+        let status = match target {
+            ServiceTarget::One(_) => format!("{verb_ing} service..."),
+            ServiceTarget::All => format!("{verb_ing} all services..."),
+        };
+        set_status(app, status);
         return;
     }
 
-    let confirmation_status =
-        format!("{verb_title} service {service}? [{confirm_key}] confirm, [Esc] cancel");
-    app.pending_package_action = None;
-    app.pending_upgrade_all_outdated = false;
-    app.pending_service_action = Some(PendingServiceAction { action, service });
+    let note = service_action_note(action);
+    let confirmation_status = match &target {
+        ServiceTarget::One(service) => format!(
+            "{verb_title} service {service}{note}? {}",
+            confirm_hint(app, confirm_action)
+        ),
+        ServiceTarget::All => {
+            let affected = app.services_affected_by(action);
+            if affected.is_empty() {
+                clear_pending_confirmations(app);
+                set_status(app, format!("No services to {}", verb_title.to_lowercase()));
+                return;
+            }
+            format!(
+                "{verb_title} {} with --all ({}){note}? {}",
+                plural(affected.len(), "service"),
+                summarize_names(&affected),
+                confirm_hint(app, confirm_action)
+            )
+        }
+    };
+    clear_pending_confirmations(app);
+    app.pending_service_action = Some(PendingServiceAction { action, target });
     set_status(app, confirmation_status);
 }
 
-fn package_action_labels(action: PackageAction) -> (CommandKind, &'static str, &'static str, char) {
+/// Arms `--all` for the next service action key.
+fn choose_all_services(app: &mut App) {
+    let count = app
+        .system_status
+        .as_ref()
+        .map_or(0, |status| status.services.len());
+    if count == 0 {
+        set_status(app, "No services");
+        return;
+    }
+    clear_pending_confirmations(app);
+    app.pending_service_all = true;
+    let status = format!(
+        "All {}: [{}] start, [{}] stop, [{}] restart, [{}] run, [{}] kill, [{}] cancel",
+        plural(count, "service"),
+        app.key_hint(Action::ServiceStart),
+        app.key_hint(Action::ServiceStop),
+        app.key_hint(Action::ServiceRestart),
+        app.key_hint(Action::ServiceRun),
+        app.key_hint(Action::ServiceKill),
+        app.key_hint(Action::Cancel)
+    );
+    set_status(app, status);
+}
+
+fn run_or_confirm_services_cleanup(app: &mut App, channels: &RuntimeChannels) {
+    if app.pending_services_cleanup || !app.config.confirm.requires_confirmation(true) {
+        app.request_command(
+            CommandKind::ServiceCleanup,
+            &["services", "cleanup"],
+            &channels.command_tx,
+        );
+        clear_pending_confirmations(app);
+        set_status(app, "Cleaning up unused services...");
+        return;
+    }
+
+    let services = app
+        .system_status
+        .as_ref()
+        .map(|status| status.services.as_slice())
+        .unwrap_or_default();
+    let stale: Vec<String> = stale_service_files(services)
+        .iter()
+        .filter_map(|path| path.file_name())
+        .map(|name| name.to_string_lossy().to_string())
+        .collect();
+    let found = if stale.is_empty() {
+        "no stale service files found".to_string()
+    } else {
+        format!(
+            "removes {} ({})",
+            plural(stale.len(), "unused service file"),
+            summarize_names(&stale)
+        )
+    };
+    let status = format!(
+        "Run brew services cleanup? It kills orphaned services and {found}. {}",
+        confirm_hint(app, Action::ServiceCleanup)
+    );
+    clear_pending_confirmations(app);
+    app.pending_services_cleanup = true;
+    set_status(app, status);
+}
+
+fn service_action_note(action: ServiceAction) -> &'static str {
     match action {
-        PackageAction::Install => (CommandKind::Install, "Installing", "Install", 'i'),
-        PackageAction::Uninstall => (CommandKind::Uninstall, "Uninstalling", "Uninstall", 'u'),
-        PackageAction::Upgrade => (CommandKind::Upgrade, "Upgrading", "Upgrade", 'U'),
+        ServiceAction::Run => " without registering it at login",
+        ServiceAction::Kill => " now, keeping it registered at login",
+        ServiceAction::Start | ServiceAction::Stop | ServiceAction::Restart => "",
+    }
+}
+
+/// Up to five names, then a count of the rest.
+fn summarize_names(names: &[String]) -> String {
+    const SHOWN: usize = 5;
+    let mut summary = names
+        .iter()
+        .take(SHOWN)
+        .cloned()
+        .collect::<Vec<_>>()
+        .join(", ");
+    if names.len() > SHOWN {
+        summary.push_str(&format!(" and {} more", names.len() - SHOWN));
+    }
+    summary
+}
+
+fn plural(count: usize, noun: &str) -> String {
+    if count == 1 {
+        format!("1 {noun}")
+    } else {
+        format!("{count} {noun}s")
+    }
+}
+
+fn run_or_confirm_keg_action(app: &mut App, channels: &RuntimeChannels, action: KegAction) {
+    let Some((formula, details)) = app.keg_formula() else {
+        set_status(app, "Select an installed formula and load its details");
+        return;
+    };
+    let formula = formula.to_string();
+    let installed = details.installed.len();
+    let linked = details.linked_keg.clone();
+    let opt_version = details.paths.as_ref().and_then(PackagePaths::opt_version);
+    let Some(version) = app.selected_keg().map(str::to_string) else {
+        set_status(app, "No installed version highlighted");
+        return;
+    };
+    if !is_keg_version(&version) {
+        set_status(app, format!("Refusing unexpected keg version '{version}'"));
+        return;
+    }
+
+    let is_linked = linked.as_deref() == Some(version.as_str());
+    match action {
+        KegAction::Switch if is_linked => {
+            set_status(app, format!("{formula} {version} is already linked"));
+            return;
+        }
+        KegAction::Remove
+            if is_linked || opt_version.as_deref() == Some(version.as_str()) || installed < 2 =>
+        {
+            set_status(
+                app,
+                format!(
+                    "{formula} {version} is in use; switch to another version or [{}] uninstall",
+                    app.key_hint(Action::Uninstall)
+                ),
+            );
+            return;
+        }
+        _ => {}
+    }
+
+    let (command_kind, script, verb_ing, verb_title, confirm_action) = match action {
+        KegAction::Switch => (
+            CommandKind::SwitchKeg,
+            SWITCH_KEG_SCRIPT,
+            "Linking",
+            "Link",
+            Action::SwitchKeg,
+        ),
+        KegAction::Remove => (
+            CommandKind::RemoveKeg,
+            REMOVE_KEG_SCRIPT,
+            "Removing",
+            "Remove",
+            Action::RemoveKeg,
+        ),
+    };
+
+    let confirmed = matches!(app.pending_keg_action.as_ref(), Some(pending) if pending.action == action && pending.formula == formula && pending.version == version);
+    let destructive = action == KegAction::Remove;
+    if confirmed || !app.config.confirm.requires_confirmation(destructive) {
+        let args = keg_script_args(script, &formula, &version);
+        app.request_command(command_kind, &args, &channels.command_tx);
+        clear_pending_confirmations(app);
+        set_status(app, format!("{verb_ing} {formula} {version}..."));
+        return;
+    }
+
+    let confirmation_status = format!(
+        "{verb_title} {formula} {version}? {}",
+        confirm_hint(app, confirm_action)
+    );
+    clear_pending_confirmations(app);
+    app.pending_keg_action = Some(PendingKegAction {
+        action,
+        formula,
+        version,
+    });
+    set_status(app, confirmation_status);
+}
+
+/// Suggested commands always ask first: they come from parsed text, not from Brewery.
+fn run_or_confirm_issue_command(app: &mut App, channels: &RuntimeChannels, command: String) {
+    let Some(args) = brew_command_args(&command) else {
+        let reason = copy_only_reason(&command).unwrap_or_default();
+        set_status(
+            app,
+            format!(
+                "`{command}` {reason}; copy it with [{}] and run it in a terminal",
+                app.key_hint(Action::Yank)
+            ),
+        );
+        return;
+    };
+
+    if app.pending_issue_command.as_deref() == Some(command.as_str()) {
+        app.request_command(CommandKind::DoctorFix, &args, &channels.command_tx);
+        clear_pending_confirmations(app);
+        set_status(app, format!("Running `{command}`..."));
+        return;
+    }
+
+    clear_pending_confirmations(app);
+    let status = format!(
+        "Run `{command}`? {}",
+        confirm_hint(app, Action::RunIssueCommand)
+    );
+    app.pending_issue_command = Some(command);
+    set_status(app, status);
+}
+
+/// What `y` copies for the focused panel, with a status label; `Err` is the
+/// status to show when there is nothing to copy.
+fn yank_target(app: &App) -> Result<(String, String), String> {
+    let status_focused = app.focus_panel == FocusedPanel::Status;
+    let details_focused = app.focus_panel == FocusedPanel::Details;
+    match app.status_tab {
+        StatusTab::Issues if status_focused || (details_focused && app.details_shows_issue()) => {
+            if let Some(entry) = app.selected_deprecated_package() {
+                return Ok((entry.name.clone(), format!("`{}`", entry.name)));
+            }
+            app.selected_issue_command()
+                .map(|command| (command.to_string(), format!("`{command}`")))
+                .ok_or_else(|| "Selected issue has no suggested commands".to_string())
+        }
+        StatusTab::Services if status_focused || details_focused => {
+            let service = app
+                .selected_service_entry()
+                .ok_or_else(|| "No service selected".to_string())?;
+            let command = service
+                .troubleshooting_commands()
+                .into_iter()
+                .next()
+                .unwrap_or_default();
+            Ok((command.clone(), format!("`{command}`")))
+        }
+        StatusTab::History if status_focused => app
+            .selected_history_record()
+            .map(|record| {
+                (
+                    record.transcript(),
+                    format!("output of `{}`", record.command_line()),
+                )
+            })
+            .ok_or_else(|| "No command selected".to_string()),
+        StatusTab::Activity if status_focused => {
+            // A running command only has its live tail so far.
+            if app.pending_command {
+                let text = app.last_command_output.join("\n");
+                return if text.is_empty() {
+                    Err("No output yet".to_string())
+                } else {
+                    Ok((text, "live output".to_string()))
+                };
+            }
+            app.command_history
+                .front()
+                .map(|record| {
+                    (
+                        record.transcript(),
+                        format!("output of `{}`", record.command_line()),
+                    )
+                })
+                .ok_or_else(|| "No command output yet".to_string())
+        }
+        _ => app
+            .selected_package_name()
+            .map(|pkg| (pkg.to_string(), format!("`{pkg}`")))
+            .ok_or_else(|| "Nothing to copy".to_string()),
+    }
+}
+
+fn yank_install_line(app: &mut App) {
+    let results = matches!(
+        app.input_mode,
+        InputMode::PackageSearch | InputMode::PackageResults
+    );
+    let Some(pkg) = app.selected_package_name().map(str::to_string) else {
+        set_status(app, "No package selected");
+        return;
+    };
+    let kind = if results {
+        PackageKind::Formula
+    } else {
+        app.active_package_kind
+    };
+    let line = format!(
+        "brew {}",
+        package_action_args(PackageAction::Install, kind, &pkg).join(" ")
+    );
+    copy_to_clipboard(app, &line, &format!("`{line}`"));
+}
+
+fn copy_to_clipboard(app: &mut App, text: &str, label: &str) {
+    match clipboard::copy(text) {
+        Ok(()) => set_status(app, format!("Copied {label}")),
+        Err(err) => set_status(app, format!("Copy failed: {err}")),
+    }
+}
+
+fn selected_issue_command(app: &mut App) -> Option<String> {
+    if app.status_tab != StatusTab::Issues {
+        set_status(app, "Open the Issues tab to pick a suggested command");
+        return None;
+    }
+    let command = app.selected_issue_command().map(str::to_string);
+    if command.is_none() {
+        set_status(app, "Selected issue has no suggested commands");
+    }
+    command
+}
+
+fn package_action_labels(
+    action: PackageAction,
+) -> (CommandKind, &'static str, &'static str, Action) {
+    match action {
+        PackageAction::Install => (
+            CommandKind::Install,
+            "Installing",
+            "Install",
+            Action::Install,
+        ),
+        PackageAction::Uninstall => (
+            CommandKind::Uninstall,
+            "Uninstalling",
+            "Uninstall",
+            Action::Uninstall,
+        ),
+        PackageAction::Upgrade => (
+            CommandKind::Upgrade,
+            "Upgrading",
+            "Upgrade",
+            Action::Upgrade,
+        ),
+        PackageAction::Link => (CommandKind::Link, "Linking", "Link", Action::ToggleLink),
+        PackageAction::Unlink => (
+            CommandKind::Unlink,
+            "Unlinking",
+            "Unlink",
+            Action::ToggleLink,
+        ),
     }
 }
 
@@ -685,6 +1626,8 @@ fn package_action_args(action: PackageAction, kind: PackageKind, pkg: &str) -> V
         (PackageAction::Uninstall, PackageKind::Cask) => vec!["uninstall", "--cask", pkg],
         (PackageAction::Upgrade, PackageKind::Formula) => vec!["upgrade", pkg],
         (PackageAction::Upgrade, PackageKind::Cask) => vec!["upgrade", "--cask", pkg],
+        (PackageAction::Link, _) => vec!["link", pkg],
+        (PackageAction::Unlink, _) => vec!["unlink", pkg],
     }
 }
 
@@ -695,37 +1638,184 @@ fn package_kind_noun(kind: PackageKind) -> &'static str {
     }
 }
 
-fn service_action_labels(action: ServiceAction) -> (CommandKind, &'static str, &'static str, char) {
+fn service_action_labels(
+    action: ServiceAction,
+) -> (CommandKind, &'static str, &'static str, Action) {
     match action {
-        ServiceAction::Start => (CommandKind::ServiceStart, "Starting", "Start", 'S'),
-        ServiceAction::Stop => (CommandKind::ServiceStop, "Stopping", "Stop", 'X'),
-        ServiceAction::Restart => (CommandKind::ServiceRestart, "Restarting", "Restart", 'R'),
+        ServiceAction::Start => (
+            CommandKind::ServiceStart,
+            "Starting",
+            "Start",
+            Action::ServiceStart,
+        ),
+        ServiceAction::Stop => (
+            CommandKind::ServiceStop,
+            "Stopping",
+            "Stop",
+            Action::ServiceStop,
+        ),
+        ServiceAction::Restart => (
+            CommandKind::ServiceRestart,
+            "Restarting",
+            "Restart",
+            Action::ServiceRestart,
+        ),
+        ServiceAction::Run => (
+            CommandKind::ServiceRun,
+            "Running",
+            "Run",
+            Action::ServiceRun,
+        ),
+        ServiceAction::Kill => (
+            CommandKind::ServiceKill,
+            "Killing",
+            "Kill",
+            Action::ServiceKill,
+        ),
     }
 }
 
-fn service_action_args(action: ServiceAction, service: &str) -> Vec<&str> {
-    match action {
-        ServiceAction::Start => vec!["services", "start", service],
-        ServiceAction::Stop => vec!["services", "stop", service],
-        ServiceAction::Restart => vec!["services", "restart", service],
-    }
+fn service_action_args(action: ServiceAction, target: &ServiceTarget) -> Vec<&str> {
+    let verb = match action {
+        ServiceAction::Start => "start",
+        ServiceAction::Stop => "stop",
+        ServiceAction::Restart => "restart",
+        ServiceAction::Run => "run",
+        ServiceAction::Kill => "kill",
+    };
+    let target = match target {
+        ServiceTarget::One(service) => service.as_str(),
+        ServiceTarget::All => "--all",
+    };
+    vec!["services", verb, target]
+}
+
+fn confirm_hint(app: &App, confirm_action: Action) -> String {
+    format!(
+        "[{}] confirm, [{}] cancel",
+        app.key_hint(confirm_action),
+        app.key_hint(Action::Cancel)
+    )
+}
+
+fn is_history_focused(app: &App) -> bool {
+    app.focus_panel == FocusedPanel::Status && app.status_tab == StatusTab::History
+}
+
+fn is_services_focused(app: &App) -> bool {
+    app.focus_panel == FocusedPanel::Status && app.status_tab == StatusTab::Services
+}
+
+fn is_issues_focused(app: &App) -> bool {
+    app.focus_panel == FocusedPanel::Status && app.status_tab == StatusTab::Issues
 }
 
 fn has_pending_confirmation(app: &App) -> bool {
     app.pending_package_action.is_some()
         || app.pending_service_action.is_some()
+        || app.pending_service_all
+        || app.pending_services_cleanup
         || app.pending_upgrade_all_outdated
         || app.pending_self_update
+        || app.pending_issue_command.is_some()
+        || app.pending_keg_action.is_some()
+        || app.pending_migration.is_some()
+        || app.pending_zap.is_some()
 }
 
 fn clear_pending_confirmations(app: &mut App) {
     app.pending_package_action = None;
     app.pending_service_action = None;
+    app.pending_service_all = false;
+    app.pending_services_cleanup = false;
     app.pending_upgrade_all_outdated = false;
     app.pending_self_update = false;
+    app.pending_issue_command = None;
+    app.pending_keg_action = None;
+    app.pending_migration = None;
+    app.pending_zap = None;
 }
 
 fn set_status(app: &mut App, status: impl Into<String>) {
     app.status = status.into();
     app.last_refresh = Instant::now();
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{service_action_args, yank_target};
+    use crate::app::{App, FocusedPanel, ServiceAction, ServiceTarget, StatusTab};
+    use crate::brew::{ServiceEntry, StatusSnapshot};
+    use crate::storage::CommandRecord;
+
+    #[test]
+    fn yank_target_follows_focus() {
+        let mut app = App::new();
+        app.leaves = vec!["jq".to_string()];
+        app.selected_index = Some(0);
+        assert_eq!(
+            yank_target(&app),
+            Ok(("jq".to_string(), "`jq`".to_string()))
+        );
+
+        app.focus_panel = FocusedPanel::Status;
+        app.status_tab = StatusTab::History;
+        assert_eq!(yank_target(&app), Err("No command selected".to_string()));
+        app.command_history.push_back(CommandRecord {
+            kind: "install".to_string(),
+            argv: vec!["brew".to_string(), "install".to_string(), "jq".to_string()],
+            started_at: 0,
+            duration_ms: 0,
+            success: true,
+            exit_code: Some(0),
+            stdout: "==> Pouring jq\n".to_string(),
+            stderr: String::new(),
+        });
+        app.history_selected_index = Some(0);
+        assert_eq!(
+            yank_target(&app),
+            Ok((
+                "$ brew install jq\n==> Pouring jq\n".to_string(),
+                "output of `brew install jq`".to_string()
+            ))
+        );
+
+        // History is only yanked from the Status panel itself.
+        app.focus_panel = FocusedPanel::Details;
+        assert_eq!(yank_target(&app).unwrap().0, "jq");
+
+        app.status_tab = StatusTab::Services;
+        assert_eq!(yank_target(&app), Err("No service selected".to_string()));
+        let redis = ServiceEntry {
+            name: "redis".to_string(),
+            status: "error".to_string(),
+            user: None,
+            file: None,
+            exit_code: Some(1),
+        };
+        let command = redis.troubleshooting_commands().remove(0);
+        app.system_status = Some(StatusSnapshot {
+            services: vec![redis],
+            ..StatusSnapshot::default()
+        });
+        app.services_selected_index = Some(0);
+        assert_eq!(yank_target(&app).unwrap().0, command);
+    }
+
+    #[test]
+    fn builds_service_action_args_for_one_or_all() {
+        let one = ServiceTarget::One("redis".to_string());
+        assert_eq!(
+            service_action_args(ServiceAction::Restart, &one),
+            ["services", "restart", "redis"]
+        );
+        assert_eq!(
+            service_action_args(ServiceAction::Stop, &ServiceTarget::All),
+            ["services", "stop", "--all"]
+        );
+        assert_eq!(
+            service_action_args(ServiceAction::Kill, &ServiceTarget::All),
+            ["services", "kill", "--all"]
+        );
+    }
+}
@@ -5,7 +5,7 @@ use crossterm::terminal::size;
 use ratatui::layout::Rect;
 
 use crate::app::{App, FocusedPanel, InputMode, StatusTab};
-use crate::ui::{help, layout, status_tab_at_column};
+use crate::ui::{help, layout, pager, status_tab_at_column};
 
 #[derive(Clone, Copy)]
 enum ScrollDirection {
@@ -14,6 +14,27 @@ enum ScrollDirection {
 }
 
 pub fn handle_mouse_event(app: &mut App, mouse: MouseEvent, help_max_offset: usize) {
+    if app.theme_picker.is_some() {
+        match mouse.kind {
+            MouseEventKind::ScrollUp => app.theme_picker_move(-1),
+            MouseEventKind::ScrollDown => app.theme_picker_move(1),
+            _ => return,
+        }
+        app.needs_redraw = true;
+        return;
+    }
+
+    if app.pager.is_some() {
+        let max_offset = pager::pager_max_offset(app, terminal_area());
+        match mouse.kind {
+            MouseEventKind::ScrollUp => app.scroll_pager(-3, max_offset),
+            MouseEventKind::ScrollDown => app.scroll_pager(3, max_offset),
+            _ => return,
+        }
+        app.needs_redraw = true;
+        return;
+    }
+
     if app.show_help_popup {
         handle_help_popup_mouse(app, mouse, help_max_offset);
         return;
@@ -302,6 +323,9 @@ fn select_status_tab(app: &mut App, column: u16, area: Rect) {
     {
         app.status_tab = next_tab;
         app.status_scroll_offset = 0;
+        if next_tab == StatusTab::History {
+            app.reconcile_history_selection();
+        }
     }
 }
 
@@ -376,6 +400,12 @@ fn contains_point(area: Rect, x: u16, y: u16) -> bool {
 fn clear_pending_confirmations(app: &mut App) {
     app.pending_package_action = None;
     app.pending_service_action = None;
+    app.pending_service_all = false;
+    app.pending_services_cleanup = false;
     app.pending_upgrade_all_outdated = false;
     app.pending_self_update = false;
+    app.pending_issue_command = None;
+    app.pending_keg_action = None;
+    app.pending_migration = None;
+    app.pending_zap = None;
 }
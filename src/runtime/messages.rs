@@ -3,21 +3,25 @@ use std::time::Instant;
 use tokio::sync::mpsc;
 
 use crate::app::{App, InputMode};
-use crate::brew::{CommandKind, DetailsLoad, LeavesMessage};
+use crate::brew::{CommandEvent, DetailsLoad, LeavesMessage};
 
 pub struct RuntimeChannels {
     pub leaves_tx: mpsc::UnboundedSender<LeavesMessage>,
     pub casks_tx: mpsc::UnboundedSender<crate::brew::CasksMessage>,
     pub details_tx: mpsc::UnboundedSender<crate::brew::DetailsMessage>,
     pub sizes_tx: mpsc::UnboundedSender<crate::brew::SizesMessage>,
-    pub command_tx: mpsc::UnboundedSender<crate::brew::CommandMessage>,
+    pub command_tx: mpsc::UnboundedSender<CommandEvent>,
     pub status_tx: mpsc::UnboundedSender<crate::brew::StatusMessage>,
+    pub logs_tx: mpsc::UnboundedSender<crate::brew::ServiceLogsMessage>,
+    pub deprecated_tx: mpsc::UnboundedSender<crate::brew::DeprecatedMessage>,
     pub leaves_rx: mpsc::UnboundedReceiver<LeavesMessage>,
     pub casks_rx: mpsc::UnboundedReceiver<crate::brew::CasksMessage>,
     pub details_rx: mpsc::UnboundedReceiver<crate::brew::DetailsMessage>,
     pub sizes_rx: mpsc::UnboundedReceiver<crate::brew::SizesMessage>,
-    pub command_rx: mpsc::UnboundedReceiver<crate::brew::CommandMessage>,
+    pub command_rx: mpsc::UnboundedReceiver<CommandEvent>,
     pub status_rx: mpsc::UnboundedReceiver<crate::brew::StatusMessage>,
+    pub logs_rx: mpsc::UnboundedReceiver<crate::brew::ServiceLogsMessage>,
+    pub deprecated_rx: mpsc::UnboundedReceiver<crate::brew::DeprecatedMessage>,
 }
 
 pub fn create_channels() -> RuntimeChannels {
@@ -27,6 +31,8 @@ pub fn create_channels() -> RuntimeChannels {
     let (sizes_tx, sizes_rx) = mpsc::unbounded_channel();
     let (command_tx, command_rx) = mpsc::unbounded_channel();
     let (status_tx, status_rx) = mpsc::unbounded_channel();
+    let (logs_tx, logs_rx) = mpsc::unbounded_channel();
+    let (deprecated_tx, deprecated_rx) = mpsc::unbounded_channel();
 
     RuntimeChannels {
         leaves_tx,
@@ -35,12 +41,16 @@ pub fn create_channels() -> RuntimeChannels {
         sizes_tx,
         command_tx,
         status_tx,
+        logs_tx,
+        deprecated_tx,
         leaves_rx,
         casks_rx,
         details_rx,
         sizes_rx,
         command_rx,
         status_rx,
+        logs_rx,
+        deprecated_rx,
     }
 }
 
@@ -63,16 +73,26 @@ pub fn process_pending_messages(app: &mut App, channels: &mut RuntimeChannels) {
         app.apply_sizes_message(message);
         received_message = true;
     }
-    while let Ok(message) = channels.command_rx.try_recv() {
+    while let Ok(event) = channels.command_rx.try_recv() {
+        received_message = true;
+        let message = match event {
+            CommandEvent::Output(line) => {
+                app.apply_command_output(line);
+                continue;
+            }
+            CommandEvent::Finished(message) => message,
+        };
         let mut should_refresh_leaves = false;
         let mut should_refresh_status = false;
+        let mut should_refresh_deprecated = false;
         let mut refresh_details_pkg = None;
         if let Ok(result) = &message.result
             && result.success
         {
             should_refresh_leaves = message.kind.refreshes_lists_on_success();
             should_refresh_status = message.kind.refreshes_status_on_success();
-            if message.kind == CommandKind::Upgrade {
+            should_refresh_deprecated = message.kind.refreshes_deprecations_on_success();
+            if message.kind.refreshes_details_on_success() {
                 refresh_details_pkg = app.last_command_target.clone();
             }
         }
@@ -84,6 +104,9 @@ pub fn process_pending_messages(app: &mut App, channels: &mut RuntimeChannels) {
         if should_refresh_status {
             app.request_status(&channels.status_tx);
         }
+        if should_refresh_deprecated {
+            app.request_deprecated(&channels.deprecated_tx);
+        }
         if let Some(pkg) = refresh_details_pkg {
             app.request_details_forced(&pkg, DetailsLoad::Basic, &channels.details_tx);
         }
@@ -93,6 +116,14 @@ pub fn process_pending_messages(app: &mut App, channels: &mut RuntimeChannels) {
         app.apply_status_message(message);
         received_message = true;
     }
+    while let Ok(message) = channels.deprecated_rx.try_recv() {
+        app.apply_deprecated_message(message);
+        received_message = true;
+    }
+    while let Ok(message) = channels.logs_rx.try_recv() {
+        app.apply_service_logs_message(message);
+        received_message = true;
+    }
 
     if received_message {
         app.needs_redraw = true;
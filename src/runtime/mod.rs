@@ -1,4 +1,7 @@
+pub mod clipboard;
 pub mod event_loop;
 pub mod input;
 pub mod messages;
+pub mod opener;
 pub mod terminal;
+pub mod tty;
@@ -0,0 +1,128 @@
+//! Opens homepages and reveals package directories.
+//!
+//! `auto` hands the target to the desktop opener when there is a desktop to
+//! show it on; over SSH or without a display it copies the target instead.
+
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+use crate::config::Opener;
+use crate::runtime::clipboard;
+
+pub enum OpenTarget<'a> {
+    Url(&'a str),
+    /// Shown in the file manager rather than opened, so an `.app` is not launched.
+    Reveal(&'a Path),
+}
+
+impl OpenTarget<'_> {
+    pub fn text(&self) -> String {
+        match self {
+            Self::Url(url) => url.to_string(),
+            Self::Reveal(path) => path.display().to_string(),
+        }
+    }
+}
+
+pub enum Opened {
+    Launched(String),
+    Printed,
+    Copied,
+}
+
+pub fn open(opener: &Opener, target: &OpenTarget) -> anyhow::Result<Opened> {
+    let command = match opener {
+        Opener::Print => return Ok(Opened::Printed),
+        Opener::Copy => None,
+        Opener::Command(command) => Some(command_with_target(command, target)),
+        Opener::Auto => auto_command(std::env::consts::OS, has_desktop(), target),
+    };
+    let Some(command) = command else {
+        clipboard::copy(&target.text())?;
+        return Ok(Opened::Copied);
+    };
+
+    let (program, args) = command
+        .split_first()
+        .ok_or_else(|| anyhow::anyhow!("opener is empty"))?;
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|err| anyhow::anyhow!("Could not run {program}: {err}"))?;
+    // Reap the opener without blocking the UI.
+    std::thread::spawn(move || child.wait());
+    Ok(Opened::Launched(program.clone()))
+}
+
+fn command_with_target(command: &[String], target: &OpenTarget) -> Vec<String> {
+    let mut command = command.to_vec();
+    command.push(target.text());
+    command
+}
+
+/// The platform opener, or `None` when nothing local could show the target.
+fn auto_command(os: &str, desktop: bool, target: &OpenTarget) -> Option<Vec<String>> {
+    if !desktop {
+        return None;
+    }
+    let command = match (os, target) {
+        ("macos", OpenTarget::Url(url)) => vec!["open".to_string(), url.to_string()],
+        ("macos", OpenTarget::Reveal(path)) => vec![
+            "open".to_string(),
+            "-R".to_string(),
+            path.display().to_string(),
+        ],
+        (_, OpenTarget::Url(url)) => vec!["xdg-open".to_string(), url.to_string()],
+        // xdg-open would open a file with its default app; show its folder instead.
+        (_, OpenTarget::Reveal(path)) => {
+            let dir = if path.is_dir() {
+                path
+            } else {
+                path.parent().unwrap_or(path)
+            };
+            vec!["xdg-open".to_string(), dir.display().to_string()]
+        }
+    };
+    Some(command)
+}
+
+/// A local graphical session; SSH sessions count as headless even with X forwarding.
+pub fn has_desktop() -> bool {
+    let set = |name: &str| std::env::var_os(name).is_some_and(|value| !value.is_empty());
+    if set("SSH_CONNECTION") || set("SSH_TTY") {
+        return false;
+    }
+    cfg!(target_os = "macos") || set("DISPLAY") || set("WAYLAND_DISPLAY")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::{OpenTarget, auto_command};
+
+    #[test]
+    fn picks_platform_opener_and_falls_back_without_desktop() {
+        let url = OpenTarget::Url("https://jqlang.github.io");
+        assert_eq!(
+            auto_command("linux", true, &url),
+            Some(vec![
+                "xdg-open".to_string(),
+                "https://jqlang.github.io".to_string()
+            ])
+        );
+        let app = OpenTarget::Reveal(Path::new("/Applications/Firefox.app"));
+        assert_eq!(
+            auto_command("macos", true, &app),
+            Some(vec![
+                "open".to_string(),
+                "-R".to_string(),
+                "/Applications/Firefox.app".to_string()
+            ])
+        );
+        assert_eq!(auto_command("linux", false, &url), None);
+    }
+}
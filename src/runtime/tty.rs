@@ -0,0 +1,395 @@
+//! Terminal colour reports for the Auto theme.
+//!
+//! crossterm cannot decode the OSC 11 reply or mode 2031 `CSI ? 997 ; n`
+//! reports (an unknown `CSI ?` sequence stalls its parser until the next `c` or
+//! `u`), so while they are enabled a relay thread is the only reader of the
+//! terminal: it takes those replies out and forwards every other byte unchanged
+//! to a pseudo-terminal that crossterm reads as stdin.
+
+use std::fs::File;
+use std::io::{self, Read, Write};
+use std::os::fd::{AsRawFd, FromRawFd, OwnedFd};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+use crate::theme::Appearance;
+
+/// Background colour query followed by device attributes: terminals answer in
+/// order, so the second reply ends the wait on terminals without OSC 11.
+const BACKGROUND_QUERY: &[u8] = b"\x1b]11;?\x1b\\\x1b[c";
+const ENABLE_COLOR_SCHEME_REPORTS: &[u8] = b"\x1b[?2031h";
+const DISABLE_COLOR_SCHEME_REPORTS: &[u8] = b"\x1b[?2031l";
+
+const ESC: u8 = 0x1b;
+const MAX_REPLY_LEN: usize = 512;
+
+/// How often the relay checks whether it should stop.
+const RELAY_POLL: Duration = Duration::from_millis(100);
+
+/// How long an unfinished escape sequence is held back before it is passed on
+/// as typed input (a lone Esc key, for instance).
+const PARTIAL_HOLD: Duration = Duration::from_millis(20);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Reply {
+    Appearance(Appearance),
+    DeviceAttributes,
+}
+
+/// Relays terminal input to crossterm and collects colour reports on the way.
+/// Restores the terminal and stdin when dropped.
+pub struct ColorReports {
+    replies: Receiver<Reply>,
+    stop: Arc<AtomicBool>,
+    relay: Option<JoinHandle<()>>,
+    tty: File,
+    original: libc::termios,
+    saved_stdin: OwnedFd,
+}
+
+impl ColorReports {
+    /// Must run before `enable_raw_mode` so crossterm sets up the relayed stdin.
+    pub fn install() -> io::Result<Self> {
+        let tty = std::fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open("/dev/tty")?;
+        let original = termios(&tty)?;
+        let (master, slave) = open_pty()?;
+
+        // SAFETY: dup only duplicates the descriptor.
+        let saved = unsafe { libc::dup(libc::STDIN_FILENO) };
+        if saved < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        // SAFETY: `saved` was just returned by dup and nothing else owns it.
+        let saved_stdin = unsafe { OwnedFd::from_raw_fd(saved) };
+
+        let mut raw = original;
+        // SAFETY: cfmakeraw only edits the struct we pass.
+        unsafe { libc::cfmakeraw(&mut raw) };
+        set_termios(&tty, &raw)?;
+        // SAFETY: both descriptors are open; fd 0 now refers to the pty slave.
+        if unsafe { libc::dup2(slave.as_raw_fd(), libc::STDIN_FILENO) } < 0 {
+            let err = io::Error::last_os_error();
+            let _ = set_termios(&tty, &original);
+            return Err(err);
+        }
+
+        let (sender, replies) = mpsc::channel();
+        let stop = Arc::new(AtomicBool::new(false));
+        let relay = {
+            let tty = tty.try_clone()?;
+            let stop = Arc::clone(&stop);
+            std::thread::spawn(move || relay(tty, File::from(master), sender, &stop))
+        };
+
+        let mut reports = Self {
+            replies,
+            stop,
+            relay: Some(relay),
+            tty,
+            original,
+            saved_stdin,
+        };
+        // Terminals without mode 2031 ignore it.
+        reports.tty.write_all(ENABLE_COLOR_SCHEME_REPORTS)?;
+        Ok(reports)
+    }
+
+    /// Asks for the background colour, answering `None` if the terminal stays quiet.
+    pub fn query_appearance(&mut self, timeout: Duration) -> Option<Appearance> {
+        self.tty.write_all(BACKGROUND_QUERY).ok()?;
+        match self.replies.recv_timeout(timeout).ok()? {
+            Reply::Appearance(appearance) => Some(appearance),
+            Reply::DeviceAttributes => None,
+        }
+    }
+
+    /// The most recent colour-scheme change since the last call, if any.
+    pub fn latest_change(&self) -> Option<Appearance> {
+        self.replies
+            .try_iter()
+            .filter_map(|reply| match reply {
+                Reply::Appearance(appearance) => Some(appearance),
+                Reply::DeviceAttributes => None,
+            })
+            .last()
+    }
+}
+
+impl Drop for ColorReports {
+    fn drop(&mut self) {
+        let _ = self.tty.write_all(DISABLE_COLOR_SCHEME_REPORTS);
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(relay) = self.relay.take() {
+            let _ = relay.join();
+        }
+        let _ = set_termios(&self.tty, &self.original);
+        // SAFETY: restores the original stdin descriptor, which we still own.
+        unsafe { libc::dup2(self.saved_stdin.as_raw_fd(), libc::STDIN_FILENO) };
+    }
+}
+
+fn relay(mut tty: File, mut pty: File, replies: Sender<Reply>, stop: &AtomicBool) {
+    let mut filter = ReplyFilter::default();
+    let mut found = Vec::new();
+    let mut buffer = [0u8; 1024];
+    while !stop.load(Ordering::Relaxed) {
+        let wait = if filter.is_holding() {
+            PARTIAL_HOLD
+        } else {
+            RELAY_POLL
+        };
+        let forward = match poll_readable(&tty, wait) {
+            Ok(false) => filter.flush(),
+            Ok(true) => match tty.read(&mut buffer) {
+                Ok(0) => return,
+                Ok(read) => filter.feed(&buffer[..read], &mut found),
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                Err(_) => return,
+            },
+            Err(_) => return,
+        };
+        for reply in found.drain(..) {
+            let _ = replies.send(reply);
+        }
+        if !forward.is_empty() && pty.write_all(&forward).is_err() {
+            return;
+        }
+    }
+}
+
+/// Splits terminal input into colour replies and the bytes crossterm should see.
+#[derive(Default)]
+struct ReplyFilter {
+    pending: Vec<u8>,
+}
+
+enum Scan {
+    /// Not one of ours: pass the escape byte on.
+    Pass,
+    /// A complete reply of this many bytes, dropped from the input.
+    Reply(usize, Option<Reply>),
+    /// Could still become a reply; wait for more bytes.
+    Partial,
+}
+
+impl ReplyFilter {
+    fn is_holding(&self) -> bool {
+        !self.pending.is_empty()
+    }
+
+    fn feed(&mut self, input: &[u8], replies: &mut Vec<Reply>) -> Vec<u8> {
+        let mut bytes = std::mem::take(&mut self.pending);
+        bytes.extend_from_slice(input);
+        let mut forward = Vec::with_capacity(bytes.len());
+        let mut index = 0;
+        while index < bytes.len() {
+            if bytes[index] != ESC {
+                forward.push(bytes[index]);
+                index += 1;
+                continue;
+            }
+            match scan(&bytes[index..]) {
+                Scan::Pass => {
+                    forward.push(ESC);
+                    index += 1;
+                }
+                Scan::Reply(len, reply) => {
+                    replies.extend(reply);
+                    index += len;
+                }
+                Scan::Partial => {
+                    self.pending = bytes[index..].to_vec();
+                    break;
+                }
+            }
+        }
+        forward
+    }
+
+    /// Gives up on a held-back sequence and passes it on as typed.
+    fn flush(&mut self) -> Vec<u8> {
+        std::mem::take(&mut self.pending)
+    }
+}
+
+/// Recognises `OSC 11 ; <colour> ST`, `CSI ? 997 ; <1|2> n` and device
+/// attributes `CSI ? <params> c` at the start of `bytes`.
+fn scan(bytes: &[u8]) -> Scan {
+    const OSC_BACKGROUND: &[u8] = b"\x1b]11;";
+    const CSI_PRIVATE: &[u8] = b"\x1b[?";
+
+    if let Some(body) = bytes.strip_prefix(OSC_BACKGROUND) {
+        let Some(end) = body.iter().position(|&byte| byte == 0x07 || byte == ESC) else {
+            return partial_unless_too_long(bytes);
+        };
+        let terminator = match body[end] {
+            0x07 => 1,
+            _ if body.get(end + 1) == Some(&b'\\') => 2,
+            _ if end + 1 == body.len() => return partial_unless_too_long(bytes),
+            _ => return Scan::Pass,
+        };
+        let reply = std::str::from_utf8(&body[..end])
+            .ok()
+            .and_then(parse_rgb_spec)
+            .map(|(r, g, b)| Reply::Appearance(Appearance::from_background(r, g, b)));
+        return Scan::Reply(OSC_BACKGROUND.len() + end + terminator, reply);
+    }
+
+    if let Some(body) = bytes.strip_prefix(CSI_PRIVATE) {
+        let params = body
+            .iter()
+            .take_while(|byte| byte.is_ascii_digit() || **byte == b';')
+            .count();
+        let len = CSI_PRIVATE.len() + params + 1;
+        return match body.get(params) {
+            None => partial_unless_too_long(bytes),
+            Some(b'c') => Scan::Reply(len, Some(Reply::DeviceAttributes)),
+            Some(b'n') => match &body[..params] {
+                b"997;1" => Scan::Reply(len, Some(Reply::Appearance(Appearance::Dark))),
+                b"997;2" => Scan::Reply(len, Some(Reply::Appearance(Appearance::Light))),
+                _ => Scan::Pass,
+            },
+            Some(_) => Scan::Pass,
+        };
+    }
+
+    if OSC_BACKGROUND.starts_with(bytes) || CSI_PRIVATE.starts_with(bytes) {
+        Scan::Partial
+    } else {
+        Scan::Pass
+    }
+}
+
+fn partial_unless_too_long(bytes: &[u8]) -> Scan {
+    if bytes.len() < MAX_REPLY_LEN {
+        Scan::Partial
+    } else {
+        Scan::Pass
+    }
+}
+
+/// `rgb:RRRR/GGGG/BBBB` with 1-4 hex digits per channel.
+fn parse_rgb_spec(spec: &str) -> Option<(u8, u8, u8)> {
+    let channels = spec.strip_prefix("rgb:")?;
+    let mut parts = channels.split('/').map(|part| {
+        let value = u32::from_str_radix(part, 16).ok()?;
+        let max = (1u32 << (4 * part.len() as u32)) - 1;
+        (!part.is_empty() && part.len() <= 4).then(|| (value * 255 / max) as u8)
+    });
+    let (r, g, b) = (parts.next()??, parts.next()??, parts.next()??);
+    parts.next().is_none().then_some((r, g, b))
+}
+
+fn poll_readable(file: &File, timeout: Duration) -> io::Result<bool> {
+    let mut pollfd = libc::pollfd {
+        fd: file.as_raw_fd(),
+        events: libc::POLLIN,
+        revents: 0,
+    };
+    // SAFETY: one valid pollfd for the duration of the call.
+    let ready = unsafe { libc::poll(&mut pollfd, 1, timeout.as_millis() as libc::c_int) };
+    match ready {
+        0 => Ok(false),
+        ready if ready > 0 => Ok(true),
+        _ => {
+            let err = io::Error::last_os_error();
+            if err.kind() == io::ErrorKind::Interrupted {
+                Ok(false)
+            } else {
+                Err(err)
+            }
+        }
+    }
+}
+
+fn open_pty() -> io::Result<(OwnedFd, OwnedFd)> {
+    let (mut master, mut slave) = (0, 0);
+    // SAFETY: openpty fills in two descriptors; the name, termios and window
+    // size arguments are optional.
+    let result = unsafe {
+        libc::openpty(
+            &mut master,
+            &mut slave,
+            std::ptr::null_mut(),
+            std::ptr::null_mut(),
+            std::ptr::null_mut(),
+        )
+    };
+    if result < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    // SAFETY: both descriptors were just opened and are owned by nobody else.
+    Ok(unsafe { (OwnedFd::from_raw_fd(master), OwnedFd::from_raw_fd(slave)) })
+}
+
+fn termios(tty: &File) -> io::Result<libc::termios> {
+    // SAFETY: tcgetattr fully initialises the struct on success.
+    let mut termios = unsafe { std::mem::zeroed() };
+    if unsafe { libc::tcgetattr(tty.as_raw_fd(), &mut termios) } < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(termios)
+}
+
+fn set_termios(tty: &File, termios: &libc::termios) -> io::Result<()> {
+    // SAFETY: applies a termios struct we own to an open descriptor.
+    if unsafe { libc::tcsetattr(tty.as_raw_fd(), libc::TCSANOW, termios) } < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Reply, ReplyFilter, parse_rgb_spec};
+    use crate::theme::Appearance;
+
+    #[test]
+    fn parses_rgb_specs() {
+        assert_eq!(parse_rgb_spec("rgb:ffff/8080/0000"), Some((255, 128, 0)));
+        assert_eq!(parse_rgb_spec("rgb:f/8/0"), Some((255, 136, 0)));
+        assert_eq!(parse_rgb_spec("rgb:ff/80"), None);
+        assert_eq!(parse_rgb_spec("#ff8000"), None);
+    }
+
+    #[test]
+    fn takes_colour_replies_out_of_the_input() {
+        let mut filter = ReplyFilter::default();
+        let mut replies = Vec::new();
+        let forward = filter.feed(
+            b"j\x1b]11;rgb:1e1e/2020/2e2e\x1b\\\x1b[?62;22ck\x1b[?997;2n\x1b[A",
+            &mut replies,
+        );
+        assert_eq!(forward, b"jk\x1b[A");
+        assert_eq!(
+            replies,
+            vec![
+                Reply::Appearance(Appearance::Dark),
+                Reply::DeviceAttributes,
+                Reply::Appearance(Appearance::Light),
+            ]
+        );
+    }
+
+    #[test]
+    fn holds_split_replies_and_releases_a_lone_escape() {
+        let mut filter = ReplyFilter::default();
+        let mut replies = Vec::new();
+        assert_eq!(filter.feed(b"q\x1b[?99", &mut replies), b"q");
+        assert_eq!(filter.feed(b"7;1n", &mut replies), b"");
+        assert_eq!(replies, vec![Reply::Appearance(Appearance::Dark)]);
+
+        assert_eq!(filter.feed(b"\x1b", &mut replies), b"");
+        assert_eq!(filter.flush(), b"\x1b");
+        assert_eq!(
+            filter.feed(b"\x1b[?1u\x1bj", &mut replies),
+            b"\x1b[?1u\x1bj"
+        );
+    }
+}
@@ -0,0 +1,161 @@
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use super::{data_dir, read_json, write_json};
+
+const COMMAND_HISTORY_FILE: &str = "command_history.json";
+/// Output kept per stream of a record; long upgrades keep only their end.
+const MAX_OUTPUT_BYTES: usize = 64 * 1024;
+
+/// One finished command with its output, trimmed to the last `MAX_OUTPUT_BYTES`
+/// of each stream.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CommandRecord {
+    pub kind: String,
+    /// Program followed by its arguments, exactly as spawned.
+    pub argv: Vec<String>,
+    /// Unix seconds when the command was started.
+    pub started_at: u64,
+    pub duration_ms: u64,
+    pub success: bool,
+    pub exit_code: Option<i32>,
+    pub stdout: String,
+    pub stderr: String,
+}
+
+impl CommandRecord {
+    pub fn command_line(&self) -> String {
+        self.argv.join(" ")
+    }
+
+    /// The command line followed by its stdout and stderr, for pasting elsewhere.
+    pub fn transcript(&self) -> String {
+        let mut text = format!("$ {}\n", self.command_line());
+        for output in [&self.stdout, &self.stderr] {
+            if !output.trim().is_empty() {
+                text.push_str(output.trim_end());
+                text.push('\n');
+            }
+        }
+        text
+    }
+
+    pub fn duration_label(&self) -> String {
+        if self.duration_ms < 60_000 {
+            format!("{:.1}s", self.duration_ms as f64 / 1000.0)
+        } else {
+            let secs = self.duration_ms / 1000;
+            format!("{}m{:02}s", secs / 60, secs % 60)
+        }
+    }
+}
+
+/// The last `MAX_OUTPUT_BYTES` of `output`, starting on a whole line.
+pub fn output_tail(output: String) -> String {
+    if output.len() <= MAX_OUTPUT_BYTES {
+        return output;
+    }
+    let mut start = output.len() - MAX_OUTPUT_BYTES;
+    while !output.is_char_boundary(start) {
+        start += 1;
+    }
+    let start = output[start..]
+        .find('\n')
+        .map_or(start, |newline| start + newline + 1);
+    format!("[earlier output truncated]\n{}", &output[start..])
+}
+
+/// Newest first.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CommandHistoryFile {
+    records: Vec<CommandRecord>,
+}
+
+/// Reads the saved history, newest first; a missing or unreadable file yields nothing.
+pub fn load_command_history(capacity: usize) -> Vec<CommandRecord> {
+    let Some(path) = command_history_path() else {
+        return Vec::new();
+    };
+    let mut records = read_json::<CommandHistoryFile>(&path)
+        .ok()
+        .flatten()
+        .unwrap_or_default()
+        .records;
+    records.truncate(capacity);
+    records
+}
+
+/// Prepends `record` to the on-disk history, keeping at most `capacity` entries.
+pub fn record_command(record: &CommandRecord, capacity: usize) -> anyhow::Result<()> {
+    let path = command_history_path()
+        .ok_or_else(|| anyhow::anyhow!("No data directory (HOME is unset)"))?;
+    let mut history: CommandHistoryFile = read_json(&path).ok().flatten().unwrap_or_default();
+    history.records.insert(0, record.clone());
+    history.records.truncate(capacity);
+    write_json(&path, &history)
+}
+
+fn command_history_path() -> Option<PathBuf> {
+    data_dir().map(|dir| dir.join(COMMAND_HISTORY_FILE))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CommandRecord, MAX_OUTPUT_BYTES, output_tail};
+
+    #[test]
+    fn formats_command_line_and_duration() {
+        let mut record = CommandRecord {
+            kind: "install".to_string(),
+            argv: vec!["brew".to_string(), "install".to_string(), "jq".to_string()],
+            started_at: 0,
+            duration_ms: 2_345,
+            success: true,
+            exit_code: Some(0),
+            stdout: String::new(),
+            stderr: String::new(),
+        };
+        assert_eq!(record.command_line(), "brew install jq");
+        assert_eq!(record.duration_label(), "2.3s");
+
+        record.duration_ms = 125_000;
+        assert_eq!(record.duration_label(), "2m05s");
+    }
+
+    #[test]
+    fn transcript_skips_empty_streams() {
+        let mut record = CommandRecord {
+            kind: "install".to_string(),
+            argv: vec!["brew".to_string(), "install".to_string(), "jq".to_string()],
+            started_at: 0,
+            duration_ms: 0,
+            success: true,
+            exit_code: Some(0),
+            stdout: "==> Pouring jq\n".to_string(),
+            stderr: "  \n".to_string(),
+        };
+        assert_eq!(record.transcript(), "$ brew install jq\n==> Pouring jq\n");
+
+        record.stderr = "Warning: already linked\n".to_string();
+        assert_eq!(
+            record.transcript(),
+            "$ brew install jq\n==> Pouring jq\nWarning: already linked\n"
+        );
+    }
+
+    #[test]
+    fn keeps_the_end_of_long_output() {
+        assert_eq!(output_tail("short\n".to_string()), "short\n");
+
+        let long = (0..10_000)
+            .map(|line| format!("line {line}\n"))
+            .collect::<String>();
+        let tail = output_tail(long);
+        assert!(tail.len() <= MAX_OUTPUT_BYTES + 32);
+        let mut lines = tail.lines();
+        assert_eq!(lines.next(), Some("[earlier output truncated]"));
+        assert!(lines.next().is_some_and(|line| line.starts_with("line ")));
+        assert!(tail.ends_with("line 9999\n"));
+    }
+}
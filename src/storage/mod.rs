@@ -0,0 +1,75 @@
+mod command_history;
+mod output_logs;
+mod paths;
+mod size_history;
+mod snapshot;
+
+pub use command_history::{CommandRecord, load_command_history, output_tail, record_command};
+pub use output_logs::save_output_log;
+pub use paths::{cache_dir, config_dir, data_dir};
+pub use size_history::{SizeBaseline, SizeHistory, record_size_snapshot};
+pub use snapshot::{FetchedAt, StartupSnapshot, load_snapshot, save_snapshot};
+
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+pub fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or(0)
+}
+
+/// Formats unix seconds as local `YYYY-MM-DD HH:MM:SS`.
+pub fn format_timestamp(unix_secs: u64) -> String {
+    let time = unix_secs as libc::time_t;
+    // SAFETY: `tm` is plain data that localtime_r fully initialises on success.
+    let mut tm: libc::tm = unsafe { std::mem::zeroed() };
+    if unsafe { libc::localtime_r(&time, &mut tm) }.is_null() {
+        return unix_secs.to_string();
+    }
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}:{:02}",
+        tm.tm_year + 1900,
+        tm.tm_mon + 1,
+        tm.tm_mday,
+        tm.tm_hour,
+        tm.tm_min,
+        tm.tm_sec
+    )
+}
+
+pub(crate) fn read_json<T: DeserializeOwned>(path: &Path) -> anyhow::Result<Option<T>> {
+    let bytes = match std::fs::read(path) {
+        Ok(bytes) => bytes,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(err.into()),
+    };
+    Ok(Some(serde_json::from_slice(&bytes)?))
+}
+
+/// Writes through a temporary sibling so a crash never leaves a truncated file behind.
+pub(crate) fn write_json<T: Serialize>(path: &Path, value: &T) -> anyhow::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let tmp = path.with_extension("json.tmp");
+    std::fs::write(&tmp, serde_json::to_vec(value)?)?;
+    std::fs::rename(&tmp, path)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::format_timestamp;
+
+    #[test]
+    fn formats_local_timestamps() {
+        let stamp = format_timestamp(1_700_000_000);
+        assert_eq!(stamp.len(), "2023-11-14 22:13:20".len());
+        assert!(stamp.starts_with("2023-11-1"), "{stamp}");
+    }
+}
@@ -0,0 +1,16 @@
+use std::path::PathBuf;
+
+use super::data_dir;
+
+const OUTPUT_LOG_DIR: &str = "logs";
+
+/// Writes `contents` to `<data_dir>/logs/<stem>.log` and returns the path.
+pub fn save_output_log(stem: &str, contents: &str) -> anyhow::Result<PathBuf> {
+    let dir = data_dir()
+        .ok_or_else(|| anyhow::anyhow!("No data directory (HOME is unset)"))?
+        .join(OUTPUT_LOG_DIR);
+    std::fs::create_dir_all(&dir)?;
+    let path = dir.join(format!("{stem}.log"));
+    std::fs::write(&path, contents)?;
+    Ok(path)
+}
@@ -0,0 +1,33 @@
+use std::ffi::OsString;
+use std::path::PathBuf;
+
+const APP_DIR: &str = "brewery";
+
+/// Directory for user settings (`$XDG_CONFIG_HOME/brewery`).
+pub fn config_dir() -> Option<PathBuf> {
+    xdg_dir("XDG_CONFIG_HOME", &[".config"])
+}
+
+/// Directory for data that should survive restarts (`$XDG_DATA_HOME/brewery`).
+pub fn data_dir() -> Option<PathBuf> {
+    xdg_dir("XDG_DATA_HOME", &[".local", "share"])
+}
+
+/// Directory for disposable data such as the startup snapshot (`$XDG_CACHE_HOME/brewery`).
+pub fn cache_dir() -> Option<PathBuf> {
+    xdg_dir("XDG_CACHE_HOME", &[".cache"])
+}
+
+fn xdg_dir(var: &str, home_fallback: &[&str]) -> Option<PathBuf> {
+    if let Some(base) = nonempty_env(var) {
+        return Some(PathBuf::from(base).join(APP_DIR));
+    }
+
+    let mut path = PathBuf::from(nonempty_env("HOME")?);
+    path.extend(home_fallback);
+    Some(path.join(APP_DIR))
+}
+
+fn nonempty_env(var: &str) -> Option<OsString> {
+    std::env::var_os(var).filter(|value| !value.is_empty())
+}
@@ -0,0 +1,274 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use super::{data_dir, read_json, unix_now, write_json};
+use crate::brew::SizeEntry;
+
+const SIZE_HISTORY_FILE: &str = "size_history.json";
+/// A new scan overwrites the newest one when that is less than this after the
+/// scan before it, so all but the newest are at least this far apart.
+const SNAPSHOT_INTERVAL_SECS: u64 = 3_600;
+/// At one scan per hour this still reaches back more than a week.
+const SIZE_HISTORY_CAPACITY: usize = 8 * 24;
+const WEEK_SECS: u64 = 7 * 86_400;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SizeSnapshot {
+    pub taken_at: u64,
+    pub entries: Vec<SizeEntry>,
+}
+
+impl SizeSnapshot {
+    pub fn total_kb(&self) -> u64 {
+        self.entries.iter().map(|entry| entry.size_kb).sum()
+    }
+
+    pub fn size_of(&self, name: &str) -> Option<u64> {
+        self.entries
+            .iter()
+            .find(|entry| entry.name == name)
+            .map(|entry| entry.size_kb)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SizeBaseline {
+    #[default]
+    LastScan,
+    LastWeek,
+}
+
+impl SizeBaseline {
+    pub fn next(self) -> Self {
+        match self {
+            Self::LastScan => Self::LastWeek,
+            Self::LastWeek => Self::LastScan,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::LastScan => "last scan",
+            Self::LastWeek => "last week",
+        }
+    }
+}
+
+/// Timestamped `fetch_sizes` results, oldest first.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct SizeHistory {
+    pub snapshots: Vec<SizeSnapshot>,
+}
+
+impl SizeHistory {
+    pub fn latest(&self) -> Option<&SizeSnapshot> {
+        self.snapshots.last()
+    }
+
+    /// Snapshot the latest scan is compared against.
+    pub fn baseline(&self, baseline: SizeBaseline) -> Option<&SizeSnapshot> {
+        let (latest, earlier) = self.snapshots.split_last()?;
+        match baseline {
+            SizeBaseline::LastScan => earlier.last(),
+            SizeBaseline::LastWeek => {
+                let cutoff = latest.taken_at.saturating_sub(WEEK_SECS);
+                earlier
+                    .iter()
+                    .rev()
+                    .find(|snapshot| snapshot.taken_at <= cutoff)
+                    .or_else(|| earlier.first())
+            }
+        }
+    }
+
+    pub fn total_delta_kb(&self, baseline: SizeBaseline) -> Option<i64> {
+        let latest = self.latest()?;
+        let base = self.baseline(baseline)?;
+        Some(latest.total_kb() as i64 - base.total_kb() as i64)
+    }
+
+    /// Per-package change against the baseline; packages new since then count
+    /// from zero and packages gone since then count down to it.
+    pub fn deltas_kb(&self, baseline: SizeBaseline) -> HashMap<String, i64> {
+        let (Some(latest), Some(base)) = (self.latest(), self.baseline(baseline)) else {
+            return HashMap::new();
+        };
+
+        let mut deltas: HashMap<String, i64> = base
+            .entries
+            .iter()
+            .map(|entry| (entry.name.clone(), -(entry.size_kb as i64)))
+            .collect();
+        for entry in &latest.entries {
+            *deltas.entry(entry.name.clone()).or_default() += entry.size_kb as i64;
+        }
+        deltas
+    }
+
+    /// Names with the largest absolute change against the baseline.
+    pub fn top_movers(&self, baseline: SizeBaseline, limit: usize) -> Vec<String> {
+        let mut movers: Vec<(String, i64)> = self
+            .deltas_kb(baseline)
+            .into_iter()
+            .filter(|(_, delta)| *delta != 0)
+            .collect();
+        movers.sort_by(|a, b| b.1.abs().cmp(&a.1.abs()).then_with(|| a.0.cmp(&b.0)));
+        movers
+            .into_iter()
+            .take(limit)
+            .map(|(name, _)| name)
+            .collect()
+    }
+
+    pub fn total_series(&self) -> Vec<u64> {
+        self.snapshots.iter().map(SizeSnapshot::total_kb).collect()
+    }
+
+    pub fn package_series(&self, name: &str) -> Vec<u64> {
+        self.snapshots
+            .iter()
+            .map(|snapshot| snapshot.size_of(name).unwrap_or(0))
+            .collect()
+    }
+
+    fn push(&mut self, snapshot: SizeSnapshot) {
+        if let [.., previous, latest] = self.snapshots.as_mut_slice()
+            && latest.taken_at.saturating_sub(previous.taken_at) < SNAPSHOT_INTERVAL_SECS
+        {
+            *latest = snapshot;
+            return;
+        }
+        self.snapshots.push(snapshot);
+        if self.snapshots.len() > SIZE_HISTORY_CAPACITY {
+            let excess = self.snapshots.len() - SIZE_HISTORY_CAPACITY;
+            self.snapshots.drain(..excess);
+        }
+    }
+}
+
+/// Adds a fresh scan to the on-disk history and returns the updated history.
+/// Blocking; async callers should run it on the blocking pool.
+pub fn record_size_snapshot(entries: &[SizeEntry]) -> anyhow::Result<SizeHistory> {
+    let path =
+        size_history_path().ok_or_else(|| anyhow::anyhow!("No data directory (HOME is unset)"))?;
+    let mut history: SizeHistory = read_json(&path).ok().flatten().unwrap_or_default();
+    history.push(SizeSnapshot {
+        taken_at: unix_now(),
+        entries: entries.to_vec(),
+    });
+    write_json(&path, &history)?;
+    Ok(history)
+}
+
+fn size_history_path() -> Option<PathBuf> {
+    data_dir().map(|dir| dir.join(SIZE_HISTORY_FILE))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SIZE_HISTORY_CAPACITY, SizeBaseline, SizeHistory, SizeSnapshot, WEEK_SECS};
+    use crate::brew::SizeEntry;
+
+    fn snapshot(taken_at: u64, entries: &[(&str, u64)]) -> SizeSnapshot {
+        SizeSnapshot {
+            taken_at,
+            entries: entries
+                .iter()
+                .map(|(name, size_kb)| SizeEntry {
+                    name: (*name).to_string(),
+                    size_kb: *size_kb,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn compares_against_previous_scan() {
+        let history = SizeHistory {
+            snapshots: vec![
+                snapshot(100, &[("git", 10), ("node", 50), ("wget", 8)]),
+                snapshot(200, &[("git", 12), ("node", 40), ("ripgrep", 5)]),
+            ],
+        };
+
+        assert_eq!(history.total_delta_kb(SizeBaseline::LastScan), Some(-11));
+        let deltas = history.deltas_kb(SizeBaseline::LastScan);
+        assert_eq!(deltas.get("git"), Some(&2));
+        assert_eq!(deltas.get("node"), Some(&-10));
+        assert_eq!(deltas.get("ripgrep"), Some(&5));
+        assert_eq!(deltas.get("wget"), Some(&-8));
+        assert_eq!(
+            history.top_movers(SizeBaseline::LastScan, 2),
+            vec!["node".to_string(), "wget".to_string()]
+        );
+    }
+
+    #[test]
+    fn week_baseline_picks_newest_scan_at_least_a_week_old() {
+        let latest = 10 * WEEK_SECS;
+        let history = SizeHistory {
+            snapshots: vec![
+                snapshot(latest - 3 * WEEK_SECS, &[("git", 1)]),
+                snapshot(latest - WEEK_SECS - 10, &[("git", 5)]),
+                snapshot(latest - 60, &[("git", 9)]),
+                snapshot(latest, &[("git", 10)]),
+            ],
+        };
+
+        assert_eq!(history.total_delta_kb(SizeBaseline::LastWeek), Some(5));
+        assert_eq!(history.total_delta_kb(SizeBaseline::LastScan), Some(1));
+    }
+
+    #[test]
+    fn week_baseline_falls_back_to_oldest_scan() {
+        let history = SizeHistory {
+            snapshots: vec![
+                snapshot(100, &[("git", 4)]),
+                snapshot(200, &[("git", 6)]),
+                snapshot(300, &[("git", 7)]),
+            ],
+        };
+
+        assert_eq!(history.total_delta_kb(SizeBaseline::LastWeek), Some(3));
+    }
+
+    #[test]
+    fn single_scan_has_no_baseline() {
+        let history = SizeHistory {
+            snapshots: vec![snapshot(100, &[("git", 4)])],
+        };
+
+        assert_eq!(history.total_delta_kb(SizeBaseline::LastScan), None);
+        assert!(history.top_movers(SizeBaseline::LastScan, 3).is_empty());
+    }
+
+    #[test]
+    fn scans_within_an_hour_replace_the_latest() {
+        let mut history = SizeHistory::default();
+        history.push(snapshot(0, &[("git", 1)]));
+        history.push(snapshot(60, &[("git", 2)]));
+        history.push(snapshot(3_000, &[("git", 3)]));
+        history.push(snapshot(3_700, &[("git", 4)]));
+        history.push(snapshot(4_000, &[("git", 5)]));
+
+        assert_eq!(history.total_series(), vec![1, 4, 5]);
+        assert_eq!(history.latest().map(|latest| latest.taken_at), Some(4_000));
+    }
+
+    #[test]
+    fn frequent_scans_keep_a_week_old_baseline() {
+        let mut history = SizeHistory::default();
+        for hour in 0..2 * SIZE_HISTORY_CAPACITY as u64 {
+            for minute in [0, 20, 40] {
+                history.push(snapshot(hour * 3_600 + minute * 60, &[("git", hour)]));
+            }
+        }
+
+        let latest = history.latest().unwrap().taken_at;
+        let week = history.baseline(SizeBaseline::LastWeek).unwrap();
+        assert!(week.taken_at <= latest - WEEK_SECS);
+        assert!(history.snapshots.len() <= SIZE_HISTORY_CAPACITY);
+    }
+}
@@ -0,0 +1,137 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use super::{cache_dir, read_json, unix_now, write_json};
+use crate::brew::{Details, SizeEntry, StatusSnapshot};
+
+const SNAPSHOT_FILE: &str = "snapshot.json";
+
+/// Last known inventory, shown on launch until the live fetches land.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct StartupSnapshot {
+    pub saved_at: u64,
+    pub leaves: Vec<String>,
+    pub casks: Vec<String>,
+    pub sizes: Vec<SizeEntry>,
+    pub status: Option<StatusSnapshot>,
+    /// Package details, most recently used first.
+    pub details: Vec<(String, Details)>,
+    #[serde(default)]
+    pub fetched_at: FetchedAt,
+}
+
+/// Unix time each section was last fetched live, which can be earlier than
+/// `saved_at` when a section was carried over from the previous snapshot.
+/// Zero (files written before these were kept) means `saved_at`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FetchedAt {
+    pub leaves: u64,
+    pub casks: u64,
+    pub sizes: u64,
+    pub status: u64,
+    pub details: HashMap<String, u64>,
+}
+
+impl StartupSnapshot {
+    /// Drops sections fetched more than `ttl` ago and fills in missing times;
+    /// `None` once nothing is left.
+    pub fn unexpired(mut self, now: u64, ttl: Duration) -> Option<Self> {
+        let saved_at = self.saved_at;
+        let resolve = |fetched_at: &mut u64| {
+            if *fetched_at == 0 {
+                *fetched_at = saved_at;
+            }
+            now.saturating_sub(*fetched_at) > ttl.as_secs()
+        };
+        let at = &mut self.fetched_at;
+        if resolve(&mut at.leaves) {
+            self.leaves.clear();
+        }
+        if resolve(&mut at.casks) {
+            self.casks.clear();
+        }
+        if resolve(&mut at.sizes) {
+            self.sizes.clear();
+        }
+        if resolve(&mut at.status) {
+            self.status = None;
+        }
+        self.details.retain(|(pkg, _)| {
+            let fetched_at = at.details.entry(pkg.clone()).or_default();
+            !resolve(fetched_at)
+        });
+        at.details
+            .retain(|pkg, _| self.details.iter().any(|(kept, _)| kept == pkg));
+        self.has_data().then_some(self)
+    }
+
+    pub fn has_data(&self) -> bool {
+        !self.leaves.is_empty()
+            || !self.casks.is_empty()
+            || !self.sizes.is_empty()
+            || self.status.is_some()
+            || !self.details.is_empty()
+    }
+}
+
+/// Returns the saved snapshot without sections older than `ttl`, or `None` if
+/// it is missing, unreadable, or entirely expired.
+pub fn load_snapshot(ttl: Duration) -> Option<StartupSnapshot> {
+    let snapshot: StartupSnapshot = read_json(&snapshot_path()?).ok().flatten()?;
+    snapshot.unexpired(unix_now(), ttl)
+}
+
+pub fn save_snapshot(snapshot: &StartupSnapshot) -> anyhow::Result<()> {
+    let path =
+        snapshot_path().ok_or_else(|| anyhow::anyhow!("No cache directory (HOME is unset)"))?;
+    write_json(&path, snapshot)
+}
+
+fn snapshot_path() -> Option<PathBuf> {
+    cache_dir().map(|dir| dir.join(SNAPSHOT_FILE))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{FetchedAt, StartupSnapshot};
+
+    #[test]
+    fn snapshot_expires_after_ttl() {
+        let snapshot = StartupSnapshot {
+            saved_at: 1_000,
+            leaves: vec!["jq".to_string()],
+            ..StartupSnapshot::default()
+        };
+        let ttl = Duration::from_secs(60);
+        assert!(snapshot.clone().unexpired(1_060, ttl).is_some());
+        assert!(snapshot.clone().unexpired(1_061, ttl).is_none());
+
+        let json = serde_json::to_string(&snapshot).unwrap();
+        let restored: StartupSnapshot = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.leaves, vec!["jq".to_string()]);
+    }
+
+    #[test]
+    fn sections_expire_by_their_own_fetch_time() {
+        let snapshot = StartupSnapshot {
+            saved_at: 1_000,
+            leaves: vec!["jq".to_string()],
+            casks: vec!["firefox".to_string()],
+            fetched_at: FetchedAt {
+                leaves: 900,
+                ..FetchedAt::default()
+            },
+            ..StartupSnapshot::default()
+        };
+
+        let kept = snapshot.unexpired(1_050, Duration::from_secs(60)).unwrap();
+        assert!(kept.leaves.is_empty());
+        assert_eq!(kept.casks, vec!["firefox".to_string()]);
+        assert_eq!(kept.fetched_at.casks, 1_000);
+    }
+}
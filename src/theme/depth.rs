@@ -0,0 +1,285 @@
+use ratatui::style::Color;
+use serde::Deserialize;
+
+use super::Theme;
+
+/// How many colours the terminal can show.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ColorDepth {
+    TrueColor,
+    Ansi256,
+    Ansi16,
+    Monochrome,
+}
+
+impl ColorDepth {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::TrueColor => "truecolor",
+            Self::Ansi256 => "256",
+            Self::Ansi16 => "16",
+            Self::Monochrome => "none",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+pub enum ColorMode {
+    #[default]
+    #[serde(rename = "auto")]
+    Auto,
+    #[serde(rename = "truecolor")]
+    TrueColor,
+    #[serde(rename = "256")]
+    Ansi256,
+    #[serde(rename = "16")]
+    Ansi16,
+    #[serde(rename = "none")]
+    None,
+}
+
+impl ColorMode {
+    pub fn resolve(self) -> ColorDepth {
+        match self {
+            Self::Auto => detect_color_depth(),
+            Self::TrueColor => ColorDepth::TrueColor,
+            Self::Ansi256 => ColorDepth::Ansi256,
+            Self::Ansi16 => ColorDepth::Ansi16,
+            Self::None => ColorDepth::Monochrome,
+        }
+    }
+}
+
+pub fn detect_color_depth() -> ColorDepth {
+    let var = |name| std::env::var(name).unwrap_or_default();
+    detect_from_env(
+        &var("NO_COLOR"),
+        &var("COLORTERM"),
+        &var("TERM"),
+        &var("TERM_PROGRAM"),
+    )
+}
+
+fn detect_from_env(no_color: &str, colorterm: &str, term: &str, term_program: &str) -> ColorDepth {
+    // https://no-color.org: any non-empty value disables colour.
+    if !no_color.is_empty() || term == "dumb" {
+        return ColorDepth::Monochrome;
+    }
+
+    let colorterm = colorterm.to_ascii_lowercase();
+    if colorterm == "truecolor" || colorterm == "24bit" {
+        return ColorDepth::TrueColor;
+    }
+
+    let term = term.to_ascii_lowercase();
+    const TRUECOLOR_TERMS: [&str; 7] = [
+        "direct",
+        "truecolor",
+        "kitty",
+        "alacritty",
+        "wezterm",
+        "ghostty",
+        "foot",
+    ];
+    if TRUECOLOR_TERMS.iter().any(|name| term.contains(name))
+        || matches!(term_program, "iTerm.app" | "WezTerm" | "vscode" | "ghostty")
+    {
+        return ColorDepth::TrueColor;
+    }
+
+    if term.contains("256color") || term.starts_with("xterm") {
+        return ColorDepth::Ansi256;
+    }
+
+    ColorDepth::Ansi16
+}
+
+impl Theme {
+    /// Maps every RGB colour down to what `depth` can show. Monochrome keeps the
+    /// palette so the renderer can tell roles apart when swapping colour for attributes.
+    pub fn for_depth(self, depth: ColorDepth) -> Theme {
+        match depth {
+            ColorDepth::TrueColor | ColorDepth::Monochrome => self,
+            ColorDepth::Ansi256 => self.map_colors(to_ansi256),
+            ColorDepth::Ansi16 => {
+                let bg_panel = to_ansi16(self.bg_panel, None);
+                let bg_main = to_ansi16(self.bg_main, None);
+                let mut theme = self.map_colors(|color| to_ansi16(color, Some(bg_panel)));
+                // Backgrounds may share a colour, everything drawn on them may not.
+                theme.bg_main = bg_main;
+                theme.bg_panel = bg_panel;
+                theme.bg_dim = to_ansi16(self.bg_dim, None);
+                theme
+            }
+        }
+    }
+
+    fn map_colors(self, map: impl Fn(Color) -> Color) -> Theme {
+        Theme {
+            accent: map(self.accent),
+            accent_secondary: map(self.accent_secondary),
+            green: map(self.green),
+            yellow: map(self.yellow),
+            red: map(self.red),
+            orange: map(self.orange),
+            text_primary: map(self.text_primary),
+            text_secondary: map(self.text_secondary),
+            text_muted: map(self.text_muted),
+            bg_main: map(self.bg_main),
+            bg_panel: map(self.bg_panel),
+            bg_selection: map(self.bg_selection),
+            bg_dim: map(self.bg_dim),
+            border: map(self.border),
+            border_active: map(self.border_active),
+            text_on_accent: map(self.text_on_accent),
+        }
+    }
+}
+
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+fn distance(a: (u8, u8, u8), b: (u8, u8, u8)) -> u32 {
+    let d = |x: u8, y: u8| (i32::from(x) - i32::from(y)).pow(2) as u32;
+    d(a.0, b.0) + d(a.1, b.1) + d(a.2, b.2)
+}
+
+/// Nearest entry in the xterm 6x6x6 cube or grey ramp.
+fn to_ansi256(color: Color) -> Color {
+    let Color::Rgb(r, g, b) = color else {
+        return color;
+    };
+
+    let level = |value: u8| {
+        (0..CUBE_LEVELS.len())
+            .min_by_key(|idx| (i32::from(CUBE_LEVELS[*idx]) - i32::from(value)).abs())
+            .unwrap_or(0)
+    };
+    let (ri, gi, bi) = (level(r), level(g), level(b));
+    let cube_rgb = (CUBE_LEVELS[ri], CUBE_LEVELS[gi], CUBE_LEVELS[bi]);
+    let cube_index = 16 + 36 * ri + 6 * gi + bi;
+
+    let average = (u32::from(r) + u32::from(g) + u32::from(b)) / 3;
+    let gray_step = ((average as i32 - 3) / 10).clamp(0, 23) as u8;
+    let gray_value = 8 + 10 * gray_step;
+    let gray_index = 232 + usize::from(gray_step);
+
+    if distance((r, g, b), (gray_value, gray_value, gray_value)) < distance((r, g, b), cube_rgb) {
+        Color::Indexed(gray_index as u8)
+    } else {
+        Color::Indexed(cube_index as u8)
+    }
+}
+
+/// Picks a basic ANSI colour by hue for saturated colours and by lightness for greys,
+/// avoiding `avoid` so text never vanishes into its background.
+fn to_ansi16(color: Color, avoid: Option<Color>) -> Color {
+    let Color::Rgb(r, g, b) = color else {
+        return color;
+    };
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let chroma = max - min;
+
+    let candidates: Vec<Color> = if chroma >= 40 && max >= 90 {
+        let hue = hue_degrees(r, g, b, max, chroma);
+        let (normal, bright) = match ((hue / 60.0).round() as u32) % 6 {
+            0 => (Color::Red, Color::LightRed),
+            1 => (Color::Yellow, Color::LightYellow),
+            2 => (Color::Green, Color::LightGreen),
+            3 => (Color::Cyan, Color::LightCyan),
+            4 => (Color::Blue, Color::LightBlue),
+            _ => (Color::Magenta, Color::LightMagenta),
+        };
+        if max >= 230 {
+            vec![bright, normal]
+        } else {
+            vec![normal, bright]
+        }
+    } else {
+        let luma = (299 * u32::from(r) + 587 * u32::from(g) + 114 * u32::from(b)) / 1000;
+        let mut grays = [
+            (0, Color::Black),
+            (127, Color::DarkGray),
+            (229, Color::Gray),
+            (255, Color::White),
+        ];
+        grays.sort_by_key(|(level, _)| (i64::from(*level) - i64::from(luma)).abs());
+        grays.into_iter().map(|(_, color)| color).collect()
+    };
+
+    candidates
+        .iter()
+        .copied()
+        .find(|candidate| Some(*candidate) != avoid)
+        .unwrap_or(candidates[0])
+}
+
+fn hue_degrees(r: u8, g: u8, b: u8, max: u8, chroma: u8) -> f32 {
+    let (r, g, b, chroma) = (f32::from(r), f32::from(g), f32::from(b), f32::from(chroma));
+    let hue = if f32::from(max) == r {
+        60.0 * ((g - b) / chroma)
+    } else if f32::from(max) == g {
+        60.0 * ((b - r) / chroma) + 120.0
+    } else {
+        60.0 * ((r - g) / chroma) + 240.0
+    };
+    hue.rem_euclid(360.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::style::Color;
+
+    use super::{ColorDepth, Theme, detect_from_env, to_ansi16, to_ansi256};
+
+    #[test]
+    fn detects_depth_from_environment() {
+        assert_eq!(
+            detect_from_env("1", "truecolor", "xterm-256color", ""),
+            ColorDepth::Monochrome
+        );
+        assert_eq!(
+            detect_from_env("", "truecolor", "screen", ""),
+            ColorDepth::TrueColor
+        );
+        assert_eq!(
+            detect_from_env("", "", "tmux-256color", ""),
+            ColorDepth::Ansi256
+        );
+        assert_eq!(detect_from_env("", "", "linux", ""), ColorDepth::Ansi16);
+        assert_eq!(detect_from_env("", "", "dumb", ""), ColorDepth::Monochrome);
+        assert_eq!(
+            detect_from_env("", "", "xterm-kitty", ""),
+            ColorDepth::TrueColor
+        );
+    }
+
+    #[test]
+    fn maps_to_ansi256_cube_and_grays() {
+        assert_eq!(to_ansi256(Color::Rgb(255, 0, 0)), Color::Indexed(196));
+        assert_eq!(to_ansi256(Color::Rgb(0, 0, 0)), Color::Indexed(16));
+        assert_eq!(to_ansi256(Color::Rgb(128, 128, 128)), Color::Indexed(244));
+        assert_eq!(to_ansi256(Color::Reset), Color::Reset);
+    }
+
+    #[test]
+    fn maps_to_ansi16_by_hue_and_lightness() {
+        assert_eq!(to_ansi16(Color::Rgb(200, 155, 80), None), Color::Yellow);
+        assert_eq!(to_ansi16(Color::Rgb(185, 110, 100), None), Color::Red);
+        assert_eq!(to_ansi16(Color::Rgb(30, 26, 22), None), Color::Black);
+        assert_eq!(
+            to_ansi16(Color::Rgb(60, 52, 45), Some(Color::Black)),
+            Color::DarkGray
+        );
+    }
+
+    #[test]
+    fn ansi16_keeps_selection_and_text_off_the_panel_colour() {
+        for theme in [Theme::dark(), Theme::light()] {
+            let mapped = theme.for_depth(ColorDepth::Ansi16);
+            assert_ne!(mapped.bg_selection, mapped.bg_panel);
+            assert_ne!(mapped.text_primary, mapped.bg_panel);
+            assert_ne!(mapped.border, mapped.bg_panel);
+        }
+    }
+}
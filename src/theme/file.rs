@@ -0,0 +1,199 @@
+use std::path::PathBuf;
+
+use ratatui::style::Color;
+use serde::Deserialize;
+
+use super::Theme;
+use crate::storage::config_dir;
+
+const THEMES_DIR: &str = "themes";
+
+/// Presets shipped with Brewery, in the same format as user theme files.
+const PRESETS: &[(&str, &str)] = &[
+    ("high-contrast", include_str!("presets/high-contrast.toml")),
+    (
+        "solarized-dark",
+        include_str!("presets/solarized-dark.toml"),
+    ),
+    (
+        "solarized-light",
+        include_str!("presets/solarized-light.toml"),
+    ),
+    ("gruvbox-dark", include_str!("presets/gruvbox-dark.toml")),
+    ("gruvbox-light", include_str!("presets/gruvbox-light.toml")),
+];
+
+/// `#rrggbb` or `#rgb`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct HexColor(Color);
+
+impl<'de> Deserialize<'de> for HexColor {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        parse_hex(&text)
+            .map(HexColor)
+            .ok_or_else(|| serde::de::Error::custom(format!("invalid hex colour `{text}`")))
+    }
+}
+
+fn parse_hex(text: &str) -> Option<Color> {
+    let digits = text.strip_prefix('#')?;
+    if !digits.chars().all(|ch| ch.is_ascii_hexdigit()) {
+        return None;
+    }
+    let channel = |range: std::ops::Range<usize>| u8::from_str_radix(&digits[range], 16).ok();
+    match digits.len() {
+        6 => Some(Color::Rgb(channel(0..2)?, channel(2..4)?, channel(4..6)?)),
+        3 => {
+            let short = |idx: usize| channel(idx..idx + 1).map(|value| value * 17);
+            Some(Color::Rgb(short(0)?, short(1)?, short(2)?))
+        }
+        _ => None,
+    }
+}
+
+macro_rules! theme_file {
+    ($($field:ident),* $(,)?) => {
+        /// A theme file: any `Theme` field as a hex colour, the rest taken from `base`.
+        #[derive(Debug, Deserialize)]
+        #[serde(deny_unknown_fields)]
+        struct ThemeFile {
+            base: Option<String>,
+            $($field: Option<HexColor>,)*
+        }
+
+        impl ThemeFile {
+            fn apply(self, mut theme: Theme) -> Theme {
+                $(if let Some(color) = self.$field {
+                    theme.$field = color.0;
+                })*
+                theme
+            }
+        }
+    };
+}
+
+theme_file!(
+    accent,
+    accent_secondary,
+    green,
+    yellow,
+    red,
+    orange,
+    text_primary,
+    text_secondary,
+    text_muted,
+    bg_main,
+    bg_panel,
+    bg_selection,
+    bg_dim,
+    border,
+    border_active,
+    text_on_accent,
+);
+
+fn parse_theme(text: &str) -> anyhow::Result<Theme> {
+    let file: ThemeFile = toml::from_str(text)?;
+    let base = match file.base.as_deref().unwrap_or("dark") {
+        "light" => Theme::light(),
+        "dark" => Theme::dark(),
+        other => anyhow::bail!("base must be `light` or `dark`, got `{other}`"),
+    };
+    Ok(file.apply(base))
+}
+
+fn user_themes_dir() -> Option<PathBuf> {
+    config_dir().map(|dir| dir.join(THEMES_DIR))
+}
+
+/// Loads a theme by name; a file in the user themes directory wins over a bundled preset.
+pub fn load_theme(name: &str) -> anyhow::Result<Theme> {
+    if let Some(path) = user_themes_dir().map(|dir| dir.join(format!("{name}.toml")))
+        && path.is_file()
+    {
+        let text = std::fs::read_to_string(&path)?;
+        return parse_theme(&text)
+            .map_err(|err| anyhow::anyhow!("Invalid theme {}: {err}", path.display()));
+    }
+
+    match name {
+        "light" => Ok(Theme::light()),
+        "dark" => Ok(Theme::dark()),
+        _ => {
+            let (_, text) = PRESETS
+                .iter()
+                .find(|(preset, _)| *preset == name)
+                .ok_or_else(|| anyhow::anyhow!("Unknown theme `{name}`"))?;
+            parse_theme(text)
+        }
+    }
+}
+
+/// Every selectable theme: `auto`, the built-in palettes, presets, then user files.
+pub fn theme_names() -> Vec<String> {
+    let mut names: Vec<String> = ["auto", "light", "dark"]
+        .into_iter()
+        .chain(PRESETS.iter().map(|(name, _)| *name))
+        .map(str::to_string)
+        .collect();
+
+    let mut user_names: Vec<String> = user_themes_dir()
+        .and_then(|dir| std::fs::read_dir(dir).ok())
+        .into_iter()
+        .flatten()
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "toml"))
+        .filter_map(|path| path.file_stem()?.to_str().map(str::to_string))
+        .filter(|name| !names.contains(name))
+        .collect();
+    user_names.sort();
+    names.extend(user_names);
+    names
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::style::Color;
+
+    use super::{PRESETS, Theme, parse_hex, parse_theme};
+
+    #[test]
+    fn bundled_presets_parse() {
+        for (name, text) in PRESETS {
+            assert!(parse_theme(text).is_ok(), "preset {name} should parse");
+        }
+    }
+
+    #[test]
+    fn parses_hex_colours() {
+        assert_eq!(parse_hex("#ff8000"), Some(Color::Rgb(255, 128, 0)));
+        assert_eq!(parse_hex("#f80"), Some(Color::Rgb(255, 136, 0)));
+        assert_eq!(parse_hex("ff8000"), None);
+        assert_eq!(parse_hex("#ff80"), None);
+        assert_eq!(parse_hex("#gg0000"), None);
+    }
+
+    #[test]
+    fn unset_fields_come_from_base() {
+        let theme = parse_theme("base = \"light\"\naccent = \"#000000\"\n").expect("should parse");
+        assert_eq!(theme.accent, Color::Rgb(0, 0, 0));
+        assert_eq!(theme.bg_main, Theme::light().bg_main);
+    }
+
+    #[test]
+    fn rejects_unknown_fields_and_bad_colours() {
+        let err = parse_theme("accnt = \"#000000\"\n")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("unknown field `accnt`"), "{err}");
+
+        let err = parse_theme("accent = \"red\"\n").unwrap_err().to_string();
+        assert!(err.contains("invalid hex colour `red`"), "{err}");
+
+        let err = parse_theme("base = \"solarized\"\n")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("base must be"), "{err}");
+    }
+}
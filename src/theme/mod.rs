@@ -0,0 +1,235 @@
+mod depth;
+mod file;
+
+use std::sync::atomic::{AtomicU8, Ordering};
+
+use ratatui::style::Color;
+
+pub use depth::{ColorDepth, ColorMode};
+pub use file::{load_theme, theme_names};
+
+#[derive(Clone, Copy, Debug)]
+pub struct Theme {
+    // Primary
+    pub accent: Color,
+    pub accent_secondary: Color,
+    pub green: Color,
+    pub yellow: Color,
+    pub red: Color,
+    pub orange: Color,
+
+    // Text colors
+    pub text_primary: Color,
+    pub text_secondary: Color,
+    pub text_muted: Color,
+
+    // Backgrounds
+    pub bg_main: Color,
+    pub bg_panel: Color,
+    pub bg_selection: Color,
+    pub bg_dim: Color,
+
+    // UI elements
+    pub border: Color,
+    pub border_active: Color,
+    pub text_on_accent: Color,
+}
+
+impl Theme {
+    pub fn light() -> Self {
+        Self {
+            accent: Color::Rgb(180, 130, 60),
+            accent_secondary: Color::Rgb(150, 100, 65),
+            green: Color::Rgb(100, 140, 80),
+            yellow: Color::Rgb(190, 160, 100),
+            red: Color::Rgb(180, 100, 90),
+            orange: Color::Rgb(175, 125, 80),
+
+            text_primary: Color::Rgb(60, 50, 40),
+            text_secondary: Color::Rgb(110, 95, 80),
+            text_muted: Color::Rgb(150, 135, 120),
+
+            bg_main: Color::Rgb(252, 249, 242),
+            bg_panel: Color::Rgb(248, 244, 235),
+            bg_selection: Color::Rgb(230, 220, 200),
+            bg_dim: Color::Rgb(180, 175, 165),
+
+            border: Color::Rgb(200, 185, 165),
+            border_active: Color::Rgb(180, 130, 60),
+            text_on_accent: Color::Rgb(255, 252, 245),
+        }
+    }
+
+    pub fn dark() -> Self {
+        Self {
+            accent: Color::Rgb(200, 155, 80),
+            accent_secondary: Color::Rgb(175, 120, 75),
+            green: Color::Rgb(130, 165, 95),
+            yellow: Color::Rgb(195, 165, 105),
+            red: Color::Rgb(185, 110, 100),
+            orange: Color::Rgb(180, 130, 85),
+
+            text_primary: Color::Rgb(210, 200, 185),
+            text_secondary: Color::Rgb(145, 135, 120),
+            text_muted: Color::Rgb(105, 95, 85),
+
+            bg_main: Color::Rgb(30, 26, 22),
+            bg_panel: Color::Rgb(38, 33, 28),
+            bg_selection: Color::Rgb(60, 52, 45),
+            bg_dim: Color::Rgb(18, 15, 12),
+
+            border: Color::Rgb(65, 58, 50),
+            border_active: Color::Rgb(200, 155, 80),
+            text_on_accent: Color::Rgb(30, 26, 22),
+        }
+    }
+}
+
+/// `auto` follows the system appearance; anything else names a theme from `theme_names`.
+#[derive(Clone, PartialEq, Eq, Debug, Default, serde::Deserialize)]
+#[serde(from = "String")]
+pub enum ThemeMode {
+    #[default]
+    Auto,
+    Named(String),
+}
+
+impl From<String> for ThemeMode {
+    fn from(name: String) -> Self {
+        if name == "auto" {
+            Self::Auto
+        } else {
+            Self::Named(name)
+        }
+    }
+}
+
+impl ThemeMode {
+    pub fn label(&self) -> &str {
+        match self {
+            Self::Auto => "auto",
+            Self::Named(name) => name,
+        }
+    }
+
+    pub fn resolve(&self) -> anyhow::Result<Theme> {
+        match self {
+            Self::Auto => Ok(detect_system_theme()),
+            Self::Named(name) => load_theme(name),
+        }
+    }
+}
+
+/// Light or dark, as reported by the terminal itself.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Appearance {
+    Dark,
+    Light,
+}
+
+impl Appearance {
+    /// Dark when the background's relative luminance is below one half.
+    pub fn from_background(r: u8, g: u8, b: u8) -> Self {
+        let luminance =
+            (0.2126 * f32::from(r) + 0.7152 * f32::from(g) + 0.0722 * f32::from(b)) / 255.0;
+        if luminance < 0.5 {
+            Self::Dark
+        } else {
+            Self::Light
+        }
+    }
+
+    fn theme(self) -> Theme {
+        match self {
+            Self::Dark => Theme::dark(),
+            Self::Light => Theme::light(),
+        }
+    }
+}
+
+/// 0 = unknown; set from the OSC 11 reply or mode 2031 reports.
+static TERMINAL_APPEARANCE: AtomicU8 = AtomicU8::new(0);
+
+pub fn set_terminal_appearance(appearance: Appearance) {
+    let value = match appearance {
+        Appearance::Dark => 1,
+        Appearance::Light => 2,
+    };
+    TERMINAL_APPEARANCE.store(value, Ordering::Relaxed);
+}
+
+fn terminal_appearance() -> Option<Appearance> {
+    match TERMINAL_APPEARANCE.load(Ordering::Relaxed) {
+        1 => Some(Appearance::Dark),
+        2 => Some(Appearance::Light),
+        _ => None,
+    }
+}
+
+/// The terminal's own report wins; otherwise ask the platform.
+pub fn detect_system_theme() -> Theme {
+    terminal_appearance()
+        .map(Appearance::theme)
+        .unwrap_or_else(detect_platform_theme)
+}
+
+#[cfg(target_os = "macos")]
+fn detect_platform_theme() -> Theme {
+    if let Ok(output) = std::process::Command::new("defaults")
+        .args(["read", "-g", "AppleInterfaceStyle"])
+        .output()
+        && output.status.success()
+    {
+        let style = String::from_utf8_lossy(&output.stdout);
+        if style.trim().eq_ignore_ascii_case("dark") {
+            return Theme::dark();
+        }
+    }
+    Theme::light()
+}
+
+#[cfg(not(target_os = "macos"))]
+fn detect_platform_theme() -> Theme {
+    // Check COLORFGBG env var
+    if let Ok(colorfgbg) = std::env::var("COLORFGBG")
+        && let Some(bg) = colorfgbg.split(';').next_back()
+        && let Ok(bg_num) = bg.parse::<u8>()
+    {
+        if bg_num == 0 || (8..=15).contains(&bg_num) {
+            return Theme::dark();
+        }
+        return Theme::light();
+    }
+
+    // Check for common dark mode indicators
+    if let Ok(term) = std::env::var("TERM_PROGRAM") {
+        let term_lower = term.to_lowercase();
+        if (term_lower.contains("iterm")
+            || term_lower.contains("alacritty")
+            || term_lower.contains("kitty"))
+            && let Ok(appearance) = std::env::var("TERM_PROGRAM_VERSION")
+            && appearance.contains("light")
+        {
+            return Theme::light();
+        }
+    }
+
+    Theme::light()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Appearance;
+
+    #[test]
+    fn background_luminance_picks_appearance() {
+        assert_eq!(Appearance::from_background(0, 0, 0), Appearance::Dark);
+        assert_eq!(Appearance::from_background(40, 42, 54), Appearance::Dark);
+        assert_eq!(
+            Appearance::from_background(253, 246, 227),
+            Appearance::Light
+        );
+        // Saturated blue is dark even though one channel is maxed out.
+        assert_eq!(Appearance::from_background(0, 0, 255), Appearance::Dark);
+    }
+}
@@ -3,9 +3,18 @@ use ratatui::style::{Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Paragraph, Wrap};
 
-use crate::app::{App, InputMode, StatusTab, ViewMode};
+use crate::app::{App, InputMode, PendingZap, StatusTab, ViewMode};
+use crate::brew::{
+    CaskInfo, Deprecation, DeprecationState, Details, FormulaMetadata, IssueSeverity, PackagePaths,
+    ServiceEntry, ServiceRegistration, StanzaItem, copy_only_reason, is_shell_snippet,
+};
+use crate::keymap::Action;
+use crate::storage::format_timestamp;
 use crate::ui::util::{format_size, symbol};
 
+/// Conflicting paths listed inline before pointing at the pager.
+const MAX_LINK_CONFLICTS: usize = 10;
+
 pub fn draw_details_panel(frame: &mut ratatui::Frame, area: Rect, app: &App, is_focused: bool) {
     let theme = &app.theme;
 
@@ -16,6 +25,12 @@ pub fn draw_details_panel(frame: &mut ratatui::Frame, area: Rect, app: &App, is_
         build_details_lines(app, app.selected_package_result())
     } else if app.status_tab == StatusTab::Services {
         build_service_details_lines(app)
+    } else if app.status_tab == StatusTab::Issues && app.selected_issue().is_some() {
+        build_issue_details_lines(app)
+    } else if app.status_tab == StatusTab::Issues
+        && let Some(entry) = app.selected_deprecated_package()
+    {
+        build_details_lines(app, Some(entry.name.as_str()))
     } else {
         match app.view_mode {
             ViewMode::Details => build_details_lines(app, app.selected_package_name()),
@@ -107,6 +122,44 @@ fn build_details_lines(app: &App, pkg: Option<&str>) -> Vec<Line<'static>> {
             .add_modifier(Modifier::BOLD),
     )));
 
+    // Details are fresher than the last status check when both exist.
+    let deprecation = match app.details_cache.peek(pkg) {
+        Some(details) => details.deprecation.as_ref(),
+        None => app
+            .deprecated_packages()
+            .iter()
+            .find(|entry| entry.name == pkg)
+            .map(|entry| &entry.deprecation),
+    };
+    if let Some(deprecation) = deprecation {
+        lines.extend(build_deprecation_lines(app, deprecation));
+    }
+
+    let install_caveats = app
+        .install_caveats
+        .as_ref()
+        .filter(|(installed, _)| installed == pkg)
+        .map(|(_, caveats)| caveats.as_str());
+    if let Some(caveats) = install_caveats {
+        lines.extend(build_caveat_lines(
+            app,
+            &format!("{} Installed, caveats:", symbol(app, "✓", "ok")),
+            caveats,
+        ));
+    }
+
+    if let Some(pending) = app
+        .pending_zap
+        .as_ref()
+        .filter(|pending| pending.cask() == pkg)
+        && let Some(cask) = app
+            .details_cache
+            .peek(pkg)
+            .and_then(|details| details.cask.as_ref())
+    {
+        lines.extend(build_zap_preview_lines(app, pending, cask));
+    }
+
     if let Some(details) = app.details_cache.peek(pkg) {
         if let Some(desc) = details.desc.as_ref() {
             lines.push(Line::from(""));
@@ -118,10 +171,16 @@ fn build_details_lines(app: &App, pkg: Option<&str>) -> Vec<Line<'static>> {
 
         if let Some(homepage) = details.homepage.as_ref() {
             lines.push(Line::from(""));
-            lines.push(Line::from(Span::styled(
-                "  Homepage".to_string(),
-                Style::default().fg(theme.text_secondary),
-            )));
+            lines.push(Line::from(vec![
+                Span::styled(
+                    "  Homepage".to_string(),
+                    Style::default().fg(theme.text_secondary),
+                ),
+                Span::styled(
+                    format!("  [{}] open", app.key_hint(Action::OpenHomepage)),
+                    Style::default().fg(theme.text_muted),
+                ),
+            ]));
             lines.push(Line::from(Span::styled(
                 format!("  {}", homepage),
                 Style::default().fg(theme.accent),
@@ -159,16 +218,32 @@ fn build_details_lines(app: &App, pkg: Option<&str>) -> Vec<Line<'static>> {
             )));
         }
 
-        if let Some(artifacts) = details.artifacts.as_ref() {
-            lines.push(Line::from(""));
-            lines.push(Line::from(Span::styled(
-                format!("  Artifacts ({})", artifacts.len()),
-                Style::default().fg(theme.orange),
-            )));
-            lines.extend(format_list_multiline(app, artifacts, theme, "    "));
+        if !details.installed.is_empty()
+            && let Some(paths) = details.paths.as_ref()
+        {
+            lines.push(build_path_line(app, paths));
         }
 
-        let is_cask = details.artifacts.is_some();
+        if let Some(metadata) = details.metadata.as_ref() {
+            lines.extend(build_metadata_lines(app, metadata));
+        }
+
+        if !details.is_cask() && !details.installed.is_empty() {
+            lines.extend(build_link_lines(app, pkg, details));
+            lines.extend(build_keg_lines(app, details));
+        }
+
+        if let Some(cask) = details.cask.as_ref() {
+            lines.extend(build_cask_lines(app, cask));
+        }
+
+        if install_caveats.is_none()
+            && let Some(caveats) = details.caveats.as_deref()
+        {
+            lines.extend(build_caveat_lines(app, "Caveats", caveats));
+        }
+
+        let is_cask = details.is_cask();
 
         if is_cask {
             lines.push(Line::from(""));
@@ -189,7 +264,10 @@ fn build_details_lines(app: &App, pkg: Option<&str>) -> Vec<Line<'static>> {
                 if is_pending {
                     "  Dependencies: loading...".to_string()
                 } else {
-                    "  Dependencies: press 'd' to load".to_string()
+                    format!(
+                        "  Dependencies: press '{}' to load",
+                        app.key_hint(Action::LoadDeps)
+                    )
                 },
                 Style::default().fg(theme.text_muted),
             )));
@@ -214,7 +292,10 @@ fn build_details_lines(app: &App, pkg: Option<&str>) -> Vec<Line<'static>> {
                 if is_pending {
                     "  Used by: loading...".to_string()
                 } else {
-                    "  Used by: press 'd' to load".to_string()
+                    format!(
+                        "  Used by: press '{}' to load",
+                        app.key_hint(Action::LoadDeps)
+                    )
                 },
                 Style::default().fg(theme.text_muted),
             )));
@@ -225,7 +306,10 @@ fn build_details_lines(app: &App, pkg: Option<&str>) -> Vec<Line<'static>> {
             if is_pending {
                 "  Loading details...".to_string()
             } else {
-                "  Press Enter to load details".to_string()
+                format!(
+                    "  Press {} to load details",
+                    app.key_hint(Action::LoadDetails)
+                )
             },
             Style::default().fg(theme.text_muted),
         )));
@@ -234,6 +318,501 @@ fn build_details_lines(app: &App, pkg: Option<&str>) -> Vec<Line<'static>> {
     lines
 }
 
+fn build_path_line(app: &App, paths: &PackagePaths) -> Line<'static> {
+    let theme = &app.theme;
+    let (path, reveal, other) = match paths {
+        PackagePaths::Formula { opt, .. } => (opt, "keg", "opt"),
+        PackagePaths::Cask { caskroom } => (caskroom, "app", "Caskroom"),
+    };
+    Line::from(vec![
+        Span::styled(
+            "  Path: ".to_string(),
+            Style::default().fg(theme.text_secondary),
+        ),
+        Span::styled(path.clone(), Style::default().fg(theme.text_primary)),
+        Span::styled(
+            format!(
+                "  [{}] reveal {reveal}  [{}] reveal {other}",
+                app.key_hint(Action::Reveal),
+                app.key_hint(Action::RevealOpt)
+            ),
+            Style::default().fg(theme.text_muted),
+        ),
+    ])
+}
+
+fn build_cask_lines(app: &App, cask: &CaskInfo) -> Vec<Line<'static>> {
+    let theme = &app.theme;
+    let presence = |present: Option<bool>| match present {
+        Some(true) => (symbol(app, "✓", "ok"), theme.green),
+        Some(false) => (symbol(app, "✗", "x"), theme.red),
+        None => (symbol(app, "·", "-"), theme.text_muted),
+    };
+    let mut lines = vec![Line::from("")];
+
+    let auto_updates = if cask.auto_updates {
+        "yes, updates itself (brew upgrade skips it without --greedy)"
+    } else {
+        "no"
+    };
+    lines.push(Line::from(vec![
+        Span::styled(
+            "  Auto-updates: ".to_string(),
+            Style::default().fg(theme.text_secondary),
+        ),
+        Span::styled(
+            auto_updates.to_string(),
+            Style::default().fg(theme.text_primary),
+        ),
+    ]));
+    for (label, items, color) in [
+        ("Depends on", &cask.depends_on, theme.text_primary),
+        ("Conflicts with", &cask.conflicts_with, theme.orange),
+    ] {
+        if !items.is_empty() {
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!("  {label}: "),
+                    Style::default().fg(theme.text_secondary),
+                ),
+                Span::styled(format_list_inline(items), Style::default().fg(color)),
+            ]));
+        }
+    }
+
+    let missing = cask.missing_artifacts();
+    let drift = if missing > 0 {
+        format!(", {missing} missing on disk")
+    } else {
+        String::new()
+    };
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        format!("  Artifacts ({}{drift})", cask.artifacts.len()),
+        Style::default().fg(if missing > 0 { theme.red } else { theme.orange }),
+    )));
+    for artifact in &cask.artifacts {
+        let (icon, color) = presence(artifact.present);
+        let location = artifact
+            .path
+            .as_deref()
+            .filter(|path| {
+                !path.ends_with(artifact.name.as_str()) || artifact.present == Some(false)
+            })
+            .map(|path| format!("  {path}"))
+            .unwrap_or_default();
+        lines.push(Line::from(vec![
+            Span::styled(format!("    {icon} "), Style::default().fg(color)),
+            Span::styled(
+                format!("{}: ", artifact.kind.label()),
+                Style::default().fg(theme.text_secondary),
+            ),
+            Span::styled(
+                artifact.name.clone(),
+                Style::default().fg(theme.text_primary),
+            ),
+            Span::styled(location, Style::default().fg(theme.text_muted)),
+        ]));
+    }
+
+    for (title, items) in [("Uninstall", &cask.uninstall), ("Zap", &cask.zap)] {
+        if items.is_empty() {
+            continue;
+        }
+        let present = items
+            .iter()
+            .filter(|item| item.present == Some(true))
+            .count();
+        let present = if items.iter().any(StanzaItem::removes_path) {
+            format!(", {present} paths present")
+        } else {
+            String::new()
+        };
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            format!("  {title} ({}{present})", items.len()),
+            Style::default().fg(theme.yellow),
+        )));
+        for item in items {
+            let (icon, color) = presence(item.present);
+            lines.push(Line::from(vec![
+                Span::styled(format!("    {icon} "), Style::default().fg(color)),
+                Span::styled(
+                    format!("{}: ", item.directive),
+                    Style::default().fg(theme.text_secondary),
+                ),
+                Span::styled(item.value.clone(), Style::default().fg(theme.text_primary)),
+                Span::styled(
+                    item.size_kb
+                        .map(|size_kb| format!("  {}", format_size(size_kb)))
+                        .unwrap_or_default(),
+                    Style::default().fg(theme.text_muted),
+                ),
+            ]));
+        }
+    }
+    lines
+}
+
+/// What `brew uninstall --zap` would delete, shown while a zap awaits confirmation.
+fn build_zap_preview_lines(app: &App, pending: &PendingZap, cask: &CaskInfo) -> Vec<Line<'static>> {
+    let theme = &app.theme;
+    let armed = matches!(pending, PendingZap::Confirm(_));
+    let mut lines = vec![Line::from("")];
+    if !cask.zap_measured {
+        lines.push(Line::from(Span::styled(
+            "  Zap preview: measuring paths...".to_string(),
+            Style::default().fg(theme.yellow),
+        )));
+        return lines;
+    }
+
+    let present: Vec<_> = cask.zap_present().collect();
+    lines.push(Line::from(Span::styled(
+        format!(
+            "  {} Zap preview: deletes {} of {} listed paths ({}) plus the cask itself",
+            symbol(app, "⚠", "!"),
+            present.len(),
+            cask.zap.iter().filter(|item| item.removes_path()).count(),
+            format_size(cask.zap_size_kb())
+        ),
+        Style::default()
+            .fg(if armed { theme.red } else { theme.yellow })
+            .add_modifier(Modifier::BOLD),
+    )));
+    for item in &present {
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("    {} ", symbol(app, "✓", "ok")),
+                Style::default().fg(theme.red),
+            ),
+            Span::styled(item.value.clone(), Style::default().fg(theme.text_primary)),
+            Span::styled(
+                item.size_kb
+                    .map(|size_kb| format!("  {}", format_size(size_kb)))
+                    .unwrap_or_else(|| "  size unknown".to_string()),
+                Style::default().fg(theme.text_muted),
+            ),
+        ]));
+    }
+    let other = cask.zap.len() - present.len();
+    if other > 0 {
+        lines.push(Line::from(Span::styled(
+            format!(
+                "    {other} more {} absent or not paths (full list below)",
+                if other == 1 {
+                    "entry is"
+                } else {
+                    "entries are"
+                }
+            ),
+            Style::default().fg(theme.text_muted),
+        )));
+    }
+    let hint = if armed {
+        format!(
+            "  [{}] zap now, this cannot be undone  [{}] cancel",
+            app.key_hint(Action::Zap),
+            app.key_hint(Action::Cancel)
+        )
+    } else {
+        format!(
+            "  [{}] continue  [{}] cancel",
+            app.key_hint(Action::Zap),
+            app.key_hint(Action::Cancel)
+        )
+    };
+    lines.push(Line::from(Span::styled(
+        hint,
+        Style::default().fg(theme.text_secondary),
+    )));
+    lines
+}
+
+fn build_deprecation_lines(app: &App, deprecation: &Deprecation) -> Vec<Line<'static>> {
+    let theme = &app.theme;
+    let (icon, color) = match deprecation.state {
+        DeprecationState::Deprecated => (symbol(app, "⚠", "!"), theme.yellow),
+        DeprecationState::Disabled => (symbol(app, "✗", "x"), theme.red),
+    };
+    let replacement = match deprecation.replacement.as_deref() {
+        Some(name) => Line::from(vec![
+            Span::styled(
+                "  Replacement: ".to_string(),
+                Style::default().fg(theme.text_secondary),
+            ),
+            Span::styled(
+                format!(
+                    "{name}{}",
+                    if deprecation.replacement_is_cask {
+                        " (cask)"
+                    } else {
+                        ""
+                    }
+                ),
+                Style::default().fg(theme.green),
+            ),
+            Span::styled(
+                format!("  [{}] migrate", app.key_hint(Action::Migrate)),
+                Style::default().fg(theme.text_muted),
+            ),
+        ]),
+        None => Line::from(Span::styled(
+            "  No replacement suggested".to_string(),
+            Style::default().fg(theme.text_muted),
+        )),
+    };
+    vec![
+        Line::from(""),
+        Line::from(Span::styled(
+            format!("  {icon} {}", deprecation.summary()),
+            Style::default().fg(color).add_modifier(Modifier::BOLD),
+        )),
+        replacement,
+    ]
+}
+
+fn build_metadata_lines(app: &App, metadata: &FormulaMetadata) -> Vec<Line<'static>> {
+    let theme = &app.theme;
+    let sep = symbol(app, "·", "|");
+    let field = |label: &str, value: String, color| {
+        Line::from(vec![
+            Span::styled(
+                format!("  {label}: "),
+                Style::default().fg(theme.text_secondary),
+            ),
+            Span::styled(value, Style::default().fg(color)),
+        ])
+    };
+    let mut lines = vec![Line::from("")];
+    lines.push(field(
+        "License",
+        format!(
+            "{}  {sep}  Tap: {}",
+            metadata.license.as_deref().unwrap_or("unknown"),
+            metadata.tap.as_deref().unwrap_or("unknown")
+        ),
+        theme.text_primary,
+    ));
+    let (bottle, color) = match metadata.bottle_tags.as_deref() {
+        Some([]) => (
+            "none for this platform, builds from source".to_string(),
+            theme.orange,
+        ),
+        Some(tags) => (format!("available ({})", tags.join(", ")), theme.green),
+        None => ("none, builds from source".to_string(), theme.orange),
+    };
+    lines.push(field("Bottle", bottle, color));
+    if let Some(installed_at) = metadata.installed_at {
+        let source = match metadata.poured_from_bottle {
+            Some(true) => " from bottle",
+            Some(false) => " built from source",
+            None => "",
+        };
+        lines.push(field(
+            "Installed on",
+            format!("{}{source}", format_timestamp(installed_at)),
+            theme.text_primary,
+        ));
+    }
+    if !metadata.aliases.is_empty() {
+        lines.push(field(
+            "Aliases",
+            format_list_inline(&metadata.aliases),
+            theme.text_primary,
+        ));
+    }
+    if !metadata.oldnames.is_empty() {
+        lines.push(field(
+            "Formerly",
+            format_list_inline(&metadata.oldnames),
+            theme.text_primary,
+        ));
+    }
+
+    let declared = [
+        ("Runtime deps", &metadata.runtime_deps),
+        ("Build deps", &metadata.build_deps),
+        ("Optional deps", &metadata.optional_deps),
+        ("Recommended deps", &metadata.recommended_deps),
+        ("Requirements", &metadata.requirements),
+    ];
+    for (label, items) in declared {
+        if !items.is_empty() {
+            lines.push(field(label, format_list_inline(items), theme.text_primary));
+        }
+    }
+    if !metadata.conflicts_with.is_empty() {
+        lines.push(field(
+            "Conflicts with",
+            format_list_inline(&metadata.conflicts_with),
+            theme.orange,
+        ));
+    }
+    if !metadata.options.is_empty() {
+        lines.push(field(
+            &format!("Options ({})", metadata.options.len()),
+            String::new(),
+            theme.text_primary,
+        ));
+        lines.extend(format_list_multiline(app, &metadata.options, theme, "    "));
+    }
+
+    lines
+}
+
+fn build_caveat_lines(app: &App, title: &str, caveats: &str) -> Vec<Line<'static>> {
+    let theme = &app.theme;
+    let mut lines = vec![
+        Line::from(""),
+        Line::from(Span::styled(
+            format!("  {title}"),
+            Style::default()
+                .fg(theme.yellow)
+                .add_modifier(Modifier::BOLD),
+        )),
+    ];
+    lines.extend(caveats.lines().map(|line| {
+        let style = if is_shell_snippet(line) {
+            Style::default()
+                .fg(theme.accent_secondary)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(theme.text_primary)
+        };
+        Line::from(Span::styled(format!("    {line}"), style))
+    }));
+    lines
+}
+
+fn build_link_lines(app: &App, pkg: &str, details: &Details) -> Vec<Line<'static>> {
+    let theme = &app.theme;
+    let (state, color) = match details.linked_keg.as_deref() {
+        Some(version) => (format!("linked ({version})"), theme.green),
+        None if details.keg_only => ("not linked (keg-only)".to_string(), theme.text_secondary),
+        None => ("not linked".to_string(), theme.orange),
+    };
+    let mut lines = vec![
+        Line::from(""),
+        Line::from(vec![
+            Span::styled(
+                "  Link: ".to_string(),
+                Style::default().fg(theme.text_secondary),
+            ),
+            Span::styled(state, Style::default().fg(color)),
+            Span::styled(
+                format!(
+                    "  [{}] {}  [{}] preview conflicts",
+                    app.key_hint(Action::ToggleLink),
+                    if details.linked_keg.is_some() {
+                        "unlink"
+                    } else {
+                        "link"
+                    },
+                    app.key_hint(Action::LinkPreview)
+                ),
+                Style::default().fg(theme.text_muted),
+            ),
+        ]),
+    ];
+    if details.keg_only {
+        lines.push(Line::from(Span::styled(
+            format!(
+                "  Keg-only: {}",
+                details
+                    .keg_only_reason
+                    .as_deref()
+                    .unwrap_or("no reason given")
+            ),
+            Style::default().fg(theme.yellow),
+        )));
+    }
+
+    let Some((_, preview)) = app
+        .link_preview
+        .as_ref()
+        .filter(|(formula, _)| formula == pkg)
+    else {
+        return lines;
+    };
+    let color = if preview.conflicts.is_empty() {
+        theme.green
+    } else {
+        theme.orange
+    };
+    lines.push(Line::from(Span::styled(
+        format!("  Link preview: {}", preview.headline()),
+        Style::default().fg(color),
+    )));
+    for path in preview.conflicts.iter().take(MAX_LINK_CONFLICTS) {
+        lines.push(Line::from(Span::styled(
+            format!("    {path}"),
+            Style::default().fg(theme.text_secondary),
+        )));
+    }
+    if preview.conflicts.len() > MAX_LINK_CONFLICTS {
+        lines.push(Line::from(Span::styled(
+            format!(
+                "    … {} more ([{}] full output)",
+                preview.conflicts.len() - MAX_LINK_CONFLICTS,
+                app.key_hint(Action::OpenOutput)
+            ),
+            Style::default().fg(theme.text_muted),
+        )));
+    }
+    lines
+}
+
+fn build_keg_lines(app: &App, details: &Details) -> Vec<Line<'static>> {
+    let theme = &app.theme;
+    let selectable = app.keg_formula().is_some() && details.installed.len() > 1;
+    let hint = if selectable {
+        format!(
+            "  [{}] link  [{}] remove",
+            app.key_hint(Action::SwitchKeg),
+            app.key_hint(Action::RemoveKeg)
+        )
+    } else {
+        String::new()
+    };
+    let mut lines = vec![
+        Line::from(""),
+        Line::from(Span::styled(
+            format!("  Installed versions ({}){hint}", details.installed.len()),
+            Style::default().fg(theme.green),
+        )),
+    ];
+
+    let width = details.installed.iter().map(String::len).max().unwrap_or(0);
+    for (index, version) in details.installed.iter().enumerate() {
+        let marker = if selectable && index == app.keg_selected_index {
+            symbol(app, "▸", ">")
+        } else {
+            " "
+        };
+        let linked = details.linked_keg.as_deref() == Some(version.as_str());
+        let link_label = if linked { "linked" } else { "" };
+        let size = match details.keg_sizes.as_ref() {
+            Some(sizes) => sizes
+                .iter()
+                .find(|entry| entry.name == *version)
+                .map(|entry| format_size(entry.size_kb))
+                .unwrap_or_else(|| "n/a".to_string()),
+            None => format!("size: press '{}'", app.key_hint(Action::LoadDeps)),
+        };
+        let style = if linked {
+            Style::default().fg(theme.text_primary)
+        } else {
+            Style::default().fg(theme.text_secondary)
+        };
+        lines.push(Line::from(Span::styled(
+            format!("  {marker} {version:width$}  {link_label:6}  {size}"),
+            style,
+        )));
+    }
+    lines
+}
+
 fn build_package_results(app: &App) -> Vec<Line<'static>> {
     let theme = &app.theme;
     let mut lines = Vec::new();
@@ -253,7 +832,10 @@ fn build_package_results(app: &App) -> Vec<Line<'static>> {
             Style::default().fg(theme.text_muted),
         )));
         lines.push(Line::from(Span::styled(
-            "  Press 'f' to search packages".to_string(),
+            format!(
+                "  Press '{}' to search packages",
+                app.key_hint(Action::FindPackages)
+            ),
             Style::default().fg(theme.text_muted),
         )));
         return lines;
@@ -270,6 +852,81 @@ fn build_package_results(app: &App) -> Vec<Line<'static>> {
     lines
 }
 
+fn build_issue_details_lines(app: &App) -> Vec<Line<'static>> {
+    let theme = &app.theme;
+    let Some(issue) = app.selected_issue() else {
+        return Vec::new();
+    };
+
+    let (icon, color) = match issue.severity {
+        IssueSeverity::Warning => (symbol(app, "⚠", "!"), theme.yellow),
+        IssueSeverity::Error => (symbol(app, "✗", "x"), theme.red),
+    };
+    let mut lines = vec![
+        Line::from(""),
+        Line::from(Span::styled(
+            format!("  {icon} {}", issue.headline()),
+            Style::default().fg(color).add_modifier(Modifier::BOLD),
+        )),
+        Line::from(""),
+    ];
+    lines.extend(issue.body.iter().map(|line| {
+        Line::from(Span::styled(
+            format!("  {line}"),
+            Style::default().fg(theme.text_primary),
+        ))
+    }));
+
+    if !issue.paths.is_empty() {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            "  Paths".to_string(),
+            Style::default()
+                .fg(theme.accent_secondary)
+                .add_modifier(Modifier::BOLD),
+        )));
+        lines.extend(issue.paths.iter().map(|path| {
+            Line::from(Span::styled(
+                format!("    {path}"),
+                Style::default().fg(theme.text_secondary),
+            ))
+        }));
+    }
+
+    if !issue.commands.is_empty() {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            format!(
+                "  Suggested commands  [{}] copy  [{}] run",
+                app.key_hint(Action::Yank),
+                app.key_hint(Action::RunIssueCommand)
+            ),
+            Style::default()
+                .fg(theme.accent_secondary)
+                .add_modifier(Modifier::BOLD),
+        )));
+        for (index, command) in issue.commands.iter().enumerate() {
+            let copy_only = if copy_only_reason(command).is_some() {
+                "  (copy only)"
+            } else {
+                ""
+            };
+            let text = format!("  {}. {command}{copy_only}", index + 1);
+            let style = if index == app.issue_command_index {
+                Style::default()
+                    .fg(theme.text_on_accent)
+                    .bg(theme.accent)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(theme.text_secondary)
+            };
+            lines.push(Line::from(Span::styled(text, style)));
+        }
+    }
+
+    lines
+}
+
 fn build_service_details_lines(app: &App) -> Vec<Line<'static>> {
     let theme = &app.theme;
     let mut lines = vec![
@@ -286,11 +943,33 @@ fn build_service_details_lines(app: &App) -> Vec<Line<'static>> {
             Style::default().fg(theme.text_muted),
         )),
         Line::from(Span::styled(
-            "  Actions: [S] start  [X] stop  [R] restart  [I] info".to_string(),
+            format!(
+                "  Actions: [{}] start  [{}] stop  [{}] restart  [{}] info  [{}] logs",
+                app.key_hint(Action::ServiceStart),
+                app.key_hint(Action::ServiceStop),
+                app.key_hint(Action::ServiceRestart),
+                app.key_hint(Action::ServiceInfo),
+                app.key_hint(Action::ServiceLogs)
+            ),
+            Style::default().fg(theme.text_secondary),
+        )),
+        Line::from(Span::styled(
+            format!(
+                "  More: [{}] run this session  [{}] kill  [{}] all services  [{}] cleanup",
+                app.key_hint(Action::ServiceRun),
+                app.key_hint(Action::ServiceKill),
+                app.key_hint(Action::ServiceAll),
+                app.key_hint(Action::ServiceCleanup)
+            ),
             Style::default().fg(theme.text_secondary),
         )),
         Line::from(Span::styled(
-            "  Filter keys: [F] failed-only  [A] auto-start-only  [K] kind".to_string(),
+            format!(
+                "  Filter keys: [{}] failed-only  [{}] auto-start-only  [{}] kind",
+                app.key_hint(Action::FilterFailedServices),
+                app.key_hint(Action::FilterAutostartServices),
+                app.key_hint(Action::CycleServiceKind)
+            ),
             Style::default().fg(theme.text_secondary),
         )),
     ];
@@ -351,13 +1030,14 @@ fn build_service_details_lines(app: &App) -> Vec<Line<'static>> {
         Style::default().fg(theme.text_secondary),
     )));
 
-    let autostart = if service.auto_start_enabled() {
-        "yes"
-    } else {
-        "no"
+    let registration = service.registration();
+    let registration_hint = match registration {
+        ServiceRegistration::Login => " (starts again after login or reboot)",
+        ServiceRegistration::Session => " (gone after logout or reboot)",
+        ServiceRegistration::None => "",
     };
     lines.push(Line::from(Span::styled(
-        format!("  Auto-start: {autostart}"),
+        format!("  Start mode: {}{registration_hint}", registration.label()),
         Style::default().fg(theme.text_secondary),
     )));
 
@@ -374,52 +1054,32 @@ fn build_service_details_lines(app: &App) -> Vec<Line<'static>> {
             format!("  {} Why is this red?", symbol(app, "⚠", "!")),
             Style::default().fg(theme.red).add_modifier(Modifier::BOLD),
         )));
-        lines.extend(platform_service_hints(app, &service.name));
+        lines.extend(platform_service_hints(app, service));
     }
 
     lines
 }
 
-fn platform_service_hints(app: &App, service: &str) -> Vec<Line<'static>> {
+fn platform_service_hints(app: &App, service: &ServiceEntry) -> Vec<Line<'static>> {
     let theme = &app.theme;
-    let mut lines = Vec::new();
-
-    if cfg!(target_os = "macos") {
-        lines.push(Line::from(Span::styled(
-            format!("    {} brew services info {service}", symbol(app, "•", "*")),
-            Style::default().fg(theme.text_primary),
-        )));
-        lines.push(Line::from(Span::styled(
-            format!(
-                "    {} launchctl print gui/$UID/homebrew.mxcl.{service}",
-                symbol(app, "•", "*")
-            ),
-            Style::default().fg(theme.text_primary),
-        )));
-        lines.push(Line::from(Span::styled(
-            format!(
-                "    {} log show --style compact --predicate 'process CONTAINS \"{service}\"' --last 10m",
-                symbol(app, "•", "*")
-            ),
-            Style::default().fg(theme.text_primary),
-        )));
-    } else {
-        lines.push(Line::from(Span::styled(
-            format!(
-                "    {} systemctl --user status {service}.service",
-                symbol(app, "•", "*")
-            ),
-            Style::default().fg(theme.text_primary),
-        )));
-        lines.push(Line::from(Span::styled(
-            format!(
-                "    {} journalctl --user-unit {service}.service -n 50 --no-pager",
-                symbol(app, "•", "*")
-            ),
-            Style::default().fg(theme.text_primary),
-        )));
-    }
-
+    let mut lines: Vec<Line<'static>> = service
+        .troubleshooting_commands()
+        .into_iter()
+        .map(|command| {
+            Line::from(Span::styled(
+                format!("    {} {command}", symbol(app, "•", "*")),
+                Style::default().fg(theme.text_primary),
+            ))
+        })
+        .collect();
+    lines.push(Line::from(Span::styled(
+        format!(
+            "    [{}] copy the first command  [{}] follow its logs",
+            app.key_hint(Action::Yank),
+            app.key_hint(Action::ServiceLogs)
+        ),
+        Style::default().fg(theme.text_muted),
+    )));
     lines
 }
 
@@ -4,19 +4,31 @@ use ratatui::text::{Line, Span};
 use ratatui::widgets::Paragraph;
 
 use crate::app::App;
+use crate::keymap::Action;
 
 pub fn draw_footer(frame: &mut ratatui::Frame, area: Rect, app: &App, dimmed: bool) {
     let theme = &app.theme;
 
-    let keys = vec![
-        ("q", "quit"),
-        ("tab", "tabs"),
-        ("i", "install"),
-        ("u", "uninstall"),
-        ("U", "upgrade"),
-        ("f,/", "search"),
-        ("?", "help"),
+    let search_keys = format!(
+        "{},{}",
+        app.key_hint(Action::FindPackages),
+        app.key_hint(Action::FilterInstalled)
+    );
+    let mut keys = vec![
+        (app.key_hint(Action::Quit), "quit"),
+        (app.key_hint(Action::NextPanel), "tabs"),
     ];
+    if !app.read_only {
+        keys.extend([
+            (app.key_hint(Action::Install), "install"),
+            (app.key_hint(Action::Uninstall), "uninstall"),
+            (app.key_hint(Action::Upgrade), "upgrade"),
+        ]);
+    }
+    keys.extend([
+        (search_keys, "search"),
+        (app.key_hint(Action::Help), "help"),
+    ]);
 
     let mut spans = Vec::new();
     for (key, label) in keys {
@@ -1,252 +1,48 @@
-use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use ratatui::style::{Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Clear, List, ListItem, ListState};
 
 use crate::app::App;
+use crate::keymap::{ACTIONS, Action, HelpSection};
 use crate::ui::layout;
 use crate::ui::util::symbol;
 
 struct HelpCommand {
     key_label: String,
     description: &'static str,
-    key_event: KeyEvent,
+    action: Action,
 }
 
 struct HelpRenderData {
     lines: Vec<Line<'static>>,
     command_line_indices: Vec<usize>,
-    command_keys: Vec<KeyEvent>,
-}
-
-fn plain_key_event(code: KeyCode) -> KeyEvent {
-    KeyEvent::new(code, KeyModifiers::NONE)
+    command_actions: Vec<Action>,
 }
 
+/// Bound actions grouped by section, straight from the active keymap.
 fn build_help_render_data(app: &App) -> HelpRenderData {
     let theme = &app.theme;
-    let keymaps = vec![
-        (
-            "Navigation",
-            vec![
-                HelpCommand {
-                    key_label: if app.icons_ascii {
-                        "j / down".to_string()
-                    } else {
-                        "j / ↓".to_string()
-                    },
-                    description: "Move down",
-                    key_event: plain_key_event(KeyCode::Char('j')),
-                },
-                HelpCommand {
-                    key_label: if app.icons_ascii {
-                        "k / up".to_string()
-                    } else {
-                        "k / ↑".to_string()
-                    },
-                    description: "Move up",
-                    key_event: plain_key_event(KeyCode::Char('k')),
-                },
-                HelpCommand {
-                    key_label: "Tab".to_string(),
-                    description: "Next panel",
-                    key_event: plain_key_event(KeyCode::Tab),
-                },
-                HelpCommand {
-                    key_label: "S-Tab".to_string(),
-                    description: "Previous panel",
-                    key_event: plain_key_event(KeyCode::BackTab),
-                },
-                HelpCommand {
-                    key_label: if app.icons_ascii {
-                        "l / left".to_string()
-                    } else {
-                        "l / ←".to_string()
-                    },
-                    description: "Previous status tab",
-                    key_event: plain_key_event(KeyCode::Char('l')),
-                },
-                HelpCommand {
-                    key_label: if app.icons_ascii {
-                        "; / right".to_string()
-                    } else {
-                        "; / →".to_string()
-                    },
-                    description: "Next status tab",
-                    key_event: plain_key_event(KeyCode::Char(';')),
-                },
-            ],
-        ),
-        (
-            "Search",
-            vec![
-                HelpCommand {
-                    key_label: "/".to_string(),
-                    description: "Search installed list",
-                    key_event: plain_key_event(KeyCode::Char('/')),
-                },
-                HelpCommand {
-                    key_label: "f".to_string(),
-                    description: "Find packages",
-                    key_event: plain_key_event(KeyCode::Char('f')),
-                },
-                HelpCommand {
-                    key_label: "C".to_string(),
-                    description: "Toggle formula/cask list",
-                    key_event: plain_key_event(KeyCode::Char('C')),
-                },
-            ],
-        ),
-        (
-            "Actions",
-            vec![
-                HelpCommand {
-                    key_label: "Enter".to_string(),
-                    description: "Load details",
-                    key_event: plain_key_event(KeyCode::Enter),
-                },
-                HelpCommand {
-                    key_label: "d".to_string(),
-                    description: "Load deps/uses",
-                    key_event: plain_key_event(KeyCode::Char('d')),
-                },
-                HelpCommand {
-                    key_label: "i".to_string(),
-                    description: "Install selected (confirm)",
-                    key_event: plain_key_event(KeyCode::Char('i')),
-                },
-                HelpCommand {
-                    key_label: "u".to_string(),
-                    description: "Uninstall selected (confirm)",
-                    key_event: plain_key_event(KeyCode::Char('u')),
-                },
-                HelpCommand {
-                    key_label: "U".to_string(),
-                    description: "Upgrade selected or all outdated (confirm)",
-                    key_event: plain_key_event(KeyCode::Char('U')),
-                },
-                HelpCommand {
-                    key_label: "P".to_string(),
-                    description: "Update Brewery via cargo (confirm)",
-                    key_event: plain_key_event(KeyCode::Char('P')),
-                },
-                HelpCommand {
-                    key_label: "S".to_string(),
-                    description: "Start selected service (confirm)",
-                    key_event: plain_key_event(KeyCode::Char('S')),
-                },
-                HelpCommand {
-                    key_label: "X".to_string(),
-                    description: "Stop selected service (confirm)",
-                    key_event: plain_key_event(KeyCode::Char('X')),
-                },
-                HelpCommand {
-                    key_label: "R".to_string(),
-                    description: "Restart selected service (confirm)",
-                    key_event: plain_key_event(KeyCode::Char('R')),
-                },
-                HelpCommand {
-                    key_label: "I".to_string(),
-                    description: "Show selected service info",
-                    key_event: plain_key_event(KeyCode::Char('I')),
-                },
-                HelpCommand {
-                    key_label: "F".to_string(),
-                    description: "Filter failed services",
-                    key_event: plain_key_event(KeyCode::Char('F')),
-                },
-                HelpCommand {
-                    key_label: "A".to_string(),
-                    description: "Filter auto-start services",
-                    key_event: plain_key_event(KeyCode::Char('A')),
-                },
-                HelpCommand {
-                    key_label: "K".to_string(),
-                    description: "Cycle service kind filter",
-                    key_event: plain_key_event(KeyCode::Char('K')),
-                },
-                HelpCommand {
-                    key_label: "o".to_string(),
-                    description: "Toggle outdated-only formula filter",
-                    key_event: plain_key_event(KeyCode::Char('o')),
-                },
-            ],
-        ),
-        (
-            "Data",
-            vec![
-                HelpCommand {
-                    key_label: "r".to_string(),
-                    description: "Refresh formulae + casks",
-                    key_event: plain_key_event(KeyCode::Char('r')),
-                },
-                HelpCommand {
-                    key_label: "s".to_string(),
-                    description: "Load sizes",
-                    key_event: plain_key_event(KeyCode::Char('s')),
-                },
-                HelpCommand {
-                    key_label: "h".to_string(),
-                    description: "Status check",
-                    key_event: plain_key_event(KeyCode::Char('h')),
-                },
-            ],
-        ),
-        (
-            "Other",
-            vec![
-                HelpCommand {
-                    key_label: "t".to_string(),
-                    description: "Cycle theme",
-                    key_event: plain_key_event(KeyCode::Char('t')),
-                },
-                HelpCommand {
-                    key_label: "m".to_string(),
-                    description: "Toggle mouse",
-                    key_event: plain_key_event(KeyCode::Char('m')),
-                },
-                HelpCommand {
-                    key_label: "Alt+i".to_string(),
-                    description: "Toggle icons",
-                    key_event: KeyEvent::new(KeyCode::Char('i'), KeyModifiers::ALT),
-                },
-                HelpCommand {
-                    key_label: "c".to_string(),
-                    description: "Cleanup",
-                    key_event: plain_key_event(KeyCode::Char('c')),
-                },
-                HelpCommand {
-                    key_label: "a".to_string(),
-                    description: "Autoremove",
-                    key_event: plain_key_event(KeyCode::Char('a')),
-                },
-                HelpCommand {
-                    key_label: "b".to_string(),
-                    description: "Bundle dump",
-                    key_event: plain_key_event(KeyCode::Char('b')),
-                },
-                HelpCommand {
-                    key_label: "v".to_string(),
-                    description: "Toggle view",
-                    key_event: plain_key_event(KeyCode::Char('v')),
-                },
-                HelpCommand {
-                    key_label: "q".to_string(),
-                    description: "Quit",
-                    key_event: plain_key_event(KeyCode::Char('q')),
-                },
-                HelpCommand {
-                    key_label: "Esc".to_string(),
-                    description: "Cancel action",
-                    key_event: plain_key_event(KeyCode::Esc),
-                },
-            ],
-        ),
-    ];
+    let keymaps: Vec<(&str, Vec<HelpCommand>)> = HelpSection::ALL
+        .iter()
+        .map(|section| {
+            let commands: Vec<HelpCommand> = ACTIONS
+                .iter()
+                .filter(|spec| spec.section == *section && spec.action != Action::Help)
+                .filter(|spec| app.keymap.is_bound(spec.action))
+                .map(|spec| HelpCommand {
+                    key_label: app.keymap.label(spec.action, app.icons_ascii),
+                    description: spec.description,
+                    action: spec.action,
+                })
+                .collect();
+            (section.title(), commands)
+        })
+        .filter(|(_, commands)| !commands.is_empty())
+        .collect();
 
     let mut lines: Vec<Line> = Vec::new();
     let mut command_line_indices = Vec::new();
-    let mut command_keys = Vec::new();
+    let mut command_actions = Vec::new();
 
     let section_count = keymaps.len();
 
@@ -260,10 +56,10 @@ fn build_help_render_data(app: &App) -> HelpRenderData {
 
         for command in keys {
             command_line_indices.push(lines.len());
-            command_keys.push(command.key_event);
+            command_actions.push(command.action);
             lines.push(Line::from(vec![
                 Span::styled(
-                    format!("   {:12}", command.key_label),
+                    format!("   {:12} ", command.key_label),
                     Style::default().fg(theme.yellow),
                 ),
                 Span::styled(command.description, Style::default().fg(theme.text_primary)),
@@ -278,7 +74,7 @@ fn build_help_render_data(app: &App) -> HelpRenderData {
     HelpRenderData {
         lines,
         command_line_indices,
-        command_keys,
+        command_actions,
     }
 }
 
@@ -287,7 +83,7 @@ pub fn help_line_count(app: &App) -> usize {
 }
 
 pub fn help_command_count(app: &App) -> usize {
-    build_help_render_data(app).command_keys.len()
+    build_help_render_data(app).command_actions.len()
 }
 
 pub fn help_command_line(app: &App, command_index: usize) -> Option<usize> {
@@ -297,9 +93,9 @@ pub fn help_command_line(app: &App, command_index: usize) -> Option<usize> {
         .copied()
 }
 
-pub fn help_selected_command_key(app: &App) -> Option<KeyEvent> {
+pub fn help_selected_action(app: &App) -> Option<Action> {
     build_help_render_data(app)
-        .command_keys
+        .command_actions
         .get(app.help_selected_command)
         .copied()
 }
@@ -337,7 +133,11 @@ pub fn draw_help_popup(frame: &mut ratatui::Frame, app: &App) {
                 .add_modifier(Modifier::BOLD),
         ))
         .title_bottom(Line::from(Span::styled(
-            " Enter runs keymap - ?/Esc close ",
+            format!(
+                " Enter runs keymap - {}/{} close ",
+                app.key_hint(Action::Help),
+                app.key_hint(Action::Cancel)
+            ),
             Style::default().fg(theme.text_muted),
         )));
 
@@ -32,7 +32,7 @@ pub fn split_app(area: Rect) -> AppLayout {
         .constraints([
             Constraint::Length(3),
             Constraint::Min(10),
-            Constraint::Length(10),
+            Constraint::Length(13),
         ])
         .split(columns[0]);
 
@@ -70,3 +70,25 @@ pub fn help_popup_area(area: Rect) -> Rect {
 pub fn help_visible_line_capacity(area: Rect) -> usize {
     help_popup_area(area).height.saturating_sub(2) as usize
 }
+
+pub fn pager_area(area: Rect) -> Rect {
+    split_app(area).body
+}
+
+pub fn pager_visible_line_capacity(area: Rect) -> usize {
+    pager_area(area).height.saturating_sub(2) as usize
+}
+
+pub fn theme_picker_area(area: Rect, option_count: usize) -> Rect {
+    let popup_width = 32u16.min(area.width.saturating_sub(4));
+    let popup_height = (option_count as u16 + 2).min(area.height.saturating_sub(4));
+    let popup_x = area.x + area.width.saturating_sub(popup_width + 2);
+    let popup_y = area.y + 2;
+
+    Rect {
+        x: popup_x,
+        y: popup_y,
+        width: popup_width,
+        height: popup_height,
+    }
+}
@@ -3,7 +3,9 @@ use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, List, ListItem, ListState};
 
-use crate::app::{App, InputMode};
+use crate::app::{App, InputMode, PackageKind};
+use crate::brew::DeprecationState;
+use crate::keymap::Action;
 use crate::ui::util::symbol;
 
 pub fn draw_leaves_panel(frame: &mut ratatui::Frame, area: Rect, app: &App, is_focused: bool) {
@@ -33,7 +35,7 @@ pub fn draw_leaves_panel(frame: &mut ratatui::Frame, area: Rect, app: &App, is_f
             casks
                 .iter()
                 .filter_map(|idx| app.casks.get(*idx))
-                .map(|item| styled_item(format!(" {item}"), theme.text_primary))
+                .map(|item| installed_item(app, item, PackageKind::Cask, String::new()))
                 .collect()
         };
         let selected = app
@@ -51,12 +53,15 @@ pub fn draw_leaves_panel(frame: &mut ratatui::Frame, area: Rect, app: &App, is_f
         let rows = if leaves.is_empty() {
             let empty_label = if app.leaves_outdated_only {
                 if app.system_status.is_some() {
-                    "  No outdated leaves"
+                    "  No outdated leaves".to_string()
                 } else {
-                    "  No outdated data yet (press h)"
+                    format!(
+                        "  No outdated data yet (press {})",
+                        app.key_hint(Action::StatusCheck)
+                    )
                 }
             } else {
-                "  No leaves found"
+                "  No leaves found".to_string()
             };
             vec![styled_item(empty_label, theme.text_muted)]
         } else {
@@ -69,7 +74,7 @@ pub fn draw_leaves_panel(frame: &mut ratatui::Frame, area: Rect, app: &App, is_f
                     } else {
                         String::new()
                     };
-                    styled_item(format!(" {marker}{item}"), theme.text_primary)
+                    installed_item(app, item, PackageKind::Formula, marker)
                 })
                 .collect()
         };
@@ -117,6 +122,25 @@ pub fn draw_leaves_panel(frame: &mut ratatui::Frame, area: Rect, app: &App, is_f
     frame.render_stateful_widget(leaves_list, area, &mut list_state);
 }
 
+/// Deprecated and disabled packages get a warning badge and colour.
+fn installed_item(app: &App, name: &str, kind: PackageKind, marker: String) -> ListItem<'static> {
+    let theme = &app.theme;
+    match app
+        .installed_deprecation(name, kind)
+        .map(|deprecation| deprecation.state)
+    {
+        Some(DeprecationState::Deprecated) => styled_item(
+            format!(" {marker}{name} {}", symbol(app, "⚠", "!")),
+            theme.yellow,
+        ),
+        Some(DeprecationState::Disabled) => styled_item(
+            format!(" {marker}{name} {}", symbol(app, "✗", "x")),
+            theme.red,
+        ),
+        None => styled_item(format!(" {marker}{name}"), theme.text_primary),
+    }
+}
+
 fn styled_item(text: impl Into<String>, color: Color) -> ListItem<'static> {
     ListItem::new(Line::from(Span::styled(
         text.into(),
@@ -3,10 +3,13 @@ mod footer;
 pub mod help;
 pub mod layout;
 mod leaves;
+mod monochrome;
+pub mod pager;
 mod search;
 mod sizes;
 mod status;
-mod util;
+mod theme_picker;
+pub mod util;
 
 use ratatui::layout::Rect;
 use ratatui::style::{Modifier, Style};
@@ -14,7 +17,9 @@ use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Paragraph};
 
 use crate::app::{App, FocusedPanel, StatusTab};
-use crate::theme::ThemeMode;
+use crate::storage::unix_now;
+use crate::theme::ColorDepth;
+use crate::ui::util::{format_elapsed, symbol};
 
 pub fn status_tab_at_column(app: &App, area: Rect, column: u16) -> Option<StatusTab> {
     status::tab_at_column(app, area, column)
@@ -32,9 +37,15 @@ pub fn draw(frame: &mut ratatui::Frame, app: &App) {
     draw_body(frame, app, app_layout);
     footer::draw_footer(frame, app_layout.footer, app, dimmed);
 
+    pager::draw_pager(frame, app);
     if app.show_help_popup {
         help::draw_help_popup(frame, app);
     }
+    theme_picker::draw_theme_picker(frame, app);
+
+    if app.color_depth == ColorDepth::Monochrome {
+        monochrome::apply_monochrome(frame.buffer_mut(), &app.theme);
+    }
 }
 
 fn draw_header(frame: &mut ratatui::Frame, area: Rect, app: &App, dimmed: bool) {
@@ -42,10 +53,10 @@ fn draw_header(frame: &mut ratatui::Frame, area: Rect, app: &App, dimmed: bool)
     let uptime = app.started_at.elapsed().as_secs();
     let current_version = env!("CARGO_PKG_VERSION");
 
-    let theme_indicator = match app.theme_mode {
-        ThemeMode::Auto => "auto",
-        ThemeMode::Light => "light",
-        ThemeMode::Dark => "dark",
+    let theme_indicator = if app.color_depth == ColorDepth::TrueColor {
+        app.theme_mode.label().to_string()
+    } else {
+        format!("{}/{}", app.theme_mode.label(), app.color_depth.label())
     };
 
     let mut version_label = format!("v{current_version}");
@@ -67,7 +78,7 @@ fn draw_header(frame: &mut ratatui::Frame, area: Rect, app: &App, dimmed: bool)
         theme.text_muted
     };
 
-    let line = Line::from(vec![
+    let mut spans = vec![
         Span::raw(" "),
         Span::styled(
             " brewery ",
@@ -81,17 +92,62 @@ fn draw_header(frame: &mut ratatui::Frame, area: Rect, app: &App, dimmed: bool)
         Span::raw("  "),
         Span::styled(&app.status, Style::default().fg(theme.text_secondary)),
         Span::raw("  "),
-        Span::styled(
-            format!("[{}] {}s", theme_indicator, uptime),
-            Style::default().fg(theme.text_secondary),
-        ),
-    ]);
+    ];
+    if app.read_only {
+        spans.push(Span::styled("read-only", Style::default().fg(theme.orange)));
+        spans.push(Span::raw("  "));
+    }
+    if let Some(label) = stale_label(app) {
+        spans.push(Span::styled(label, Style::default().fg(theme.yellow)));
+        spans.push(Span::raw("  "));
+    }
+    spans.push(Span::styled(
+        format!("[{}] {}s", theme_indicator, uptime),
+        Style::default().fg(theme.text_secondary),
+    ));
+    let line = Line::from(spans);
 
     let bg = if dimmed { theme.bg_dim } else { theme.bg_panel };
     let header = Paragraph::new(line).style(Style::default().bg(bg));
     frame.render_widget(header, area);
 }
 
+/// Shown while any panel still holds data from the startup snapshot.
+fn stale_label(app: &App) -> Option<String> {
+    let stale = app.stale;
+    if !stale.any() {
+        return None;
+    }
+    let fetched_at = &app.snapshot_fetched_at;
+    let age = [
+        (stale.leaves, fetched_at.leaves),
+        (stale.casks, fetched_at.casks),
+        (stale.sizes, fetched_at.sizes),
+        (stale.status, fetched_at.status),
+    ]
+    .into_iter()
+    .filter(|(stale, _)| *stale)
+    .map(|(_, at)| at)
+    .min()
+    .map(|oldest| {
+        format!(
+            "cached {} ago, ",
+            format_elapsed(unix_now().saturating_sub(oldest))
+        )
+    })
+    .unwrap_or_default();
+    let refreshing = (stale.leaves && app.pending_leaves)
+        || (stale.casks && app.pending_casks)
+        || (stale.sizes && app.pending_sizes)
+        || (stale.status && app.pending_status);
+    let state = if refreshing {
+        format!("stale, refreshing{}", symbol(app, "…", "..."))
+    } else {
+        "stale".to_string()
+    };
+    Some(format!("{age}{state}"))
+}
+
 fn draw_body(frame: &mut ratatui::Frame, app: &App, layout: layout::AppLayout) {
     search::draw_search_panel(frame, layout.search, app);
     leaves::draw_leaves_panel(
@@ -0,0 +1,66 @@
+use ratatui::buffer::Buffer;
+use ratatui::style::{Color, Modifier};
+
+use crate::theme::Theme;
+
+/// Replaces colour with attributes after drawing: reverse for selections and
+/// badges, bold for focus and accents, underline for errors and warnings.
+pub fn apply_monochrome(buffer: &mut Buffer, theme: &Theme) {
+    for cell in buffer.content.iter_mut() {
+        let mut modifier = Modifier::empty();
+
+        if cell.bg == theme.bg_selection
+            || cell.bg == theme.accent
+            || cell.bg == theme.accent_secondary
+        {
+            modifier |= Modifier::REVERSED;
+        } else if cell.bg == theme.bg_dim {
+            modifier |= Modifier::DIM;
+        }
+
+        if cell.fg == theme.red {
+            modifier |= Modifier::BOLD | Modifier::UNDERLINED;
+        } else if cell.fg == theme.orange || cell.fg == theme.yellow {
+            modifier |= Modifier::UNDERLINED;
+        } else if cell.fg == theme.accent || cell.fg == theme.border_active {
+            modifier |= Modifier::BOLD;
+        } else if cell.fg == theme.text_muted {
+            modifier |= Modifier::DIM;
+        }
+
+        cell.set_fg(Color::Reset);
+        cell.set_bg(Color::Reset);
+        cell.modifier |= modifier;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::buffer::Buffer;
+    use ratatui::layout::Rect;
+    use ratatui::style::{Color, Modifier, Style};
+
+    use super::apply_monochrome;
+    use crate::theme::Theme;
+
+    #[test]
+    fn swaps_palette_roles_for_attributes() {
+        let theme = Theme::dark();
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 3, 1));
+        buffer.set_string(0, 0, "a", Style::default().bg(theme.bg_selection));
+        buffer.set_string(1, 0, "b", Style::default().fg(theme.red));
+        buffer.set_string(2, 0, "c", Style::default().fg(theme.text_primary));
+
+        apply_monochrome(&mut buffer, &theme);
+
+        let cells = &buffer.content;
+        assert!(cells[0].modifier.contains(Modifier::REVERSED));
+        assert!(cells[1].modifier.contains(Modifier::UNDERLINED));
+        assert!(cells[2].modifier.is_empty());
+        assert!(
+            cells
+                .iter()
+                .all(|cell| cell.fg == Color::Reset && cell.bg == Color::Reset)
+        );
+    }
+}
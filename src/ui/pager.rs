@@ -0,0 +1,161 @@
+use ratatui::layout::Rect;
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+
+use crate::app::{
+    App, OutputHighlight, PagerLine, PagerLineKind, log_highlight, match_ranges, output_highlight,
+};
+use crate::keymap::Action;
+use crate::theme::Theme;
+use crate::ui::layout;
+
+/// Covers the whole body so long transcripts get every row.
+pub fn draw_pager(frame: &mut ratatui::Frame, app: &App) {
+    let Some(pager) = app.pager.as_ref() else {
+        return;
+    };
+    let theme = &app.theme;
+    let area = layout::pager_area(frame.area());
+
+    frame.render_widget(Clear, area);
+
+    let visible_height = layout::pager_visible_line_capacity(frame.area());
+    let offset = pager
+        .scroll
+        .min(pager.lines.len().saturating_sub(visible_height));
+    let last_visible = (offset + visible_height).min(pager.lines.len());
+
+    let position = match (pager.current_match, pager.matches.len()) {
+        (Some(current), total) if total > 0 => {
+            format!(
+                " {last_visible}/{} · match {}/{total} ",
+                pager.lines.len(),
+                current + 1
+            )
+        }
+        _ => format!(" {last_visible}/{} ", pager.lines.len()),
+    };
+    let position = if pager.tail.is_some() {
+        let following = offset + visible_height >= pager.lines.len();
+        let mode = if following {
+            "live".to_string()
+        } else {
+            format!("paused, {} to follow", app.key_hint(Action::PagerBottom))
+        };
+        format!("{position}· {mode} ")
+    } else {
+        position
+    };
+    let footer = match pager.search_input.as_ref() {
+        Some(input) => Span::styled(format!(" /{input}_ "), Style::default().fg(theme.accent)),
+        None => Span::styled(
+            format!(
+                " {}/{} scroll - {}/{} page - {} search - {}/{} next/prev - {} save - {} close ",
+                app.key_hint(Action::MoveDown),
+                app.key_hint(Action::MoveUp),
+                app.key_hint(Action::PagerPageUp),
+                app.key_hint(Action::PagerPageDown),
+                app.key_hint(Action::PagerSearch),
+                app.key_hint(Action::PagerNextMatch),
+                app.key_hint(Action::PagerPrevMatch),
+                app.key_hint(Action::PagerSave),
+                app.key_hint(Action::Cancel)
+            ),
+            Style::default().fg(theme.text_muted),
+        ),
+    };
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.border_active))
+        .style(Style::default().bg(theme.bg_main))
+        .title(Span::styled(
+            format!(" {} ", pager.title),
+            Style::default()
+                .fg(theme.accent)
+                .add_modifier(Modifier::BOLD),
+        ))
+        .title(
+            Line::from(Span::styled(
+                position,
+                Style::default().fg(theme.text_muted),
+            ))
+            .right_aligned(),
+        )
+        .title_bottom(Line::from(footer));
+
+    let current_line = pager.current_match_line();
+    let lines: Vec<Line> = pager
+        .lines
+        .iter()
+        .enumerate()
+        .skip(offset)
+        .take(visible_height)
+        .map(|(index, line)| {
+            pager_line(
+                theme,
+                line,
+                &pager.query,
+                current_line == Some(index),
+                pager.tail.is_some(),
+            )
+        })
+        .collect();
+
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+/// Service logs are classified by severity words; command output by brew's markers.
+fn pager_line<'a>(
+    theme: &Theme,
+    line: &'a PagerLine,
+    query: &str,
+    current: bool,
+    log: bool,
+) -> Line<'a> {
+    let highlight = if log {
+        log_highlight(&line.text)
+    } else {
+        output_highlight(&line.text)
+    };
+    let base = match line.kind {
+        PagerLineKind::Header => Style::default().fg(theme.text_secondary),
+        PagerLineKind::Stdout | PagerLineKind::Stderr => match highlight {
+            Some(OutputHighlight::Section) => Style::default()
+                .fg(theme.accent)
+                .add_modifier(Modifier::BOLD),
+            Some(OutputHighlight::Warning) => Style::default().fg(theme.yellow),
+            Some(OutputHighlight::Error) => {
+                Style::default().fg(theme.red).add_modifier(Modifier::BOLD)
+            }
+            None if line.kind == PagerLineKind::Stderr => Style::default().fg(theme.red),
+            None => Style::default().fg(theme.text_primary),
+        },
+    };
+    let found = Style::default().fg(theme.text_on_accent).bg(if current {
+        theme.accent
+    } else {
+        theme.accent_secondary
+    });
+
+    let mut spans = Vec::new();
+    let mut cursor = 0;
+    for range in match_ranges(&line.text, query) {
+        if range.start > cursor {
+            spans.push(Span::styled(&line.text[cursor..range.start], base));
+        }
+        spans.push(Span::styled(&line.text[range.clone()], found));
+        cursor = range.end;
+    }
+    if cursor < line.text.len() || spans.is_empty() {
+        spans.push(Span::styled(&line.text[cursor..], base));
+    }
+    Line::from(spans)
+}
+
+/// Largest scroll offset that still fills the pager for a terminal of `area`.
+pub fn pager_max_offset(app: &App, area: Rect) -> usize {
+    let total = app.pager.as_ref().map_or(0, |pager| pager.lines.len());
+    total.saturating_sub(layout::pager_visible_line_capacity(area))
+}
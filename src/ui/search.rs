@@ -20,6 +20,7 @@ pub fn draw_search_panel(frame: &mut ratatui::Frame, area: Rect, app: &App) {
             true,
         ),
         InputMode::PackageSearch => (format!("{title_prefix}{search_icon} Search packages"), true),
+        InputMode::SearchHistory => (format!("{title_prefix}{search_icon} Search history"), true),
         InputMode::PackageResults => (
             format!("{title_prefix}{search_icon} Package results"),
             false,
@@ -40,6 +41,7 @@ pub fn draw_search_panel(frame: &mut ratatui::Frame, area: Rect, app: &App) {
 
     let search_value = match app.input_mode {
         InputMode::PackageSearch | InputMode::PackageResults => &app.package_query,
+        InputMode::SearchHistory => &app.history_query,
         _ => &app.leaves_query,
     };
 
@@ -47,7 +49,9 @@ pub fn draw_search_panel(frame: &mut ratatui::Frame, area: Rect, app: &App) {
         if is_active {
             let hint = match app.input_mode {
                 InputMode::PackageSearch => "type to search... (Enter to search, Esc to cancel)",
-                InputMode::SearchLeaves => "type to filter... (Enter to browse, Esc to clear)",
+                InputMode::SearchLeaves | InputMode::SearchHistory => {
+                    "type to filter... (Enter to browse, Esc to clear)"
+                }
                 _ => "type to filter...",
             };
             Span::styled(hint, Style::default().fg(theme.text_muted))
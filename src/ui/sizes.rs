@@ -4,7 +4,12 @@ use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Paragraph};
 
 use crate::app::App;
-use crate::ui::util::format_size;
+use crate::keymap::Action;
+use crate::ui::util::{format_size, format_size_delta, sparkline};
+
+/// Number of scans shown in each sparkline.
+const SPARKLINE_POINTS: usize = 12;
+const TOP_MOVERS: usize = 3;
 
 pub fn draw_sizes_panel(frame: &mut ratatui::Frame, area: Rect, app: &App, is_focused: bool) {
     let theme = &app.theme;
@@ -30,29 +35,51 @@ pub fn draw_sizes_panel(frame: &mut ratatui::Frame, area: Rect, app: &App, is_fo
         vec![
             Line::from(""),
             Line::from(Span::styled(
-                "  Press 's' to load sizes",
+                format!(
+                    "  Press '{}' to load sizes",
+                    app.key_hint(Action::LoadSizes)
+                ),
                 Style::default().fg(theme.text_muted),
             )),
         ]
     } else {
-        app.sizes
-            .iter()
-            .take(20)
-            .skip(app.sizes_scroll_offset)
-            .take(8)
-            .map(|entry| {
-                Line::from(vec![
-                    Span::styled(
+        let baseline = app.size_baseline;
+        let deltas = app.size_history.deltas_kb(baseline);
+        let movers = app.size_history.top_movers(baseline, TOP_MOVERS);
+
+        let mut lines = vec![build_total_line(app)];
+        lines.extend(
+            movers
+                .iter()
+                .map(|name| build_mover_line(app, name, deltas[name])),
+        );
+        // Borders, the total line and the movers take the rest of the panel.
+        let rows = usize::from(area.height.saturating_sub(3)).saturating_sub(movers.len());
+        lines.extend(
+            app.sizes
+                .iter()
+                .take(20)
+                .skip(app.sizes_scroll_offset)
+                .take(rows)
+                .map(|entry| {
+                    let mut spans = vec![Span::styled(
                         format!("  {:>6}", format_size(entry.size_kb)),
                         Style::default().fg(theme.yellow),
-                    ),
-                    Span::styled(
+                    )];
+                    if let Some(delta) = deltas.get(&entry.name).filter(|delta| **delta != 0) {
+                        spans.push(Span::styled(
+                            format!(" {:>7}", format_size_delta(*delta)),
+                            Style::default().fg(delta_color(app, *delta)),
+                        ));
+                    }
+                    spans.push(Span::styled(
                         format!("  {}", entry.name),
                         Style::default().fg(theme.text_primary),
-                    ),
-                ])
-            })
-            .collect()
+                    ));
+                    Line::from(spans)
+                }),
+        );
+        lines
     };
 
     let block = Block::default()
@@ -71,3 +98,71 @@ pub fn draw_sizes_panel(frame: &mut ratatui::Frame, area: Rect, app: &App, is_fo
         .style(Style::default().bg(theme.bg_panel));
     frame.render_widget(paragraph, area);
 }
+
+fn build_total_line(app: &App) -> Line<'static> {
+    let theme = &app.theme;
+    let total_kb: u64 = app.sizes.iter().map(|entry| entry.size_kb).sum();
+    let mut spans = vec![Span::styled(
+        format!("  Total {:>6}", format_size(total_kb)),
+        Style::default()
+            .fg(theme.text_primary)
+            .add_modifier(Modifier::BOLD),
+    )];
+
+    let series = app.size_history.total_series();
+    if series.len() > 1 {
+        spans.push(Span::styled(
+            format!("  {}", sparkline(app, &recent(series))),
+            Style::default().fg(theme.accent_secondary),
+        ));
+    }
+
+    let delta_label = match app.size_history.total_delta_kb(app.size_baseline) {
+        Some(delta) => Span::styled(
+            format!(
+                "  {} since {}",
+                format_size_delta(delta),
+                app.size_baseline.label()
+            ),
+            Style::default().fg(delta_color(app, delta)),
+        ),
+        None => Span::styled("  no earlier scan", Style::default().fg(theme.text_muted)),
+    };
+    spans.push(delta_label);
+    Line::from(spans)
+}
+
+/// One of the largest changes since the baseline, with its own history.
+fn build_mover_line(app: &App, name: &str, delta_kb: i64) -> Line<'static> {
+    let theme = &app.theme;
+    Line::from(vec![
+        Span::styled(
+            format!("  {:>7}", format_size_delta(delta_kb)),
+            Style::default().fg(delta_color(app, delta_kb)),
+        ),
+        Span::styled(format!("  {name}"), Style::default().fg(theme.text_primary)),
+        Span::styled(
+            format!(
+                " {}",
+                sparkline(app, &recent(app.size_history.package_series(name)))
+            ),
+            Style::default().fg(theme.accent_secondary),
+        ),
+    ])
+}
+
+fn delta_color(app: &App, delta_kb: i64) -> ratatui::style::Color {
+    if delta_kb > 0 {
+        app.theme.orange
+    } else if delta_kb < 0 {
+        app.theme.green
+    } else {
+        app.theme.text_muted
+    }
+}
+
+fn recent(mut series: Vec<u64>) -> Vec<u64> {
+    let skip = series.len().saturating_sub(SPARKLINE_POINTS);
+    series.drain(..skip);
+    series
+}
@@ -3,9 +3,13 @@ use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Paragraph};
 
-use crate::app::{App, StatusTab, ToastLevel};
-use crate::brew::{CommandKind, StatusSnapshot};
-use crate::ui::util::symbol;
+use crate::app::{App, HistoryOutcome, StatusTab, ToastLevel};
+use crate::brew::{
+    CommandKind, DeprecationState, IssueSeverity, ServiceRegistration, StatusSnapshot,
+};
+use crate::keymap::Action;
+use crate::storage::format_timestamp;
+use crate::ui::util::{format_elapsed, symbol};
 
 type StatusLine = (String, Color);
 
@@ -63,17 +67,27 @@ pub fn draw_status_panel(frame: &mut ratatui::Frame, area: Rect, app: &App, is_f
     let theme = &app.theme;
     let mut lines = Vec::new();
 
-    if app.pending_status {
+    if app.status_tab == StatusTab::History {
+        append_history_filter_line(app, &mut lines);
+        let scroll_items = build_history_items(app);
+        append_scrolled_lines(app, &mut lines, &scroll_items);
+    } else if app.pending_status && !app.stale.status {
         lines.push(Line::from(Span::styled(
             "  Checking status...",
             Style::default().fg(theme.text_muted),
         )));
     } else if let Some(system_status) = &app.system_status {
+        if app.status_tab == StatusTab::Issues {
+            append_issues_summary_line(app, system_status, &mut lines);
+        }
         let scroll_items = build_tab_items(app, system_status);
         append_scrolled_lines(app, &mut lines, &scroll_items);
     } else {
         lines.push(Line::from(Span::styled(
-            "  Press 'h' for status check",
+            format!(
+                "  Press '{}' for status check",
+                app.key_hint(Action::StatusCheck)
+            ),
             Style::default().fg(theme.text_muted),
         )));
     }
@@ -118,6 +132,26 @@ pub fn draw_status_panel(frame: &mut ratatui::Frame, area: Rect, app: &App, is_f
     frame.render_widget(paragraph, area);
 }
 
+fn append_issues_summary_line(
+    app: &App,
+    system_status: &StatusSnapshot,
+    lines: &mut Vec<Line<'_>>,
+) {
+    let count = system_status.doctor_issues.len();
+    if count == 0 {
+        return;
+    }
+    let noun = if count == 1 { "issue" } else { "issues" };
+    lines.push(Line::from(Span::styled(
+        format!(
+            "  {} {count} {noun} from brew doctor, [{}] inspect",
+            symbol(app, "ℹ", "i"),
+            app.key_hint(Action::LoadDetails)
+        ),
+        Style::default().fg(app.theme.text_muted),
+    )));
+}
+
 fn build_tab_items(app: &App, system_status: &StatusSnapshot) -> Vec<StatusLine> {
     match app.status_tab {
         StatusTab::Activity => build_activity_items(app, system_status),
@@ -146,18 +180,71 @@ fn build_outdated_items(app: &App, system_status: &StatusSnapshot) -> Vec<Status
 
 fn build_issues_items(app: &App, system_status: &StatusSnapshot) -> Vec<StatusLine> {
     let theme = &app.theme;
-    if system_status.doctor_issues.is_empty() {
+    let deprecated = app.deprecated_packages();
+    if system_status.doctor_issues.is_empty() && deprecated.is_empty() {
         return vec![(
             format!("{} No issues found", symbol(app, "✓", "ok")),
             theme.green,
         )];
     }
 
-    system_status
+    let marker = |index: usize| {
+        if app.issues_selected_index == Some(index) {
+            symbol(app, "▸", ">")
+        } else {
+            " "
+        }
+    };
+    let mut items: Vec<StatusLine> = system_status
         .doctor_issues
         .iter()
-        .map(|issue| (issue.clone(), theme.yellow))
-        .collect()
+        .enumerate()
+        .map(|(index, issue)| {
+            let (icon, color) = match issue.severity {
+                IssueSeverity::Warning => (symbol(app, "⚠", "!"), theme.yellow),
+                IssueSeverity::Error => (symbol(app, "✗", "x"), theme.red),
+            };
+            (format!("{} {icon} {}", marker(index), issue.title), color)
+        })
+        .collect();
+    if items.is_empty() {
+        items.push((
+            format!("{} brew doctor found no issues", symbol(app, "✓", "ok")),
+            theme.green,
+        ));
+    }
+    if deprecated.is_empty() {
+        return items;
+    }
+
+    items.push((
+        format!("Deprecated & disabled ({})", deprecated.len()),
+        theme.text_secondary,
+    ));
+    let offset = system_status.doctor_issues.len();
+    items.extend(deprecated.iter().enumerate().map(|(index, entry)| {
+        let (icon, color) = match entry.deprecation.state {
+            DeprecationState::Deprecated => (symbol(app, "⚠", "!"), theme.yellow),
+            DeprecationState::Disabled => (symbol(app, "✗", "x"), theme.red),
+        };
+        let kind = if entry.cask { " (cask)" } else { "" };
+        let replacement = entry
+            .deprecation
+            .replacement
+            .as_deref()
+            .map(|name| format!(" {} {name}", symbol(app, "→", "->")))
+            .unwrap_or_default();
+        (
+            format!(
+                "{} {icon} {}{kind} {}{replacement}",
+                marker(offset + index),
+                entry.name,
+                entry.deprecation.state.label()
+            ),
+            color,
+        )
+    }));
+    items
 }
 
 fn build_services_items(app: &App, system_status: &StatusSnapshot) -> Vec<StatusLine> {
@@ -204,9 +291,14 @@ fn build_services_items(app: &App, system_status: &StatusSnapshot) -> Vec<Status
                 .unwrap_or_else(|| "-".to_string());
             (
                 format!(
-                    "{marker} {} ({}, exit {exit_label}, {backend})",
+                    "{marker} {} ({}{}, exit {exit_label}, {backend})",
                     service.name,
                     service.state_label(),
+                    if service.registration() == ServiceRegistration::Session {
+                        ", session only"
+                    } else {
+                        ""
+                    },
                 ),
                 status_color,
             )
@@ -223,30 +315,48 @@ fn build_history_items(app: &App) -> Vec<StatusLine> {
         )];
     }
 
-    app.command_history
+    let filtered_indices = app.filtered_history_indices();
+    if filtered_indices.is_empty() {
+        return vec![(
+            format!(
+                "{} No commands match ({})",
+                symbol(app, "i", "i"),
+                app.history_filter_summary()
+            ),
+            theme.text_muted,
+        )];
+    }
+
+    filtered_indices
         .iter()
-        .map(|entry| {
-            let prefix = if entry.success {
+        .map(|index| {
+            let record = &app.command_history[*index];
+            let marker = if app.history_selected_index == Some(*index) {
+                symbol(app, "▸", ">")
+            } else {
+                " "
+            };
+            let prefix = if record.success {
                 symbol(app, "✓", "ok")
             } else {
                 symbol(app, "✗", "x")
             };
-            let color = if entry.success {
+            let color = if record.success {
                 theme.green
             } else {
                 theme.red
             };
-            let exit_label = entry
+            let exit_label = record
                 .exit_code
                 .map(|code| code.to_string())
                 .unwrap_or_else(|| "n/a".to_string());
             (
                 format!(
-                    "{prefix} [{}] {} (exit {exit_label}, {}s, {}s ago)",
-                    entry.kind,
-                    entry.command,
-                    entry.duration_secs,
-                    entry.finished_at.elapsed().as_secs()
+                    "{marker} {prefix} {} [{}] {} (exit {exit_label}, {})",
+                    format_timestamp(record.started_at),
+                    record.kind,
+                    record.command_line(),
+                    record.duration_label(),
                 ),
                 color,
             )
@@ -254,6 +364,25 @@ fn build_history_items(app: &App) -> Vec<StatusLine> {
         .collect()
 }
 
+/// Active search and filters above the history list.
+fn append_history_filter_line(app: &App, lines: &mut Vec<Line<'_>>) {
+    if app.history_query.trim().is_empty()
+        && app.history_kind_filter.is_none()
+        && app.history_outcome_filter == HistoryOutcome::All
+    {
+        return;
+    }
+
+    lines.push(Line::from(Span::styled(
+        format!(
+            "  {} Showing {}",
+            symbol(app, "ℹ", "i"),
+            app.history_filter_summary()
+        ),
+        Style::default().fg(app.theme.text_muted),
+    )));
+}
+
 fn build_activity_items(app: &App, system_status: &StatusSnapshot) -> Vec<StatusLine> {
     let mut items = Vec::new();
 
@@ -348,11 +477,16 @@ fn build_pending_command_items(app: &App) -> Option<Vec<StatusLine>> {
         Some(CommandKind::Uninstall) => "Uninstalling",
         Some(CommandKind::Upgrade) => "Upgrading",
         Some(CommandKind::UpgradeAll) => "Upgrading outdated packages",
-        Some(CommandKind::ServiceStart) => "Starting service",
-        Some(CommandKind::ServiceStop) => "Stopping service",
-        Some(CommandKind::ServiceRestart) => "Restarting service",
+        Some(CommandKind::ServiceStart) => "Starting",
+        Some(CommandKind::ServiceStop) => "Stopping",
+        Some(CommandKind::ServiceRestart) => "Restarting",
+        Some(CommandKind::ServiceRun) => "Running (this session only)",
+        Some(CommandKind::ServiceKill) => "Killing",
+        Some(CommandKind::ServiceCleanup) => "Cleaning up unused services",
         Some(CommandKind::ServiceInfo) => "Loading service info",
         Some(CommandKind::SelfUpdate) => "Updating Brewery",
+        Some(CommandKind::DoctorFix) => "Running suggested fix",
+        Some(CommandKind::Update) => "Updating Homebrew",
         _ => "Running",
     };
     let label = app
@@ -367,11 +501,7 @@ fn build_pending_command_items(app: &App) -> Option<Vec<StatusLine>> {
 
     let mut items = vec![(format!("{label} ({elapsed})"), theme.accent)];
     if let Some(kind) = app.last_command {
-        let binary = if kind == CommandKind::SelfUpdate {
-            "cargo"
-        } else {
-            "brew"
-        };
+        let binary = kind.binary();
         let args = app.last_command_args.join(" ");
         let command_text = if args.is_empty() {
             binary.to_string()
@@ -403,6 +533,8 @@ fn build_recent_completion_items(app: &App) -> Option<Vec<StatusLine>> {
         CommandKind::ServiceStart => "Service start",
         CommandKind::ServiceStop => "Service stop",
         CommandKind::ServiceRestart => "Service restart",
+        CommandKind::ServiceRun => "Service run",
+        CommandKind::ServiceKill => "Service kill",
         _ => "Command",
     };
     Some(vec![(format!("{verb} completed: {pkg}"), theme.green)])
@@ -488,6 +620,12 @@ fn build_status_snapshot_items(app: &App, system_status: &StatusSnapshot) -> Vec
             theme.text_muted,
         ));
     }
+    if let Some(summary) = app.last_update_summary.as_ref() {
+        items.push((
+            format!("Update brought: {}", summary.headline()),
+            theme.text_secondary,
+        ));
+    }
 
     if let Some(t) = app.last_status_check {
         items.push((
@@ -597,19 +735,6 @@ fn spinner_frame(app: &App) -> &'static str {
     }
 }
 
-fn format_elapsed(secs: u64) -> String {
-    if secs < 60 {
-        return format!("{secs}s");
-    }
-    if secs < 3600 {
-        return format!("{}m", secs / 60);
-    }
-    if secs < 86_400 {
-        return format!("{}h", secs / 3600);
-    }
-    format!("{}d", secs / 86_400)
-}
-
 fn text_width(value: &str) -> u16 {
     value.chars().count() as u16
 }
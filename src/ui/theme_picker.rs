@@ -0,0 +1,64 @@
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, List, ListItem, ListState};
+
+use crate::app::App;
+use crate::keymap::Action;
+use crate::ui::layout;
+use crate::ui::util::symbol;
+
+/// Drawn in a corner without dimming so the previewed theme stays visible.
+pub fn draw_theme_picker(frame: &mut ratatui::Frame, app: &App) {
+    let Some(picker) = app.theme_picker.as_ref() else {
+        return;
+    };
+    let theme = &app.theme;
+    let popup_area = layout::theme_picker_area(frame.area(), picker.options.len());
+
+    frame.render_widget(Clear, popup_area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.border_active))
+        .style(Style::default().bg(theme.bg_main))
+        .title(Span::styled(
+            " Theme ",
+            Style::default()
+                .fg(theme.accent)
+                .add_modifier(Modifier::BOLD),
+        ))
+        .title_bottom(Line::from(Span::styled(
+            format!(" Enter apply - {} cancel ", app.key_hint(Action::Cancel)),
+            Style::default().fg(theme.text_muted),
+        )));
+
+    let items: Vec<ListItem> = picker
+        .options
+        .iter()
+        .map(|name| {
+            let current = if name == picker.previous_mode.label() {
+                symbol(app, " ✓", " *")
+            } else {
+                ""
+            };
+            ListItem::new(Line::from(vec![
+                Span::styled(format!(" {name}"), Style::default().fg(theme.text_primary)),
+                Span::styled(current, Style::default().fg(theme.green)),
+            ]))
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(block)
+        .highlight_style(
+            Style::default()
+                .bg(theme.bg_selection)
+                .fg(theme.accent)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol(symbol(app, "▌", "> "));
+
+    let mut state = ListState::default();
+    state.select(Some(picker.selected));
+    frame.render_stateful_widget(list, popup_area, &mut state);
+}
@@ -12,6 +12,19 @@ pub fn symbol<'a>(app: &App, nerd: &'a str, ascii: &'a str) -> &'a str {
     if app.icons_ascii { ascii } else { nerd }
 }
 
+pub fn format_elapsed(secs: u64) -> String {
+    if secs < 60 {
+        return format!("{secs}s");
+    }
+    if secs < 3600 {
+        return format!("{}m", secs / 60);
+    }
+    if secs < 86_400 {
+        return format!("{}h", secs / 3600);
+    }
+    format!("{}d", secs / 86_400)
+}
+
 pub fn format_size(size_kb: u64) -> String {
     let megabytes = size_kb as f64 / 1024.0;
     if megabytes < 1024.0 {
@@ -20,3 +33,59 @@ pub fn format_size(size_kb: u64) -> String {
     let gigabytes = megabytes / 1024.0;
     format!("{gigabytes:.1}G")
 }
+
+pub fn format_size_delta(delta_kb: i64) -> String {
+    let sign = if delta_kb < 0 { "-" } else { "+" };
+    format!("{sign}{}", format_size(delta_kb.unsigned_abs()))
+}
+
+/// Renders values as a one-row bar chart scaled between the series min and max.
+pub fn sparkline(app: &App, values: &[u64]) -> String {
+    const NERD_BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+    const ASCII_BARS: [char; 4] = ['_', '.', '-', '^'];
+    let bars: &[char] = if app.icons_ascii {
+        &ASCII_BARS
+    } else {
+        &NERD_BARS
+    };
+
+    let (Some(min), Some(max)) = (values.iter().min(), values.iter().max()) else {
+        return String::new();
+    };
+    let range = max - min;
+
+    values
+        .iter()
+        .map(|value| {
+            if range == 0 {
+                return bars[bars.len() / 2];
+            }
+            let level = (value - min) * (bars.len() as u64 - 1) / range;
+            bars[level as usize]
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{format_size_delta, sparkline};
+    use crate::app::App;
+
+    #[test]
+    fn sparkline_scales_between_min_and_max() {
+        let mut app = App::new();
+        app.icons_ascii = false;
+        assert_eq!(sparkline(&app, &[10, 20, 30]), "▁▄█");
+        assert_eq!(sparkline(&app, &[5, 5]), "▅▅");
+        assert_eq!(sparkline(&app, &[]), "");
+
+        app.icons_ascii = true;
+        assert_eq!(sparkline(&app, &[0, 3]), "_^");
+    }
+
+    #[test]
+    fn formats_signed_size_deltas() {
+        assert_eq!(format_size_delta(2048), "+2.0M");
+        assert_eq!(format_size_delta(-512), "-0.5M");
+    }
+}